@@ -50,4 +50,17 @@ fn both() {
     println!("{}", "some warning".yellow());
     // let the environment decide again
     colored::control::unset_override();
+
+    // this will never blink, even though it was asked to
+    colored::control::disable_style(Styles::Blink);
+    println!("{}", "some warning".yellow().blink());
+    colored::control::enable_style(Styles::Blink);
+
+    // every blue will be rendered as bright blue
+    colored::control::set_color_map(|color| match color {
+        Color::Blue => Color::BrightBlue,
+        color => color,
+    });
+    println!("{}", "some info".blue());
+    colored::control::unset_color_map();
 }
@@ -9,6 +9,6 @@ fn main() {
     "white string".color("zorglub");
 
     // the safer way via a Result
-    let color_res = "zorglub".parse(); // <- this returns a Result<Color, ()>
+    let color_res = "zorglub".parse(); // <- this returns a Result<Color, ParseColorError>
     "red string".color(color_res.unwrap_or(Color::Red));
 }
@@ -0,0 +1,262 @@
+//! Compile-time macros for the [`colored`](https://docs.rs/colored) crate,
+//! re-exported by it with the `embed-theme` feature.
+
+use std::{env, fs, path::PathBuf};
+
+use proc_macro::{TokenStream, TokenTree};
+
+/// Parses a theme file at compile time into a `&'static Theme`, see
+/// `colored::embed_theme`
+#[proc_macro]
+pub fn embed_theme(input: TokenStream) -> TokenStream {
+    let expanded = match theme_path(input).and_then(|path| expand(&path)) {
+        Ok(code) => code,
+        Err(msg) => format!("::core::compile_error!({msg:?})"),
+    };
+    expanded.parse().expect("generated code is valid")
+}
+
+/// The path of the theme file, relative to the manifest of the crate using
+/// the macro
+fn theme_path(input: TokenStream) -> Result<PathBuf, String> {
+    let mut tokens = input.into_iter();
+    let literal = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(literal)), None) => literal.to_string(),
+        _ => return Err("embed_theme! expects a single string literal".to_owned()),
+    };
+    let path = literal
+        .strip_prefix('"')
+        .and_then(|l| l.strip_suffix('"'))
+        .filter(|path| !path.contains('\\'))
+        .ok_or("embed_theme! expects a plain string literal")?;
+
+    let root = env::var_os("CARGO_MANIFEST_DIR").map_or_else(PathBuf::new, PathBuf::from);
+    Ok(root.join(path))
+}
+
+/// The code of a `&'static Theme` with the styles of the file at `path`
+fn expand(path: &PathBuf) -> Result<String, String> {
+    let src = fs::read_to_string(path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    let styles = parse_theme(&src).map_err(|e| format!("{}:{e}", path.display()))?;
+
+    let mut sets = String::new();
+    for (name, spec) in styles {
+        sets.push_str(&format!("theme.set({name:?}, {spec});"));
+    }
+    // `include_bytes!` makes the compiler rebuild when the theme changes
+    Ok(format!(
+        "{{
+            const _: &[u8] = ::core::include_bytes!({path:?});
+            static THEME: ::colored::__private::LazyLock<::colored::theme::Theme> =
+                ::colored::__private::LazyLock::new(|| {{
+                    let mut theme = ::colored::theme::Theme::empty();
+                    {sets}
+                    theme
+                }});
+            &*THEME
+        }}",
+        path = path.display().to_string(),
+    ))
+}
+
+/// Parses the TOML subset of theme files: `[table]` headers, `name = "style"`
+/// entries and `#` comments. The names of a table are prefixed with the
+/// table name and a dot, and the styles use the syntax of `git config`.
+fn parse_theme(src: &str) -> Result<Vec<(String, String)>, String> {
+    let mut table = String::new();
+    let mut styles = Vec::new();
+    for (i, line) in src.lines().enumerate() {
+        let at = |msg: String| format!("{}: {msg}", i + 1);
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let name = header
+                .strip_suffix(']')
+                .map(str::trim)
+                .filter(|name| is_key(name))
+                .ok_or_else(|| at(format!("invalid table header `{line}`")))?;
+            table = format!("{name}.");
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| at(format!("expected `name = \"style\"`, found `{line}`")))?;
+        let key = key.trim();
+        let key = unquote(key).unwrap_or(key);
+        if !is_key(key) {
+            return Err(at(format!("invalid name `{key}`")));
+        }
+        let value = unquote(value.trim()).ok_or_else(|| at(format!("the style of `{key}` is not a string")))?;
+        styles.push((format!("{table}{key}"), spec_code(value).map_err(at)?));
+    }
+    Ok(styles)
+}
+
+/// The line without a trailing `#` comment, unless it is inside a string
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {},
+        }
+    }
+    line
+}
+
+/// The contents of a string without escapes
+fn unquote(s: &str) -> Option<&str> {
+    s.strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .filter(|s| !s.contains(['"', '\\']))
+}
+
+/// Whether `s` is a bare or dotted key
+fn is_key(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// The code of the `StyleSpec` of a style written with the syntax of
+/// `git config`, mirroring `StyleSpec::from_git`
+fn spec_code(src: &str) -> Result<String, String> {
+    let mut fg = None;
+    let mut bg = None;
+    let mut colors = 0_u8;
+    let mut styles = Vec::new();
+    for word in src.split_whitespace() {
+        let lower = word.to_lowercase();
+        if let Some(style) = attribute(&lower) {
+            if !styles.contains(&style) {
+                styles.push(style);
+            }
+        } else if let Some(style) = lower
+            .strip_prefix("no-")
+            .or_else(|| lower.strip_prefix("no"))
+            .and_then(attribute)
+        {
+            styles.retain(|&s| s != style);
+        } else if lower == "reset" {
+            (fg, bg) = (None, None);
+            styles.clear();
+        } else {
+            let invalid = || format!("{word} is an invalid git color or attribute");
+            let color = color_code(&lower).ok_or_else(invalid)?;
+            match (colors, color) {
+                (0, Some(color)) => fg = Some(color),
+                (1, Some(color)) => bg = Some(color),
+                (0 | 1, None) => {},
+                _ => return Err(invalid()),
+            }
+            colors += 1;
+        }
+    }
+
+    let mut code = "::colored::StyleSpec::new()".to_owned();
+    if let Some(fg) = fg {
+        code.push_str(&format!(".fg({fg})"));
+    }
+    if let Some(bg) = bg {
+        code.push_str(&format!(".bg({bg})"));
+    }
+    for style in styles {
+        code.push_str(&format!(".with(::colored::Styles::{style})"));
+    }
+    Ok(code)
+}
+
+/// The `Styles` variant of a `git config` attribute
+fn attribute(word: &str) -> Option<&'static str> {
+    Some(match word {
+        "bold" => "Bold",
+        "dim" => "Dimmed",
+        "italic" => "Italic",
+        "ul" => "Underline",
+        "blink" => "Blink",
+        "reverse" => "Reversed",
+        "strike" => "Strikethrough",
+        _ => return None,
+    })
+}
+
+/// The code of a `git config` color, `Some(None)` for no color
+fn color_code(word: &str) -> Option<Option<String>> {
+    if word == "normal" || word == "default" {
+        return Some(None);
+    }
+    if let Some(hex) = word.strip_prefix('#') {
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let hex = match hex.len() {
+            3 => hex.chars().flat_map(|c| [c, c]).collect(),
+            6 | 8 => hex.to_owned(),
+            _ => return None,
+        };
+        let channel = |i: usize| hex.get(i..i + 2).and_then(|c| u8::from_str_radix(c, 16).ok());
+        let (r, g, b) = (channel(0)?, channel(2)?, channel(4)?);
+        return Some(Some(format!("::colored::Color::TrueColor {{ r: {r}, g: {g}, b: {b} }}")));
+    }
+    if let Ok(index) = word.parse::<u8>() {
+        return Some(Some(format!("::colored::Color::Ansi256({index})")));
+    }
+
+    let (bright, name) = match word.strip_prefix("bright") {
+        Some(name) => ("Bright", name),
+        None => ("", word),
+    };
+    let name = match name {
+        "black" => "Black",
+        "red" => "Red",
+        "green" => "Green",
+        "yellow" => "Yellow",
+        "blue" => "Blue",
+        "magenta" | "purple" => "Magenta",
+        "cyan" => "Cyan",
+        "white" => "White",
+        _ => return None,
+    };
+    Some(Some(format!("::colored::Color::{bright}{name}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_and_comments() {
+        let src = "# dark theme\nok = \"green\"\n\n[severity]\nwarn = \"yellow bold\" # loud\n\"error\" = \"#ff0000\"\n";
+        let styles = parse_theme(src).unwrap();
+        let names: Vec<_> = styles.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["ok", "severity.warn", "severity.error"]);
+        assert_eq!(
+            styles[1].1,
+            "::colored::StyleSpec::new().fg(::colored::Color::Yellow).with(::colored::Styles::Bold)"
+        );
+    }
+
+    #[test]
+    fn git_syntax() {
+        assert_eq!(
+            spec_code("ul brightblue 208").unwrap(),
+            "::colored::StyleSpec::new().fg(::colored::Color::BrightBlue).bg(::colored::Color::Ansi256(208)).with(::colored::Styles::Underline)"
+        );
+        assert_eq!(spec_code("bold nobold normal").unwrap(), "::colored::StyleSpec::new()");
+        assert!(spec_code("red blue green").is_err());
+        assert!(spec_code("redd").is_err());
+        assert!(spec_code("#ff00").is_err());
+        assert_eq!(spec_code("#f80").unwrap(), spec_code("#ff880080").unwrap());
+    }
+
+    #[test]
+    fn errors_have_line_numbers() {
+        assert_eq!(parse_theme("ok = \"green\"\nerr = \"rde\"").unwrap_err(), "2: rde is an invalid git color or attribute");
+        assert!(parse_theme("ok = green").is_err());
+        assert!(parse_theme("[bad table").is_err());
+        assert!(parse_theme("just words").is_err());
+    }
+}
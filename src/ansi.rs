@@ -0,0 +1,257 @@
+//! Scanning text for escape sequences, e.g. to process the colored output of
+//! a child process.
+//!
+//! ```rust
+//! # use colored::{ansi::{AnsiEvent, SgrParser}, *};
+//! let events: Vec<_> = SgrParser::new("\x1B[1;31merror\x1B[0m\x1B]0;title\x07").collect();
+//! assert_eq!(events, [
+//!     AnsiEvent::Sgr("1;31"),
+//!     AnsiEvent::Text("error"),
+//!     AnsiEvent::Sgr("0"),
+//!     AnsiEvent::Escape("\x1B]0;title\x07"),
+//! ]);
+//! ```
+
+use std::ops::{Index, Range};
+
+/// The escape character, starting every sequence
+const ESC: u8 = 0x1B;
+
+/// A piece of text scanned by a [`SgrParser`], either a `str` or bytes
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub enum AnsiEvent<'a, T: ?Sized = str> {
+    /// Text without any escape sequence, control characters included
+    Text(&'a T),
+    /// The parameters of an SGR sequence setting colors and styles, e.g.
+    /// `1;31` for `ESC [ 1 ; 31 m`
+    Sgr(&'a T),
+    /// Any other escape sequence, whole: cursor movements, OSC sequences
+    /// such as titles and hyperlinks, etc.
+    Escape(&'a T),
+}
+
+/// An iterator over the text runs and escape sequences of a `str` or of
+/// bytes, without copying them.
+///
+/// A parser created with [`SgrParser::partial`] stops before an escape
+/// sequence which is cut at the end of the input, so that input can be
+/// processed chunk by chunk: what remains in [`SgrParser::pending`] is
+/// prepended to the next chunk.
+///
+/// ```rust
+/// # use colored::ansi::{AnsiEvent, SgrParser};
+/// let mut parser = SgrParser::partial("ok\x1B[3");
+/// assert_eq!(parser.next(), Some(AnsiEvent::Text("ok")));
+/// assert_eq!(parser.next(), None);
+///
+/// let next = format!("{}2mgreen", parser.pending());
+/// let events: Vec<_> = SgrParser::partial(&next).collect();
+/// assert_eq!(events, [AnsiEvent::Sgr("32"), AnsiEvent::Text("green")]);
+/// ```
+#[derive(Debug)]
+pub struct SgrParser<'a, T: ?Sized = str> {
+    /// The input
+    input:   &'a T,
+    /// Where the next event starts
+    pos:     usize,
+    /// Whether a cut escape sequence at the end is left pending
+    partial: bool,
+}
+
+// derived, these would require `T: Clone`, which `str` and `[u8]` are not
+impl<T: ?Sized> Clone for AnsiEvent<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for AnsiEvent<'_, T> {}
+
+impl<T: ?Sized> Clone for SgrParser<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            input:   self.input,
+            pos:     self.pos,
+            partial: self.partial,
+        }
+    }
+}
+
+impl<'a> SgrParser<'a, str> {
+    /// Scan `input`, an escape sequence cut at its end being returned as an
+    /// [`AnsiEvent::Escape`]
+    #[inline]
+    #[must_use]
+    pub const fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            pos: 0,
+            partial: false,
+        }
+    }
+
+    /// Scan `input`, stopping before an escape sequence cut at its end
+    #[inline]
+    #[must_use]
+    pub const fn partial(input: &'a str) -> Self {
+        Self {
+            input,
+            pos: 0,
+            partial: true,
+        }
+    }
+}
+
+impl<'a> SgrParser<'a, [u8]> {
+    /// Scan the bytes `input`, see [`SgrParser::new`]. Bytes which are not
+    /// UTF-8 are given as they are in the text runs.
+    #[inline]
+    #[must_use]
+    pub const fn from_bytes(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            pos: 0,
+            partial: false,
+        }
+    }
+
+    /// Scan the bytes `input`, stopping before an escape sequence cut at its
+    /// end, see [`SgrParser::partial`]
+    #[inline]
+    #[must_use]
+    pub const fn partial_bytes(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            pos: 0,
+            partial: true,
+        }
+    }
+}
+
+impl<'a, T> SgrParser<'a, T>
+where
+    T: ?Sized + AsRef<[u8]> + Index<Range<usize>, Output = T>,
+{
+    /// The input which was not scanned yet, e.g. an escape sequence cut at
+    /// the end of a partial input once the iteration is over
+    #[inline]
+    #[must_use]
+    pub fn pending(&self) -> &'a T {
+        let input = self.input;
+        &input[self.pos..input.as_ref().len()]
+    }
+}
+
+impl<'a, T> Iterator for SgrParser<'a, T>
+where
+    T: ?Sized + AsRef<[u8]> + Index<Range<usize>, Output = T>,
+{
+    type Item = AnsiEvent<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = self.input;
+        let bytes = input.as_ref();
+        let start = self.pos;
+        if start >= bytes.len() {
+            return None;
+        }
+
+        if bytes[start] != ESC {
+            let end = bytes[start..]
+                .iter()
+                .position(|&b| b == ESC)
+                .map_or(bytes.len(), |len| start + len);
+            self.pos = end;
+            return Some(AnsiEvent::Text(&input[start..end]));
+        }
+
+        let Some((len, is_sgr)) = escape_len(&bytes[start..]) else {
+            if self.partial {
+                return None;
+            }
+            self.pos = bytes.len();
+            return Some(AnsiEvent::Escape(&input[start..bytes.len()]));
+        };
+        self.pos = start + len;
+        Some(if is_sgr {
+            AnsiEvent::Sgr(&input[start + 2..start + len - 1])
+        } else {
+            AnsiEvent::Escape(&input[start..start + len])
+        })
+    }
+}
+
+/// The length of the escape sequence at the start of `bytes` and whether it
+/// is an SGR sequence, `None` if it is cut
+fn escape_len(bytes: &[u8]) -> Option<(usize, bool)> {
+    match *bytes.get(1)? {
+        // CSI, until its final byte
+        b'[' => {
+            let end = 2 + bytes[2..].iter().position(|b| (0x40..=0x7E).contains(b))?;
+            let is_sgr = bytes[end] == b'm'
+                && bytes[2..end].iter().all(|&b| b.is_ascii_digit() || b == b';' || b == b':');
+            Some((end + 1, is_sgr))
+        },
+        // strings terminated by BEL or ST
+        b']' | b'P' | b'X' | b'^' | b'_' => {
+            let mut i = 2;
+            loop {
+                match *bytes.get(i)? {
+                    0x07 => return Some((i + 1, false)),
+                    ESC if *bytes.get(i + 1)? == b'\\' => return Some((i + 2, false)),
+                    _ => i += 1,
+                }
+            }
+        },
+        // two-character sequences, the second one possibly not ASCII
+        _ => {
+            let len = 2 + bytes[2..].iter().take_while(|&&b| b & 0xC0 == 0x80).count();
+            Some((len, false))
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events() {
+        let events: Vec<_> = SgrParser::new("a\x1B[?25lb\x1B[38:2::1:2:3mc\x1B7\x1BPq\x1B\\").collect();
+        assert_eq!(events, [
+            AnsiEvent::Text("a"),
+            AnsiEvent::Escape("\x1B[?25l"),
+            AnsiEvent::Text("b"),
+            AnsiEvent::Sgr("38:2::1:2:3"),
+            AnsiEvent::Text("c"),
+            AnsiEvent::Escape("\x1B7"),
+            AnsiEvent::Escape("\x1BPq\x1B\\"),
+        ]);
+        assert_eq!(SgrParser::new("\x1B[m").collect::<Vec<_>>(), [AnsiEvent::Sgr("")]);
+        assert_eq!(SgrParser::new("\x1Bé").collect::<Vec<_>>(), [AnsiEvent::Escape("\x1Bé")]);
+    }
+
+    #[test]
+    fn cut_sequences() {
+        for cut in ["\x1B", "\x1B[1;3", "\x1B]8;;http", "\x1B]0;t\x1B"] {
+            let input = format!("x{cut}");
+            assert_eq!(SgrParser::new(&input).last(), Some(AnsiEvent::Escape(cut)));
+
+            let mut parser = SgrParser::partial(&input);
+            assert_eq!(parser.next(), Some(AnsiEvent::Text("x")));
+            assert_eq!(parser.next(), None);
+            assert_eq!(parser.pending(), cut);
+        }
+    }
+
+    #[test]
+    fn bytes() {
+        let mut parser = SgrParser::partial_bytes(b"\xFF\x1B[1m\x1B[");
+        assert_eq!(parser.next(), Some(AnsiEvent::Text(&b"\xFF"[..])));
+        assert_eq!(parser.next(), Some(AnsiEvent::Sgr(&b"1"[..])));
+        assert_eq!(parser.next(), None);
+        assert_eq!(parser.pending(), b"\x1B[");
+    }
+}
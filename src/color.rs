@@ -1,19 +1,33 @@
 //! Standard colors for the command line and methods regarding them
 
-use std::{borrow::Cow, cmp::Ordering, io, str::FromStr};
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Mul},
+    str::FromStr,
+};
+
+use crate::{
+    control::{self, ColorLevel, SgrSyntax},
+    ColoredString,
+    Colorize,
+    Gradient,
+    Palette,
+};
 
-// TODO: Add 256-ANSI support
 #[cfg(feature = "serde")]
 use serde_crate::{Deserialize, Serialize};
 
 /// The 8 standard colors.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate")
 )]
 #[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[non_exhaustive]
 pub enum Color {
     Black,
     Red,
@@ -31,7 +45,11 @@ pub enum Color {
     BrightMagenta,
     BrightCyan,
     BrightWhite,
+    Ansi256(u8),
     TrueColor { r: u8, g: u8, b: u8 },
+    /// The default color of the terminal, e.g. to reset only the background
+    /// inside a styled region
+    Default,
 }
 
 #[allow(missing_docs)]
@@ -57,7 +75,9 @@ impl Color {
             Self::BrightMagenta => "95".into(),
             Self::BrightCyan => "96".into(),
             Self::BrightWhite => "97".into(),
+            Self::Ansi256(index) => format!("38;5;{index}").into(),
             Self::TrueColor { r, g, b } => format!("38;2;{r};{g};{b}").into(),
+            Self::Default => "39".into(),
         }
     }
 
@@ -82,7 +102,29 @@ impl Color {
             Self::BrightMagenta => "105".into(),
             Self::BrightCyan => "106".into(),
             Self::BrightWhite => "107".into(),
+            Self::Ansi256(index) => format!("48;5;{index}").into(),
             Self::TrueColor { r, g, b } => format!("48;2;{r};{g};{b}").into(),
+            Self::Default => "49".into(),
+        }
+    }
+
+    /// Convert a [`Color`] to a string used for foreground colors, using the
+    /// given separator syntax for truecolor parameters
+    pub(crate) fn to_fg_str_in(&self, syntax: SgrSyntax) -> Cow<'static, str> {
+        match (*self, syntax) {
+            (Self::Ansi256(index), SgrSyntax::Colon) => format!("38:5:{index}").into(),
+            (Self::TrueColor { r, g, b }, SgrSyntax::Colon) => format!("38:2::{r}:{g}:{b}").into(),
+            _ => self.to_fg_str(),
+        }
+    }
+
+    /// Convert a [`Color`] to a string used for background colors, using the
+    /// given separator syntax for truecolor parameters
+    pub(crate) fn to_bg_str_in(&self, syntax: SgrSyntax) -> Cow<'static, str> {
+        match (*self, syntax) {
+            (Self::Ansi256(index), SgrSyntax::Colon) => format!("48:5:{index}").into(),
+            (Self::TrueColor { r, g, b }, SgrSyntax::Colon) => format!("48:2::{r}:{g}:{b}").into(),
+            _ => self.to_bg_str(),
         }
     }
 
@@ -111,8 +153,11 @@ impl Color {
             "95" => Some(Self::BrightMagenta),
             "96" => Some(Self::BrightCyan),
             "97" => Some(Self::BrightWhite),
+            "39" => Some(Self::Default),
             color => {
-                if color.starts_with("38;2;") {
+                if let Some(index) = color.strip_prefix("38;5;") {
+                    index.parse().ok().map(Self::Ansi256)
+                } else if color.starts_with("38;2;") {
                     let mut it = s.split(';');
                     it.next()?;
                     it.next()?;
@@ -128,6 +173,27 @@ impl Color {
         }
     }
 
+    /// Parses a [`Color`] from an *ansi* background color string, the
+    /// counterpart of [`Color::from_fg_str`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::from_bg_str("101"), Some(Color::BrightRed));
+    /// assert_eq!(Color::from_bg_str("49"), Some(Color::Default));
+    /// assert_eq!(Color::from_bg_str("31"), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_bg_str(s: &str) -> Option<Self> {
+        if let Some(rest) = s.strip_prefix("48;") {
+            return Self::from_fg_str(&format!("38;{rest}"));
+        }
+        match s.parse::<u8>().ok()? {
+            param @ (40..=47 | 49 | 100..=107) => Self::from_fg_str(&(param - 10).to_string()),
+            _ => None,
+        }
+    }
+
     /// Convert a [`Color`] to a hex array
     ///
     /// Notes:
@@ -139,6 +205,8 @@ impl Color {
     ///     as the original
     ///   - These **will not** be the same as the `fg_str`, which produces an
     ///     ansi sequence which will be interpreted by the terminal
+    ///   - [`Color::Ansi256`] colors are those of [`Palette::xterm`]
+    ///   - [`Color::Default`] is assumed to be white
     #[inline]
     #[must_use]
     pub const fn to_hex_array(&self) -> [u8; 3] {
@@ -150,7 +218,7 @@ impl Color {
             Self::Yellow => [0xFF, 0xFF, 0x00],
             Self::Magenta => [0xFF, 0x00, 0xFF],
             Self::Cyan => [0x00, 0xFF, 0xFF],
-            Self::White | Self::BrightWhite => [0xFF, 0xFF, 0xFF],
+            Self::White | Self::BrightWhite | Self::Default => [0xFF, 0xFF, 0xFF],
             Self::BrightBlack => [0x22, 0x20, 0x24],
             Self::BrightRed => [0xFF, 0x16, 0x0C],
             Self::BrightGreen => [0x32, 0xCD, 0x32],
@@ -158,6 +226,7 @@ impl Color {
             Self::BrightYellow => [0xFF, 0xFF, 0xE0],
             Self::BrightMagenta => [0xFF, 0x00, 0xCD],
             Self::BrightCyan => [0xE0, 0xFF, 0xFF],
+            Self::Ansi256(index) => Palette::xterm().get(index),
             Self::TrueColor { r, g, b } => [r, g, b],
         }
     }
@@ -176,52 +245,62 @@ impl Color {
         s
     }
 
-    /// Parses a string to a `Color::TrueColor` from *6 char notation*. If the
-    /// provided string is only 6 digits (i.e., no prefix), or starts with
-    /// `0x` or `#`, then the color is able to be parsed.
+    /// Parses a string to a `Color::TrueColor` from *6 char notation*, the
+    /// CSS *3 char* shorthand where `abc` means `aabbcc`, or the *8 char*
+    /// notation with an alpha channel, which is ignored. The digits can be
+    /// prefixed with `0x` or `#`.
     ///
-    /// Any colors like `0x1f1f1f` or `#ABBA12` or `121212` are valid.
+    /// Any colors like `0x1f1f1f` or `#ABBA12` or `121212` or `#fa0` or
+    /// `#ff880080` are valid.
+    ///
+    /// The CSS functions `rgb(255, 0, 136)`, `rgba(255, 0, 136, 0.5)` and
+    /// `hsl(210, 50%, 40%)`, as copied from the developer tools of a browser,
+    /// are also accepted. The RGB channels can be percentages, the arguments
+    /// can be separated by spaces instead of commas, and the alpha channel is
+    /// ignored.
     ///
     /// This is not to be used to parse a color word, instead use
     /// `Color::from_str`
     ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::from_hex("#fa0").unwrap(), Color::truecolor(0xFF, 0xAA, 0x00));
+    /// assert_eq!(Color::from_hex("#ff880080").unwrap(), Color::truecolor(0xFF, 0x88, 0x00));
+    /// assert_eq!(Color::from_hex("rgb(255, 0, 136)").unwrap(), Color::truecolor(255, 0, 136));
+    /// assert_eq!(Color::from_hex("rgba(100% 0% 0% / 50%)").unwrap(), Color::truecolor(255, 0, 0));
+    /// assert_eq!(Color::from_hex("hsl(210, 50%, 40%)").unwrap(), Color::truecolor(51, 102, 153));
+    /// ```
+    ///
     /// # Errors
-    /// Will produce an error if the length of the `Color` is not 6 characters
-    /// minus the hash (`#`) or hex (`0x`) prefix, or if it is not a valid hex
-    /// sequence
+    /// Will produce a [`ParseColorError`] if the length of the `Color` is not
+    /// 3, 6 or 8 characters minus the hash (`#`) or hex (`0x`) prefix, or if
+    /// it is not a valid hex sequence nor a valid `rgb()` or `hsl()` function
     #[inline]
-    pub fn from_hex<S: AsRef<str>>(color: S) -> Result<Self, io::Error> {
+    pub fn from_hex<S: AsRef<str>>(color: S) -> Result<Self, ParseColorError> {
         let color = color.as_ref();
-        /// Test whether the input is 6 characters long
-        macro_rules! if_6 {
-            ($c:ident) => {
-                ($c.len() == 6).then(|| $c)
-            };
-        }
-
-        let result = color.strip_prefix("0x").map_or_else(
-            || {
-                color
-                    .strip_prefix('#')
-                    .map_or_else(|| if_6!(color), |c| if_6!(c))
-            },
-            |c| if_6!(c),
-        );
+        if let Some((r, g, b)) = parse_css_function(color) {
+            return Ok(Self::TrueColor { r, g, b });
+        }
+        if color.contains('(') {
+            return Err(ParseColorError::BadFunction(color.trim().to_owned()));
+        }
 
-        if let Some(color) = result {
-            // hex
-            if let Some((r, g, b)) = parse_hex(color) {
-                return Ok(Self::TrueColor { r, g, b });
-            }
+        let digits = color
+            .strip_prefix("0x")
+            .or_else(|| color.strip_prefix('#'))
+            .unwrap_or(color);
+        if let Some(bad) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(ParseColorError::BadDigit(bad));
         }
 
-        Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!(
-                "{} is an invalid color and is unable to be parsed",
-                color.to_owned()
-            ),
-        ))
+        let parsed = match digits.len() {
+            3 => parse_hex(&digits.chars().flat_map(|c| [c, c]).collect::<String>()),
+            6 | 8 => parse_hex(digits),
+            _ => None,
+        };
+        parsed
+            .map(|(r, g, b)| Self::TrueColor { r, g, b })
+            .ok_or(ParseColorError::BadLength(digits.len()))
     }
 
     /// Create a new [`Color::TrueColor`] without an explicit instantiation of
@@ -237,6 +316,557 @@ impl Color {
     pub const fn truecolor(r: u8, g: u8, b: u8) -> Self {
         Self::TrueColor { r, g, b }
     }
+
+    /// A gray `percent` of the way from black (`0.0`) to white (`100.0`),
+    /// chosen for the color level of the terminal, see
+    /// [`Color::grayscale_percent_for`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// println!("{}", "secondary text".color(Color::grayscale_percent(60.0)));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn grayscale_percent(percent: f32) -> Self {
+        Self::grayscale_percent_for(percent, control::SHOULD_COLORIZE.detected_level())
+    }
+
+    /// A gray `percent` of the way from black (`0.0`) to white (`100.0`),
+    /// which renders sensibly at `level`: one of black, bright black, white
+    /// or bright white with 16 colors or less, the closest gray of the 256
+    /// color palette with 256 colors, and the exact gray with truecolors.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::grayscale_percent_for(40.0, ColorLevel::Ansi16), Color::BrightBlack);
+    /// assert_eq!(Color::grayscale_percent_for(40.0, ColorLevel::Ansi256), Color::Ansi256(241));
+    /// assert_eq!(Color::grayscale_percent_for(40.0, ColorLevel::TrueColor), Color::truecolor(102, 102, 102));
+    /// ```
+    #[must_use]
+    pub fn grayscale_percent_for(percent: f32, level: ColorLevel) -> Self {
+        let percent = if percent.is_nan() { 0.0 } else { percent.clamp(0.0, 100.0) };
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let gray = (percent * 2.55).round() as u8;
+        match level {
+            ColorLevel::None | ColorLevel::Ansi16 => match gray {
+                0..=63 => Self::Black,
+                64..=127 => Self::BrightBlack,
+                128..=191 => Self::White,
+                _ => Self::BrightWhite,
+            },
+            ColorLevel::Ansi256 => {
+                // the gray ramp from 232 to 255, and black and white from the cube
+                let ramp = (0..24).map(|i| (8 + i * 10, 232 + i)).chain([(0, 16), (255, 231)]);
+                let nearest = ramp.min_by_key(|&(level, _): &(u8, u8)| level.abs_diff(gray));
+                nearest.map_or(Self::Black, |(_, index)| Self::Ansi256(index))
+            },
+            ColorLevel::TrueColor => Self::truecolor(gray, gray, gray),
+        }
+    }
+
+    /// A block of `width` spaces with the color as background, to preview it
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::Red.swatch(3), "   ".on_red());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn swatch(self, width: usize) -> ColoredString {
+        " ".repeat(width).on_color(self)
+    }
+
+    /// Linearly interpolate between two colors, `t` going from `0.0` (`self`)
+    /// to `1.0` (`other`). The result is always a [`Color::TrueColor`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let black = Color::truecolor(0, 0, 0);
+    /// let white = Color::truecolor(255, 255, 255);
+    /// assert_eq!(black.lerp(white, 0.5), Color::truecolor(128, 128, 128));
+    /// ```
+    #[must_use]
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let [r1, g1, b1] = self.to_hex_array();
+        let [r2, g2, b2] = other.to_hex_array();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let channel = |a: u8, b: u8| (f32::from(b) - f32::from(a)).mul_add(t, f32::from(a)).round() as u8;
+        Self::truecolor(channel(r1, r2), channel(g1, g2), channel(b1, b2))
+    }
+
+    /// Blend `t` of `other` into the color, `t` going from `0.0` (`self`) to
+    /// `1.0` (`other`). The same as [`Color::lerp`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let bar = Color::truecolor(0, 0, 255).mix(Color::truecolor(255, 0, 0), 0.25);
+    /// assert_eq!(bar, Color::truecolor(64, 0, 191));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn mix(self, other: Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+
+    /// The color of `value` on a heatmap going from green (`0.0`) through
+    /// yellow to red (`1.0`), e.g. to color latencies or CPU usage.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::heat(0.0), Color::truecolor(0x00, 0x80, 0x00));
+    /// assert_eq!(Color::heat(1.0), Color::truecolor(0xFF, 0x00, 0x00));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn heat(value: f32) -> Self {
+        Self::heat_with(value, &[Self::Green, Self::Yellow, Self::Red])
+    }
+
+    /// The color of `value` (`0.0..=1.0`) on a gradient going evenly through
+    /// `stops`. An empty gradient is white.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let gradient = [Color::Blue, Color::Red];
+    /// assert_eq!(Color::heat_with(0.5, &gradient), Color::truecolor(128, 0, 128));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn heat_with(value: f32, stops: &[Self]) -> Self {
+        Gradient::evenly(stops).at(value)
+    }
+
+    /// The color of `value` (`0.0..=1.0`) on `gradient`, e.g. one of the
+    /// presets such as [`Gradient::VIRIDIS`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::heat_in(0.0, &Gradient::MAGMA), Color::truecolor(0, 0, 4));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn heat_in(value: f32, gradient: &Gradient) -> Self {
+        gradient.at(value)
+    }
+
+    /// The color with its HSL lightness raised by `amount` (`0.0..=1.0`),
+    /// e.g. for the hover variant of a theme color. The result is always a
+    /// [`Color::TrueColor`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::truecolor(0x80, 0, 0).lighten(0.25), Color::truecolor(0xFF, 0, 0));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn lighten(self, amount: f32) -> Self {
+        self.adjust_hsl(2, amount)
+    }
+
+    /// The color with its HSL lightness lowered by `amount` (`0.0..=1.0`).
+    /// The result is always a [`Color::TrueColor`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::truecolor(0xFF, 0, 0).darken(0.5), Color::truecolor(0, 0, 0));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn darken(self, amount: f32) -> Self {
+        self.adjust_hsl(2, -amount)
+    }
+
+    /// The color with its HSL saturation raised by `amount` (`0.0..=1.0`).
+    /// The result is always a [`Color::TrueColor`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::truecolor(0xBF, 0x40, 0x40).saturate(1.0), Color::truecolor(0xFF, 0, 0));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn saturate(self, amount: f32) -> Self {
+        self.adjust_hsl(1, amount)
+    }
+
+    /// The color with its HSL saturation lowered by `amount` (`0.0..=1.0`),
+    /// a gray once the saturation reaches `0.0`. The result is always a
+    /// [`Color::TrueColor`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::truecolor(0xFF, 0, 0).desaturate(1.0), Color::truecolor(0x80, 0x80, 0x80));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn desaturate(self, amount: f32) -> Self {
+        self.adjust_hsl(1, -amount)
+    }
+
+    /// The RGB inverse of the color, each channel subtracted from `255`.
+    /// The result is always a [`Color::TrueColor`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::truecolor(255, 200, 0).invert(), Color::truecolor(0, 55, 255));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn invert(self) -> Self {
+        let [r, g, b] = self.to_hex_array();
+        Self::truecolor(255 - r, 255 - g, 255 - b)
+    }
+
+    /// The complement of the color, its hue rotated by 180 degrees with the
+    /// same saturation and lightness, e.g. to derive an accent from a base
+    /// color. The result is always a [`Color::TrueColor`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::truecolor(255, 128, 0).complement(), Color::truecolor(0, 127, 255));
+    /// ```
+    #[must_use]
+    pub fn complement(self) -> Self {
+        let [h, s, l] = rgb_to_hsl(self.to_hex_array());
+        let [r, g, b] = hsl_to_rgb([h + 180.0, s, l]);
+        Self::truecolor(r, g, b)
+    }
+
+    /// The relative luminance of the color as defined by WCAG, from `0.0`
+    /// (black) to `1.0` (white)
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::truecolor(0, 0, 0).relative_luminance(), 0.0);
+    /// assert_eq!(Color::truecolor(255, 255, 255).relative_luminance(), 1.0);
+    /// ```
+    #[must_use]
+    pub fn relative_luminance(&self) -> f32 {
+        let [r, g, b] = self.to_hex_array().map(|c| {
+            let c = f32::from(c) / 255.0;
+            if c <= 0.039_28 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        });
+        0.0722f32.mul_add(b, 0.2126f32.mul_add(r, 0.7152 * g))
+    }
+
+    /// The WCAG contrast ratio between two colors, from `1.0` (the same
+    /// luminance) to `21.0` (black and white). WCAG asks for at least `4.5`
+    /// for text, or `3.0` for large text.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let fg = Color::truecolor(0x77, 0x77, 0x77);
+    /// let bg = Color::truecolor(0xFF, 0xFF, 0xFF);
+    /// assert!(fg.contrast_ratio(&bg) > 4.47 && fg.contrast_ratio(&bg) < 4.48);
+    /// ```
+    #[must_use]
+    pub fn contrast_ratio(&self, other: &Self) -> f32 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Add `delta` to the saturation (`1`) or lightness (`2`) of the color,
+    /// clamped to `0.0..=1.0`
+    fn adjust_hsl(self, channel: usize, delta: f32) -> Self {
+        let mut hsl = rgb_to_hsl(self.to_hex_array());
+        if !delta.is_nan() {
+            hsl[channel] = (hsl[channel] + delta).clamp(0.0, 1.0);
+        }
+        let [r, g, b] = hsl_to_rgb(hsl);
+        Self::truecolor(r, g, b)
+    }
+
+    /// Returns if the color is one of the 8 bright colors, e.g.
+    /// [`Color::BrightRed`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert!(Color::BrightRed.is_bright());
+    /// assert!(!Color::Red.is_bright());
+    /// assert!(!Color::Ansi256(9).is_bright());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn is_bright(&self) -> bool {
+        matches!(
+            self,
+            Self::BrightBlack
+                | Self::BrightRed
+                | Self::BrightGreen
+                | Self::BrightYellow
+                | Self::BrightBlue
+                | Self::BrightMagenta
+                | Self::BrightCyan
+                | Self::BrightWhite
+        )
+    }
+
+    /// The bright variant of a standard color, e.g. [`Color::BrightRed`] for
+    /// [`Color::Red`]. Other colors are returned as is.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::Red.to_bright(), Color::BrightRed);
+    /// assert_eq!(Color::BrightRed.to_bright(), Color::BrightRed);
+    /// assert_eq!(Color::Default.to_bright(), Color::Default);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn to_bright(self) -> Self {
+        match self {
+            Self::Black => Self::BrightBlack,
+            Self::Red => Self::BrightRed,
+            Self::Green => Self::BrightGreen,
+            Self::Yellow => Self::BrightYellow,
+            Self::Blue => Self::BrightBlue,
+            Self::Magenta => Self::BrightMagenta,
+            Self::Cyan => Self::BrightCyan,
+            Self::White => Self::BrightWhite,
+            _ => self,
+        }
+    }
+
+    /// The standard variant of a bright color, e.g. [`Color::Red`] for
+    /// [`Color::BrightRed`]. Other colors are returned as is.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::BrightRed.to_normal(), Color::Red);
+    /// assert_eq!(Color::Red.to_normal(), Color::Red);
+    /// assert_eq!(Color::truecolor(1, 2, 3).to_normal(), Color::truecolor(1, 2, 3));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn to_normal(self) -> Self {
+        match self {
+            Self::BrightBlack => Self::Black,
+            Self::BrightRed => Self::Red,
+            Self::BrightGreen => Self::Green,
+            Self::BrightYellow => Self::Yellow,
+            Self::BrightBlue => Self::Blue,
+            Self::BrightMagenta => Self::Magenta,
+            Self::BrightCyan => Self::Cyan,
+            Self::BrightWhite => Self::White,
+            _ => self,
+        }
+    }
+
+    /// Every named [`Color`], in the order of their escape codes
+    pub const NAMED: [Self; 16] = [
+        Self::Black,
+        Self::Red,
+        Self::Green,
+        Self::Yellow,
+        Self::Blue,
+        Self::Magenta,
+        Self::Cyan,
+        Self::White,
+        Self::BrightBlack,
+        Self::BrightRed,
+        Self::BrightGreen,
+        Self::BrightYellow,
+        Self::BrightBlue,
+        Self::BrightMagenta,
+        Self::BrightCyan,
+        Self::BrightWhite,
+    ];
+
+    /// The name of the color, which is parsed back by `Color::from_str`.
+    /// [`Color::TrueColor`] has no name, use [`Color::to_hex`] instead.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::BrightBlue.name(), Some("bright blue"));
+    /// assert_eq!("bright blue".parse(), Ok(Color::BrightBlue));
+    /// assert_eq!(Color::truecolor(1, 2, 3).name(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn name(&self) -> Option<&'static str> {
+        match *self {
+            Self::Black => Some("black"),
+            Self::Red => Some("red"),
+            Self::Green => Some("green"),
+            Self::Yellow => Some("yellow"),
+            Self::Blue => Some("blue"),
+            Self::Magenta => Some("magenta"),
+            Self::Cyan => Some("cyan"),
+            Self::White => Some("white"),
+            Self::BrightBlack => Some("bright black"),
+            Self::BrightRed => Some("bright red"),
+            Self::BrightGreen => Some("bright green"),
+            Self::BrightYellow => Some("bright yellow"),
+            Self::BrightBlue => Some("bright blue"),
+            Self::BrightMagenta => Some("bright magenta"),
+            Self::BrightCyan => Some("bright cyan"),
+            Self::BrightWhite => Some("bright white"),
+            Self::Default => Some("default"),
+            Self::Ansi256(_) | Self::TrueColor { .. } => None,
+        }
+    }
+
+    /// The index of the color in the terminal palette, from 0 to 15 for the
+    /// named colors and up to 255 for [`Color::Ansi256`].
+    /// [`Color::TrueColor`] is not in the palette. This is the inverse of
+    /// [`Color::from_ansi_index`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::BrightRed.ansi_index(), Some(9));
+    /// assert_eq!(Color::Ansi256(208).ansi_index(), Some(208));
+    /// assert_eq!(Color::truecolor(1, 2, 3).ansi_index(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn ansi_index(&self) -> Option<u8> {
+        match *self {
+            Self::Black => Some(0),
+            Self::Red => Some(1),
+            Self::Green => Some(2),
+            Self::Yellow => Some(3),
+            Self::Blue => Some(4),
+            Self::Magenta => Some(5),
+            Self::Cyan => Some(6),
+            Self::White => Some(7),
+            Self::BrightBlack => Some(8),
+            Self::BrightRed => Some(9),
+            Self::BrightGreen => Some(10),
+            Self::BrightYellow => Some(11),
+            Self::BrightBlue => Some(12),
+            Self::BrightMagenta => Some(13),
+            Self::BrightCyan => Some(14),
+            Self::BrightWhite => Some(15),
+            Self::Ansi256(index) => Some(index),
+            Self::TrueColor { .. } | Self::Default => None,
+        }
+    }
+
+    /// The index of the 256 color palette closest to the color, for terminals
+    /// without truecolors. A [`Color::TrueColor`] is approximated by the
+    /// nearest color of the cube or gray ramp (`16..=255`) of
+    /// [`control::palette`], which unlike the first 16 colors are not
+    /// redefined by terminal themes.
+    /// [`Color::Default`] has no index and is approximated by 15, the white
+    /// also assumed by [`Color::to_hex_array`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::truecolor(255, 135, 0).to_ansi256(), 208);
+    /// assert_eq!(Color::truecolor(100, 100, 100).to_ansi256(), 241);
+    /// assert_eq!(Color::BrightRed.to_ansi256(), 9);
+    /// ```
+    #[must_use]
+    pub fn to_ansi256(&self) -> u8 {
+        match *self {
+            Self::TrueColor { r, g, b } => control::palette().nearest_cube_or_gray(r, g, b),
+            Self::Ansi256(index) => index,
+            Self::Default => 15,
+            named => named.ansi_index().unwrap_or_default(),
+        }
+    }
+
+    /// The closest of the 16 named colors, for terminals without indexed
+    /// colors. Indexed and truecolors are compared to the named colors of
+    /// [`control::palette`] with a perceptual distance, weighting the channels
+    /// the way the eye does.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::truecolor(250, 10, 20).to_ansi16(), Color::BrightRed);
+    /// assert_eq!(Color::Ansi256(34).to_ansi16(), Color::Green);
+    /// assert_eq!(Color::Ansi256(4).to_ansi16(), Color::Blue);
+    /// assert_eq!(Color::Cyan.to_ansi16(), Color::Cyan);
+    /// ```
+    #[must_use]
+    pub fn to_ansi16(&self) -> Self {
+        let palette = control::palette();
+        let rgb = match *self {
+            Self::Ansi256(index) if index >= 16 => palette.get(index),
+            Self::TrueColor { r, g, b } => [r, g, b],
+            Self::Ansi256(index) => return Self::from_ansi_index(index).unwrap_or(Self::White),
+            named => return named,
+        };
+
+        Self::NAMED
+            .into_iter()
+            .zip(0..)
+            .min_by_key(|&(_, index)| perceptual_distance(palette.get(index), rgb))
+            .map_or(Self::White, |(color, _)| color)
+    }
+
+    /// The color at `index` in the terminal palette, a named color if it is
+    /// below 16 and a [`Color::Ansi256`] otherwise
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::from_ansi_index(9), Some(Color::BrightRed));
+    /// assert_eq!(Color::from_ansi_index(16), Some(Color::Ansi256(16)));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn from_ansi_index(index: u8) -> Option<Self> {
+        if (index as usize) < Self::NAMED.len() {
+            Some(Self::NAMED[index as usize])
+        } else {
+            Some(Self::Ansi256(index))
+        }
+    }
+}
+
+/// A distance between two RGB values close to the perceived one, weighting
+/// red and blue by how much red there is ("redmean")
+fn perceptual_distance(a: [u8; 3], b: [u8; 3]) -> u32 {
+    let [r1, g1, b1] = a.map(u32::from);
+    let [r2, g2, b2] = b.map(u32::from);
+    let rmean = (r1 + r2) / 2;
+    let (dr, dg, db) = (r1.abs_diff(r2), g1.abs_diff(g2), b1.abs_diff(b2));
+    (((512 + rmean) * dr * dr) >> 8) + 4 * dg * dg + (((767 - rmean) * db * db) >> 8)
+}
+
+/// The hue (degrees), saturation and lightness of an RGB value
+fn rgb_to_hsl(rgb: [u8; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb.map(|c| f32::from(c) / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return [0.0, 0.0, l];
+    }
+
+    let s = delta / (1.0 - 2.0f32.mul_add(l, -1.0).abs());
+    let h = if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    [h * 60.0, s, l]
+}
+
+/// The RGB value of a hue (degrees), saturation and lightness
+fn hsl_to_rgb(hsl: [f32; 3]) -> [u8; 3] {
+    let [h, s, l] = hsl;
+    let chroma = (1.0 - 2.0f32.mul_add(l, -1.0).abs()) * s;
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match h {
+        h if h < 1.0 => (chroma, x, 0.0),
+        h if h < 2.0 => (x, chroma, 0.0),
+        h if h < 3.0 => (0.0, chroma, x),
+        h if h < 4.0 => (0.0, x, chroma),
+        h if h < 5.0 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = l - chroma / 2.0;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    [r, g, b].map(|c| ((c + m) * 255.0).round().clamp(0.0, 255.0) as u8)
 }
 
 impl PartialOrd for Color {
@@ -251,26 +881,133 @@ impl Ord for Color {
     }
 }
 
+impl Add for Color {
+    type Output = Self;
+
+    /// Add the RGB channels of both colors, saturating at 255, e.g. to blend
+    /// scaled colors. The result is always a [`Color::TrueColor`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let (a, b) = (Color::truecolor(200, 0, 40), Color::truecolor(100, 60, 0));
+    /// assert_eq!(a * 0.5 + b * 0.5, Color::truecolor(150, 30, 20));
+    /// assert_eq!(a + b, Color::truecolor(255, 60, 40));
+    /// ```
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        let [r1, g1, b1] = self.to_hex_array();
+        let [r2, g2, b2] = other.to_hex_array();
+        Self::truecolor(r1.saturating_add(r2), g1.saturating_add(g2), b1.saturating_add(b2))
+    }
+}
+
+impl Mul<f32> for Color {
+    type Output = Self;
+
+    /// Scale the RGB channels of the color, clamped to `0..=255`, e.g. to
+    /// darken or brighten it. The result is always a [`Color::TrueColor`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Color::truecolor(100, 200, 0) * 0.5, Color::truecolor(50, 100, 0));
+    /// assert_eq!(Color::truecolor(100, 200, 0) * 2.0, Color::truecolor(200, 255, 0));
+    /// ```
+    #[inline]
+    fn mul(self, factor: f32) -> Self {
+        let factor = if factor.is_nan() { 0.0 } else { factor.max(0.0) };
+        let [r, g, b] = self.to_hex_array();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let channel = |c: u8| (f32::from(c) * factor).round().min(255.0) as u8;
+        Self::truecolor(channel(r), channel(g), channel(b))
+    }
+}
+
+/// Formats the color as 6 hex digits, prefixed with `#` with the alternate
+/// flag.
+///
+/// ```rust
+/// # use colored::Color;
+/// assert_eq!(format!("{:x}", Color::truecolor(255, 0, 136)), "ff0088");
+/// assert_eq!(format!("{:#x}", Color::truecolor(255, 0, 136)), "#ff0088");
+/// ```
+impl fmt::LowerHex for Color {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b] = self.to_hex_array();
+        let hex = format!("{r:02x}{g:02x}{b:02x}");
+        if f.alternate() {
+            f.pad(&format!("#{hex}"))
+        } else {
+            f.pad(&hex)
+        }
+    }
+}
+
+/// Formats the color as 6 uppercase hex digits, prefixed with `#` with the
+/// alternate flag.
+///
+/// ```rust
+/// # use colored::Color;
+/// assert_eq!(format!("{:X}", Color::truecolor(255, 0, 136)), "FF0088");
+/// assert_eq!(format!("{:#X}", Color::truecolor(255, 0, 136)), "#FF0088");
+/// ```
+impl fmt::UpperHex for Color {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b] = self.to_hex_array();
+        let hex = format!("{r:02X}{g:02X}{b:02X}");
+        if f.alternate() {
+            f.pad(&format!("#{hex}"))
+        } else {
+            f.pad(&hex)
+        }
+    }
+}
+
 impl From<&'_ str> for Color {
     #[inline]
     fn from(src: &str) -> Self {
-        src.parse().unwrap_or(Self::White)
+        src.parse()
+            .unwrap_or_else(|_| unknown_color(src, control::strict_parsing()))
     }
 }
 
 impl From<String> for Color {
     #[inline]
     fn from(src: String) -> Self {
-        src.parse().unwrap_or(Self::White)
+        Self::from(src.as_str())
+    }
+}
+
+/// The color of an unknown color name, white. If `strict`, debug builds
+/// panic and release builds print a warning, see
+/// [`control::set_strict_parsing`]
+#[allow(clippy::print_stderr)]
+fn unknown_color(src: &str, strict: bool) -> Color {
+    if strict {
+        if cfg!(debug_assertions) {
+            panic!("unknown color `{src}`");
+        }
+        eprintln!("colored: unknown color `{src}`, using white");
     }
+    Color::White
 }
 
 impl FromStr for Color {
-    type Err = ();
+    type Err = ParseColorError;
 
+    /// Parses, in this order, a color word such as `bright blue`, an `xterm`
+    /// 256 color name such as `DarkSeaGreen4` (see [`xterm`](crate::xterm)),
+    /// a CSS color name such as `orange` with the `css-names` feature, a hex
+    /// color prefixed with `#` or `0x` such as `#ff0000`, or a CSS `rgb()` or
+    /// `hsl()` function such as `rgb(255, 0, 0)`, see [`Color::from_hex`].
+    ///
+    /// `From<&str>` goes through the same steps, so `.color("#ff0088")` and
+    /// `.color("rgb(255, 0, 136)")` work as well as `.color("red")`.
     #[inline]
     fn from_str(src: &str) -> Result<Self, Self::Err> {
-        match src.to_lowercase().trim() {
+        let trimmed = src.trim();
+        match trimmed.to_lowercase().as_str() {
             "black" => Ok(Self::Black),
             "red" => Ok(Self::Red),
             "green" => Ok(Self::Green),
@@ -287,11 +1024,74 @@ impl FromStr for Color {
             "bright magenta" => Ok(Self::BrightMagenta),
             "bright cyan" => Ok(Self::BrightCyan),
             "bright white" => Ok(Self::BrightWhite),
-            _ => Err(()),
+            "default" => Ok(Self::Default),
+            name => {
+                let color = crate::xterm::from_name(name);
+                #[cfg(feature = "css-names")]
+                let color = color.or_else(|| crate::css::from_name(name));
+                if let Some(color) = color {
+                    Ok(color)
+                } else if name.starts_with('#') || name.starts_with("0x") {
+                    Self::from_hex(name)
+                } else if name.contains('(') {
+                    Self::from_hex(trimmed)
+                } else {
+                    Err(ParseColorError::UnknownName(trimmed.to_owned()))
+                }
+            },
         }
     }
 }
 
+/// The error of parsing a [`Color`] with [`Color::from_hex`],
+/// [`Color::from_str`](FromStr) or
+/// [`Colorizer::parse_color`](crate::Colorizer::parse_color), or a style
+/// with [`StyleSpec::from_git`](crate::StyleSpec::from_git) or
+/// [`StyleSpec::from_sgr`](crate::StyleSpec::from_sgr)
+///
+/// ```rust
+/// # use colored::*;
+/// assert_eq!("#ff00".parse::<Color>(), Err(ParseColorError::BadLength(4)));
+/// assert_eq!(Color::from_hex("#ff00zz"), Err(ParseColorError::BadDigit('z')));
+/// assert_eq!(
+///     "zorglub".parse::<Color>().unwrap_err().to_string(),
+///     "unknown color name `zorglub`"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseColorError {
+    /// A hex color without 3, 6 or 8 digits, the number of digits it has
+    BadLength(usize),
+    /// A character of a hex color which is not a hex digit
+    BadDigit(char),
+    /// A word which is not the name of a color
+    UnknownName(String),
+    /// A CSS function which is not a valid `rgb()` or `hsl()` color
+    BadFunction(String),
+    /// A word of a `git config` style which is neither a color nor an
+    /// attribute, or a third color
+    BadStyle(String),
+    /// The parameters of an SGR sequence which are not a valid style
+    BadSgr(String),
+}
+
+impl fmt::Display for ParseColorError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadLength(len) => write!(f, "a hex color has 3, 6 or 8 digits, not {len}"),
+            Self::BadDigit(c) => write!(f, "invalid hex digit `{c}`"),
+            Self::UnknownName(name) => write!(f, "unknown color name `{name}`"),
+            Self::BadFunction(function) => write!(f, "invalid color function `{function}`"),
+            Self::BadStyle(word) => write!(f, "invalid git color or attribute `{word}`"),
+            Self::BadSgr(params) => write!(f, "invalid SGR sequence `{params}`"),
+        }
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
 #[cfg(feature = "rusqlite-sql")]
 /// Allow for the conversion of `Color` to `rusqlite` types
 pub mod sql {
@@ -342,7 +1142,7 @@ const fn hex_chars_to_u8(ch: (u8, u8)) -> u8 {
     result
 }
 
-/// Parse a 6-char-hex string into a 3 digit hex value
+/// Parse the first 6 chars of a hex string into a 3 digit hex value
 fn parse_hex(color: &str) -> Option<(u8, u8, u8)> {
     let mut bytes = color.as_bytes().chunks(2);
 
@@ -353,6 +1153,70 @@ fn parse_hex(color: &str) -> Option<(u8, u8, u8)> {
     ))
 }
 
+/// Parse a CSS `rgb()`, `rgba()`, `hsl()` or `hsla()` function
+fn parse_css_function(color: &str) -> Option<(u8, u8, u8)> {
+    parse_rgb_function(color).or_else(|| parse_hsl_function(color))
+}
+
+/// The three arguments of the CSS function `name` or its alpha variant, e.g.
+/// `rgba`, separated by commas or spaces. The alpha channel, after a fourth
+/// comma or a slash, is ignored.
+fn css_function_args<'a>(color: &'a str, name: &str) -> Option<[&'a str; 3]> {
+    let (function, args) = color.trim().split_once('(')?;
+    let function = function.trim_end().to_ascii_lowercase();
+    if function.strip_suffix('a').unwrap_or(&function) != name {
+        return None;
+    }
+    let args = args.strip_suffix(')')?;
+    let (args, alpha) = match args.split_once('/') {
+        Some((args, alpha)) => (args, Some(alpha)),
+        None => (args, None),
+    };
+
+    let mut args: Vec<&str> = if args.contains(',') {
+        args.split(',').map(str::trim).collect()
+    } else {
+        args.split_whitespace().collect()
+    };
+    if alpha.is_none() && args.len() == 4 {
+        args.pop();
+    }
+    match args[..] {
+        [a, b, c] => Some([a, b, c]),
+        _ => None,
+    }
+}
+
+/// A percentage from `0` to `100`, with or without the `%` sign
+fn css_percent(arg: &str) -> Option<f32> {
+    let percent = arg.strip_suffix('%').unwrap_or(arg).parse::<f32>().ok()?;
+    (0.0..=100.0).contains(&percent).then_some(percent)
+}
+
+/// Parse a CSS `rgb()` or `rgba()` function, whose channels are numbers up to
+/// 255 or percentages
+fn parse_rgb_function(color: &str) -> Option<(u8, u8, u8)> {
+    let channel = |c: &str| {
+        if c.ends_with('%') {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            css_percent(c).map(|percent| (percent * 2.55).round() as u8)
+        } else {
+            c.parse().ok()
+        }
+    };
+    let [r, g, b] = css_function_args(color, "rgb")?;
+    Some((channel(r)?, channel(g)?, channel(b)?))
+}
+
+/// Parse a CSS `hsl()` or `hsla()` function, whose hue is in degrees and
+/// saturation and lightness are percentages
+fn parse_hsl_function(color: &str) -> Option<(u8, u8, u8)> {
+    let [h, s, l] = css_function_args(color, "hsl")?;
+    let h = h.strip_suffix("deg").unwrap_or(h).parse::<f32>().ok().filter(|h| h.is_finite())?;
+    let [r, g, b] = hsl_to_rgb([h, css_percent(s)? / 100.0, css_percent(l)? / 100.0]);
+    Some((r, g, b))
+}
+
 #[cfg(test)]
 mod tests {
     pub(crate) use super::*;
@@ -392,6 +1256,23 @@ mod tests {
             brightcyan: "bright cyan" => Color::BrightCyan,
             brightwhite: "bright white" => Color::BrightWhite,
 
+            hash_hex: "#ff0088" => Color::TrueColor { r: 255, g: 0, b: 136 },
+            prefix_hex: "0x00FF00" => Color::TrueColor { r: 0, g: 255, b: 0 },
+            invalid_hex: "#ff00" => Color::White,
+            short_hex: "#f08" => Color::TrueColor { r: 255, g: 0, b: 136 },
+            alpha_hex: "0xff008880" => Color::TrueColor { r: 255, g: 0, b: 136 },
+            not_hex: "#ff00zz" => Color::White,
+            rgb_fn: "rgb(255, 0, 136)" => Color::TrueColor { r: 255, g: 0, b: 136 },
+            rgba_fn: "RGBA(255,0,136,0.5)" => Color::TrueColor { r: 255, g: 0, b: 136 },
+            rgb_fn_spaces: "rgb(255 0 136 / 50%)" => Color::TrueColor { r: 255, g: 0, b: 136 },
+            rgb_fn_percent: "rgb(100%, 0%, 50%)" => Color::TrueColor { r: 255, g: 0, b: 128 },
+            rgb_fn_overflow: "rgb(256, 0, 0)" => Color::White,
+            rgb_fn_missing: "rgb(255, 0)" => Color::White,
+            hsl_fn: "hsl(210, 50%, 40%)" => Color::TrueColor { r: 51, g: 102, b: 153 },
+            hsla_fn: "hsla(120deg 100% 50% / 0.5)" => Color::TrueColor { r: 0, g: 255, b: 0 },
+            hsl_fn_overflow: "hsl(0, 120%, 50%)" => Color::White,
+            rgbx_fn: "rgbx(255, 0, 0)" => Color::White,
+
             invalid: "invalid" => Color::White,
             capitalized: "BLUE" => Color::Blue,
             mixed_case: "bLuE" => Color::Blue
@@ -433,6 +1314,11 @@ mod tests {
             brightcyan: "bright cyan" => Color::BrightCyan,
             brightwhite: "bright white" => Color::BrightWhite,
 
+            hash_hex: "#ff0088" => Color::TrueColor { r: 255, g: 0, b: 136 },
+            upper_prefix_hex: "0XFF0088" => Color::TrueColor { r: 255, g: 0, b: 136 },
+            unprefixed_hex: "ff0088" => Color::White,
+            rgb_fn: " rgb(255, 0, 136) " => Color::TrueColor { r: 255, g: 0, b: 136 },
+
             invalid: "invalid" => Color::White,
             capitalized: "BLUE" => Color::Blue,
             mixed_case: "bLuE" => Color::Blue
@@ -450,8 +1336,277 @@ mod tests {
 
         #[test]
         fn error() {
-            let color: Result<Color, ()> = "bloublou".parse();
-            assert_eq!(Err(()), color);
+            let color: Result<Color, _> = "bloublou".parse();
+            assert_eq!(Err(ParseColorError::UnknownName("bloublou".to_owned())), color);
+        }
+
+        #[test]
+        fn error_kinds() {
+            assert_eq!(Err(ParseColorError::BadLength(5)), "#ff008".parse::<Color>());
+            assert_eq!(Err(ParseColorError::BadDigit('g')), "0xfg0088".parse::<Color>());
+            assert_eq!(Err(ParseColorError::BadDigit('r')), Color::from_hex("red"));
+            assert_eq!(
+                Err(ParseColorError::BadFunction("RGB(1, 2)".to_owned())),
+                " RGB(1, 2) ".parse::<Color>()
+            );
+            assert_eq!(Err(ParseColorError::BadLength(0)), Color::from_hex("#"));
+        }
+    }
+
+    mod name {
+        pub(crate) use super::*;
+
+        #[test]
+        fn round_trip() {
+            for color in Color::NAMED {
+                let name = color.name().unwrap();
+                assert_eq!(Ok(color), Color::from_str(name));
+            }
+        }
+
+        #[test]
+        fn truecolor() {
+            assert_eq!(None, Color::truecolor(0, 0, 0).name());
+        }
+    }
+
+    mod grayscale {
+        use super::*;
+
+        #[test]
+        fn levels() {
+            assert_eq!(Color::grayscale_percent_for(0.0, ColorLevel::None), Color::Black);
+            assert_eq!(Color::grayscale_percent_for(70.0, ColorLevel::Ansi16), Color::White);
+            assert_eq!(Color::grayscale_percent_for(f32::NAN, ColorLevel::Ansi16), Color::Black);
+            assert_eq!(Color::grayscale_percent_for(100.0, ColorLevel::Ansi256), Color::Ansi256(231));
+            assert_eq!(Color::grayscale_percent_for(1.0, ColorLevel::Ansi256), Color::Ansi256(16));
+            assert_eq!(Color::grayscale_percent_for(150.0, ColorLevel::TrueColor), Color::truecolor(255, 255, 255));
+        }
+    }
+
+    mod ops {
+        use super::*;
+
+        #[test]
+        fn add_saturates() {
+            assert_eq!(Color::White + Color::Black, Color::truecolor(255, 255, 255));
+            assert_eq!(Color::Red + Color::Blue, Color::truecolor(255, 0, 255));
+        }
+
+        #[test]
+        fn mul_clamps() {
+            assert_eq!(Color::Red * -1.0, Color::truecolor(0, 0, 0));
+            assert_eq!(Color::Red * f32::NAN, Color::truecolor(0, 0, 0));
+            assert_eq!(Color::Green * 1.0, Color::truecolor(0, 0x80, 0));
+            assert_eq!(Color::truecolor(1, 2, 3) * f32::INFINITY, Color::truecolor(255, 255, 255));
+        }
+    }
+
+    mod heat {
+        pub(crate) use super::*;
+
+        #[test]
+        fn lerp_bounds() {
+            let a = Color::truecolor(10, 20, 30);
+            let b = Color::truecolor(200, 100, 0);
+            assert_eq!(a, a.lerp(b, -1.0));
+            assert_eq!(b, a.lerp(b, 2.0));
+            assert_eq!(a, a.lerp(b, f32::NAN));
+        }
+
+        #[test]
+        fn mix() {
+            let a = Color::truecolor(10, 20, 30);
+            let b = Color::truecolor(200, 100, 0);
+            assert_eq!(a.lerp(b, 0.3), a.mix(b, 0.3));
+            assert_eq!(b, a.mix(b, 1.0));
+            assert_eq!(a, a.mix(a, 0.7));
+        }
+
+        #[test]
+        fn gradient() {
+            assert_eq!(Color::truecolor(0xFF, 0xFF, 0x00), Color::heat(0.5));
+            assert_eq!(Color::truecolor(0x80, 0xC0, 0x00), Color::heat(0.25));
+            assert_eq!(Color::truecolor(0, 0, 0xFF), Color::heat_with(0.7, &[Color::Blue]));
+        }
+    }
+
+    mod hsl {
+        pub(crate) use super::*;
+
+        #[test]
+        fn round_trip() {
+            for rgb in [[0, 0, 0], [255, 255, 255], [12, 200, 99], [250, 10, 20], [30, 40, 220]] {
+                assert_eq!(rgb, hsl_to_rgb(rgb_to_hsl(rgb)));
+            }
+        }
+
+        #[test]
+        fn adjust() {
+            assert_eq!(Color::truecolor(0xFF, 0xFF, 0xFF), Color::Blue.lighten(1.0));
+            assert_eq!(Color::truecolor(0, 0, 0), Color::Blue.darken(2.0));
+            assert_eq!(Color::truecolor(0x80, 0x80, 0x80), Color::truecolor(0x80, 0x80, 0x80).saturate(0.0));
+            let red = Color::truecolor(0x80, 0x00, 0x00);
+            assert_eq!(red, red.desaturate(0.0));
+            assert_eq!(red, red.lighten(f32::NAN));
+            assert_eq!(Color::truecolor(0x60, 0x20, 0x20), red.desaturate(0.5));
+        }
+
+        #[test]
+        fn invert_and_complement() {
+            let base = Color::truecolor(12, 200, 99);
+            assert_eq!(base, base.invert().invert());
+            assert_eq!(base, base.complement().complement());
+            assert_eq!(Color::truecolor(0x80, 0x80, 0x80), Color::truecolor(0x80, 0x80, 0x80).complement());
+            assert_eq!(Color::truecolor(0, 0xFF, 0xFF), Color::truecolor(0xFF, 0, 0).complement());
+        }
+    }
+
+    mod wcag {
+        pub(crate) use super::*;
+
+        #[test]
+        fn luminance() {
+            let red = Color::truecolor(255, 0, 0).relative_luminance();
+            assert!((red - 0.2126).abs() < 1e-4);
+            let gray = Color::truecolor(0x80, 0x80, 0x80).relative_luminance();
+            assert!((gray - 0.2159).abs() < 1e-4);
+        }
+
+        #[test]
+        fn contrast() {
+            let black = Color::truecolor(0, 0, 0);
+            let white = Color::truecolor(255, 255, 255);
+            assert!((black.contrast_ratio(&white) - 21.0).abs() < 1e-4);
+            assert_eq!(black.contrast_ratio(&white), white.contrast_ratio(&black));
+            assert_eq!(1.0, Color::Blue.contrast_ratio(&Color::Blue));
+        }
+    }
+
+    mod hex_fmt {
+        pub(crate) use super::*;
+
+        #[test]
+        fn lower_hex() {
+            assert_eq!("00ff00", format!("{:x}", Color::truecolor(0, 255, 0)));
+            assert_eq!("#0000ff", format!("{:#x}", Color::Blue));
+            assert_eq!(format!("{:x}", Color::Red), Color::Red.to_hex());
+        }
+
+        #[test]
+        fn upper_hex() {
+            assert_eq!("ABCDEF", format!("{:X}", Color::truecolor(0xAB, 0xCD, 0xEF)));
+            assert_eq!("#ABCDEF", format!("{:#X}", Color::truecolor(0xAB, 0xCD, 0xEF)));
+        }
+    }
+
+    mod sgr_syntax {
+        pub(crate) use super::*;
+        use crate::control::SgrSyntax;
+
+        #[test]
+        fn colon() {
+            let color = Color::truecolor(1, 2, 3);
+            assert_eq!("38:2::1:2:3", color.to_fg_str_in(SgrSyntax::Colon));
+            assert_eq!("48:2::1:2:3", color.to_bg_str_in(SgrSyntax::Colon));
+            assert_eq!("31", Color::Red.to_fg_str_in(SgrSyntax::Colon));
+            assert_eq!(color.to_fg_str(), color.to_fg_str_in(SgrSyntax::Semicolon));
+        }
+    }
+
+    mod strict_parsing {
+        pub(crate) use super::*;
+
+        #[test]
+        fn lenient() {
+            assert_eq!(unknown_color("bleu", false), Color::White);
+        }
+
+        #[test]
+        #[cfg(debug_assertions)]
+        #[should_panic(expected = "unknown color `bleu`")]
+        fn strict_panics_in_debug() {
+            let _ = unknown_color("bleu", true);
+        }
+    }
+
+    mod ansi256 {
+        pub(crate) use super::*;
+
+        #[test]
+        fn sgr() {
+            let color = Color::Ansi256(208);
+            assert_eq!("38;5;208", color.to_fg_str());
+            assert_eq!("48;5;208", color.to_bg_str());
+            assert_eq!("38:5:208", color.to_fg_str_in(SgrSyntax::Colon));
+            assert_eq!(Some(color), Color::from_fg_str(&color.to_fg_str()));
+            assert_eq!(None, Color::from_fg_str("38;5;256"));
+            assert_eq!(Some(color), Color::from_bg_str(&color.to_bg_str()));
+            assert_eq!([0xFF, 0x87, 0x00], color.to_hex_array());
+        }
+    }
+
+    mod to_ansi256 {
+        pub(crate) use super::*;
+
+        #[test]
+        fn nearest() {
+            assert_eq!(Color::truecolor(0, 0, 0).to_ansi256(), 16);
+            assert_eq!(Color::truecolor(255, 255, 255).to_ansi256(), 231);
+            assert_eq!(Color::truecolor(0x87, 0xAF, 0x87).to_ansi256(), 108);
+            assert_eq!(Color::truecolor(0x88, 0xB0, 0x86).to_ansi256(), 108);
+            assert_eq!(Color::truecolor(0xEE, 0xEE, 0xED).to_ansi256(), 255);
+            assert_eq!(Color::Ansi256(42).to_ansi256(), 42);
+            for index in 16..=255 {
+                let [r, g, b] = Color::Ansi256(index).to_hex_array();
+                let nearest = Color::truecolor(r, g, b).to_ansi256();
+                assert_eq!(Color::Ansi256(nearest).to_hex_array(), [r, g, b]);
+            }
+        }
+    }
+
+    mod to_ansi16 {
+        pub(crate) use super::*;
+
+        #[test]
+        fn nearest() {
+            for (index, color) in Color::NAMED.iter().enumerate() {
+                let [r, g, b] = Palette::xterm().get(u8::try_from(index).unwrap());
+                assert_eq!(Color::truecolor(r, g, b).to_ansi16(), *color);
+                assert_eq!(color.to_ansi16(), *color);
+            }
+            assert_eq!(Color::truecolor(20, 20, 20).to_ansi16(), Color::Black);
+            assert_eq!(Color::truecolor(255, 140, 0).to_ansi16(), Color::Yellow);
+            assert_eq!(Color::Ansi256(231).to_ansi16(), Color::BrightWhite);
+        }
+    }
+
+    mod bright {
+        pub(crate) use super::*;
+
+        #[test]
+        fn round_trip() {
+            for (base, bright) in Color::NAMED[..8].iter().zip(&Color::NAMED[8..]) {
+                assert!(!base.is_bright());
+                assert!(bright.is_bright());
+                assert_eq!(base.to_bright(), *bright);
+                assert_eq!(bright.to_normal(), *base);
+            }
+        }
+    }
+
+    mod ansi_index {
+        pub(crate) use super::*;
+
+        #[test]
+        fn round_trip() {
+            for index in 0..16 {
+                let color = Color::from_ansi_index(index).unwrap();
+                assert_eq!(color.ansi_index(), Some(index));
+                assert_eq!(color, Color::NAMED[usize::from(index)]);
+            }
+            assert_eq!(Color::from_ansi_index(255), Some(Color::Ansi256(255)));
+            assert_eq!(Color::Ansi256(255).ansi_index(), Some(255));
         }
     }
 }
@@ -0,0 +1,57 @@
+//! Aligning colored cells into columns
+
+use crate::ColoredString;
+
+/// Lay out `cells` as one row of columns separated by a space, padding each
+/// cell to the visible width of its column in `widths`.
+///
+/// The padding is written after the cell is reset, so backgrounds and
+/// underlines don't bleed into it. Widths are counted in terminal columns,
+/// see [`ColoredString::display_width`]. A cell wider than its column is
+/// written whole, and cells past the end of `widths`, like the last cell, are
+/// not padded.
+///
+/// ```rust
+/// # use colored::*;
+/// let header = format_columns(&["NAME".bold(), "STATUS".bold()], &[8, 6]);
+/// let row = format_columns(&["api".normal(), "up".green()], &[8, 6]);
+/// assert_eq!(row, format!("api      {}", "up".green()));
+/// # let _ = header;
+/// ```
+#[must_use]
+pub fn format_columns(cells: &[ColoredString], widths: &[usize]) -> String {
+    let mut row = String::new();
+    for (idx, cell) in cells.iter().enumerate() {
+        if idx > 0 {
+            row.push(' ');
+        }
+        row.push_str(&cell.to_ansi_string());
+
+        let is_last = idx + 1 == cells.len();
+        if let Some(&width) = widths.get(idx).filter(|_| !is_last) {
+            let len = cell.display_width();
+            row.extend(std::iter::repeat(' ').take(width.saturating_sub(len)));
+        }
+    }
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Colorize;
+
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn pads_after_reset() {
+        let row = format_columns(&["é".on_red(), "日".normal(), "c".blue()], &[3, 3, 9]);
+        assert_eq!(row, format!("{}   日  {}", "é".on_red(), "c".blue()));
+    }
+
+    #[test]
+    fn overflowing_and_extra_cells() {
+        let row = format_columns(&["long".normal(), "x".normal(), "y".normal()], &[2]);
+        assert_eq!(row, "long x y");
+        assert_eq!(format_columns(&[], &[4]), "");
+    }
+}
@@ -1,9 +1,26 @@
 //! A couple of functions to enable and disable coloring.
 
 use std::{
+    borrow::Cow,
+    cell::Cell,
+    collections::HashMap,
     default::Default,
     env,
-    sync::{atomic::{AtomicBool, Ordering}, LazyLock},
+    fmt,
+    io,
+    str::FromStr,
+    sync::{atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering}, Arc, OnceLock, RwLock},
+};
+
+use crate::{
+    style::{Style, Styles},
+    Color,
+    ColoredString,
+    LazyLock,
+    Palette,
+    ParseColorError,
+    RenderBackend,
+    WidthPolicy,
 };
 
 /// Sets a flag to the console to use a virtual terminal environment.
@@ -58,96 +75,2166 @@ pub fn set_virtual_terminal(use_virtual: bool) -> Result<(), ()> {
     Ok(())
 }
 
-/// A flag to to if coloring should occur.
-pub struct ShouldColorize {
-    /// `CLICOLOR` status
-    clicolor:            bool,
-    /// `CLICOLORFORCE` status
-    clicolor_force:      Option<bool>,
-    // XXX we can't use Option<Atomic> because we can't use &mut references to ShouldColorize
-    has_manual_override: AtomicBool,
-    manual_override:     AtomicBool,
+/// The first build of Windows 10 whose console supports
+/// `ENABLE_VIRTUAL_TERMINAL_PROCESSING`
+#[cfg(windows)]
+const WINDOWS_VT_BUILD: u32 = 10586;
+
+/// Checks whether the console is able to interpret ANSI escape codes, either
+/// because it runs inside a terminal emulator known to handle them, or
+/// because the version of Windows supports virtual terminal processing.
+#[cfg(windows)]
+fn windows_supports_ansi() -> bool {
+    if env::var_os("WT_SESSION").is_some()
+        || env::var_os("ANSICON").is_some()
+        || env::var("ConEmuANSI").map_or(false, |v| v == "ON")
+        || env::var("TERM").map_or(false, |t| t != "dumb")
+    {
+        return true;
+    }
+
+    windows_build_number().map_or(false, |build| build >= WINDOWS_VT_BUILD)
 }
 
-/// Use this to force colored to ignore the environment and always/never
-/// colorize See `example/control.rs`
-#[inline]
-pub fn set_override(override_colorize: bool) {
-    SHOULD_COLORIZE.set_override(override_colorize);
+/// The build number of the running Windows version.
+///
+/// `RtlGetVersion` is used because `GetVersionEx` reports an outdated version
+/// to applications without a compatibility manifest.
+#[cfg(windows)]
+fn windows_build_number() -> Option<u32> {
+    use winapi::{
+        shared::{minwindef::DWORD, ntdef::NTSTATUS},
+        um::winnt::RTL_OSVERSIONINFOW,
+    };
+
+    #[link(name = "ntdll")]
+    extern "system" {
+        fn RtlGetVersion(info: *mut RTL_OSVERSIONINFOW) -> NTSTATUS;
+    }
+
+    unsafe {
+        let mut info: RTL_OSVERSIONINFOW = std::mem::zeroed();
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            info.dwOSVersionInfoSize = std::mem::size_of::<RTL_OSVERSIONINFOW>() as DWORD;
+        }
+
+        (RtlGetVersion(&mut info) == 0).then_some(info.dwBuildNumber)
+    }
 }
 
-/// Remove the manual override and let the environment decide if it's ok to
-/// colorize See example/control.rs
-#[inline]
-pub fn unset_override() {
-    SHOULD_COLORIZE.unset_override();
+/// Values of `TERM` for terminals which display 256 colors, but mis-render
+/// 24-bit codes even when `COLORTERM` (often inherited from the outer
+/// terminal) advertises them.
+const ANSI256_TERMS: &[&str] = &[
+    "screen-256color",
+    "screen.xterm-256color",
+    "rxvt-256color",
+    "rxvt-unicode-256color",
+    "eterm-256color",
+    "nsterm-256color",
+];
+
+/// How many colors the terminal is able to display.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorLevel {
+    /// No colors at all
+    None,
+    /// The 16 standard and bright colors
+    Ansi16,
+    /// The 256 indexed colors
+    Ansi256,
+    /// 24-bit colors
+    TrueColor,
 }
 
-/// The persistent [`ShouldColorize`].
-pub static SHOULD_COLORIZE: LazyLock<ShouldColorize> = LazyLock::new(|| ShouldColorize::from_env() );
+impl ColorLevel {
+    /// Detect the color level of `stdout` from the environment.
+    ///
+    /// The rules of the `supports-color` crate are used when the
+    /// `supports-color` feature is enabled, otherwise `COLORTERM` and `TERM`
+    /// decide how many colors are available once [`ShouldColorize::from_env`]
+    /// allowed colorization.
+    #[inline]
+    #[must_use]
+    pub fn from_env() -> Self {
+        if cfg!(feature = "supports-color") {
+            Self::from_env_supports_color()
+        } else {
+            Self::from_term(|name| env::var(name).ok())
+        }
+    }
 
-impl Default for ShouldColorize {
+    /// Detect the color level of `stdout` with the same rules as the
+    /// `supports-color` crate, so that both report consistent levels.
+    #[inline]
+    #[must_use]
+    pub fn from_env_supports_color() -> Self {
+        Self::supports_color(|name| env::var(name).ok(), atty::is(atty::Stream::Stdout))
+    }
+
+    /// Color level advertised by `COLORTERM` and `TERM`. Terminals listed in
+    /// [`ANSI256_TERMS`] and Apple's Terminal never get truecolor, and inside
+    /// `screen` or `tmux` truecolor is only trusted for `tmux` 3.2 or later.
+    fn from_term(var: impl Fn(&str) -> Option<String>) -> Self {
+        let term = var("TERM").unwrap_or_default();
+        if in_multiplexer(&var) {
+            let truecolor = matches!(var("COLORTERM").as_deref(), Some("truecolor" | "24bit"))
+                && var("TERM_PROGRAM").as_deref() == Some("tmux")
+                && var("TERM_PROGRAM_VERSION").map_or(false, |v| tmux_version_at_least(&v, (3, 2)));
+            if truecolor {
+                Self::TrueColor
+            } else if term.contains("256") {
+                Self::Ansi256
+            } else {
+                Self::Ansi16
+            }
+        } else if ANSI256_TERMS.contains(&term.as_str())
+            || var("TERM_PROGRAM").as_deref() == Some("Apple_Terminal")
+        {
+            Self::Ansi256
+        } else if matches!(var("COLORTERM").as_deref(), Some("truecolor" | "24bit")) {
+            Self::TrueColor
+        } else if term.contains("256") {
+            Self::Ansi256
+        } else {
+            Self::Ansi16
+        }
+    }
+
+    /// Color level following the rules of the `supports-color` crate
+    fn supports_color(var: impl Fn(&str) -> Option<String>, is_tty: bool) -> Self {
+        let level = |force: usize| match force {
+            0 => Self::None,
+            1 => Self::Ansi16,
+            2 => Self::Ansi256,
+            _ => Self::TrueColor,
+        };
+        let force_color = match var("FORCE_COLOR").as_deref() {
+            Some("" | "true") => 1,
+            Some("false") => 0,
+            Some(force) => force.parse().unwrap_or(1),
+            None => usize::from(var("CLICOLOR_FORCE").map_or(false, |v| v != "0")),
+        };
+        if force_color > 0 {
+            return level(force_color);
+        }
+
+        let term = var("TERM").unwrap_or_default();
+        let term_program = var("TERM_PROGRAM").unwrap_or_default();
+        let no_color = var("NO_COLOR").map_or(false, |v| v != "0");
+        let ignore_tty = var("IGNORE_IS_TERMINAL").map_or(false, |v| v != "0");
+
+        if no_color || term == "dumb" || !(is_tty || ignore_tty) {
+            Self::None
+        } else if matches!(var("COLORTERM").as_deref(), Some("truecolor" | "24bit"))
+            || term.ends_with("direct")
+            || term.ends_with("truecolor")
+            || term_program == "iTerm.app"
+        {
+            Self::TrueColor
+        } else if term_program == "Apple_Terminal" || term.ends_with("256") || term.ends_with("256color") {
+            Self::Ansi256
+        } else if var("COLORTERM").is_some()
+            || ["screen", "xterm", "vt100", "vt220", "rxvt"]
+                .iter()
+                .any(|prefix| term.starts_with(prefix))
+            || ["color", "ansi", "cygwin", "linux"]
+                .iter()
+                .any(|part| term.contains(part))
+            || cfg!(windows)
+            || var("CLICOLOR").map_or(false, |v| v != "0")
+            || var("CI").is_some()
+        {
+            Self::Ansi16
+        } else {
+            Self::None
+        }
+    }
+}
+
+/// Checks whether the terminal is known to support OSC 8 hyperlinks
+fn hyperlinks_from_env(var: impl Fn(&str) -> Option<String>, is_tty: bool) -> bool {
+    if let Some(force) = var("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+    if !is_tty {
+        return false;
+    }
+
+    let term_program = var("TERM_PROGRAM").unwrap_or_default();
+    let term = var("TERM").unwrap_or_default();
+    var("DOMTERM").is_some()
+        || var("WT_SESSION").is_some()
+        || var("KONSOLE_VERSION").is_some()
+        || var("VTE_VERSION")
+            .and_then(|v| v.parse::<u32>().ok())
+            .map_or(false, |v| v >= 5000)
+        || ["Hyper", "iTerm.app", "terminology", "WezTerm", "vscode", "ghostty"]
+            .contains(&term_program.as_str())
+        || ["xterm-kitty", "alacritty", "foot", "xterm-ghostty"].contains(&term.as_str())
+}
+
+/// How the parameters of truecolor codes are separated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SgrSyntax {
+    /// `38;2;r;g;b`, understood by nearly every terminal
+    #[default]
+    Semicolon,
+    /// `38:2::r:g:b`, the syntax of ECMA-48 which some terminals and
+    /// multiplexers parse differently
+    Colon,
+}
+
+/// How bold and bright colors are traded for each other on terminals which
+/// only display 16 colors, for legacy terminals rendering bold as bright.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BoldBright {
+    /// Render bold and bright colors as they are
+    #[default]
+    Off,
+    /// Render a bright foreground as bold with the standard color
+    BrightToBold,
+    /// Render bold with a standard foreground as the bright color
+    BoldToBright,
+}
+
+/// Which streams must be terminals for colors to be used, when the
+/// environment does not decide.
+///
+/// ```rust
+/// # use colored::control::{self, TtyRule};
+/// // colorize neither stream when either one is piped, so that the output
+/// // looks the same on both
+/// control::set_tty_rule(TtyRule::Both);
+/// # control::set_tty_rule(TtyRule::default());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TtyRule {
+    /// `stdout` is a terminal
+    #[default]
+    Stdout,
+    /// `stderr` is a terminal
+    Stderr,
+    /// Both `stdout` and `stderr` are terminals
+    Both,
+    /// `stdout` or `stderr` is a terminal
+    Either,
+}
+
+/// The [`SgrSyntax`] is detected from the environment
+const SGR_AUTO: u8 = 0;
+/// The [`SgrSyntax`] was set to [`SgrSyntax::Semicolon`]
+const SGR_SEMICOLON: u8 = 1;
+/// The [`SgrSyntax`] was set to [`SgrSyntax::Colon`]
+const SGR_COLON: u8 = 2;
+
+/// Checks whether the output goes through `screen` or `tmux`
+fn in_multiplexer(var: impl Fn(&str) -> Option<String>) -> bool {
+    let term = var("TERM").unwrap_or_default();
+    var("TMUX").is_some() || term.starts_with("screen") || term.starts_with("tmux")
+}
+
+/// Checks whether a `tmux` version such as `3.2a` or `next-3.4` is at least
+/// `(major, minor)`
+fn tmux_version_at_least(version: &str, (major, minor): (u32, u32)) -> bool {
+    let version = version.trim_start_matches(|c: char| !c.is_ascii_digit());
+    let mut parts = version
+        .split('.')
+        .map(|part| part.trim_end_matches(|c: char| !c.is_ascii_digit()).parse::<u32>());
+    match (parts.next(), parts.next()) {
+        (Some(Ok(ma)), Some(Ok(mi))) => (ma, mi) >= (major, minor),
+        (Some(Ok(ma)), None) => ma > major,
+        _ => false,
+    }
+}
+
+/// Wrap an escape sequence in a `tmux` DCS passthrough, so that `tmux`
+/// forwards it to the outer terminal instead of swallowing it. The sequence is
+/// returned unchanged outside of `tmux`.
+///
+/// # Notes
+/// > `tmux` only forwards passthrough sequences when its `allow-passthrough`
+/// > option is on.
+///
+/// ```rust
+/// # use colored::control;
+/// let link = "\x1B]8;;https://docs.rs\x1B\\docs\x1B]8;;\x1B\\";
+/// if std::env::var_os("TMUX").is_none() {
+///     assert_eq!(control::tmux_passthrough(link), link);
+/// }
+/// ```
+#[must_use]
+pub fn tmux_passthrough(seq: &str) -> String {
+    if env::var_os("TMUX").is_none() {
+        return seq.to_owned();
+    }
+    wrap_passthrough(seq)
+}
+
+/// Wrap `seq` in a `tmux` DCS passthrough, doubling every escape character
+fn wrap_passthrough(seq: &str) -> String {
+    format!("\x1BPtmux;{}\x1B\\", seq.replace('\x1B', "\x1B\x1B"))
+}
+
+/// Checks whether the terminal is known to support the extended underline
+/// styles (curly, dotted, ...) and underline colors, i.e. `4:3` and `58`
+fn extended_underline_from_env(var: impl Fn(&str) -> Option<String>) -> bool {
+    let term_program = var("TERM_PROGRAM").unwrap_or_default();
+    let term = var("TERM").unwrap_or_default();
+    var("KITTY_WINDOW_ID").is_some()
+        || var("VTE_VERSION")
+            .and_then(|v| v.parse::<u32>().ok())
+            .map_or(false, |v| v >= 5102)
+        || ["WezTerm", "ghostty"].contains(&term_program.as_str())
+        || ["xterm-kitty", "foot", "xterm-ghostty", "wezterm"].contains(&term.as_str())
+}
+
+/// Columns and rows of the terminal on `stdout`, as reported by the terminal
+/// with the `sigwinch` feature, otherwise as exported in `COLUMNS` and `LINES`
+fn terminal_size_from_env(var: impl Fn(&str) -> Option<String>) -> Option<(u16, u16)> {
+    #[cfg(all(unix, feature = "sigwinch"))]
+    {
+        let mut size = std::mem::MaybeUninit::<libc::winsize>::zeroed();
+        if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, size.as_mut_ptr()) } == 0 {
+            let size = unsafe { size.assume_init() };
+            if size.ws_col > 0 && size.ws_row > 0 {
+                return Some((size.ws_col, size.ws_row));
+            }
+        }
+    }
+
+    let number = |name: &str| var(name)?.parse::<u16>().ok().filter(|&n| n > 0);
+    Some((number("COLUMNS")?, number("LINES")?))
+}
+
+/// Set when the terminal was resized, so that its capabilities are detected
+/// again the next time they are needed
+static STALE: AtomicBool = AtomicBool::new(false);
+
+/// A standard output stream, whose terminal status is checked on first use
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Stream {
+    /// The standard output
+    Stdout,
+    /// The standard error
+    Stderr,
+}
+
+impl Stream {
+    /// Checks whether the stream is connected to a terminal right now
+    fn check_tty(self) -> bool {
+        match self {
+            Self::Stdout => atty::is(atty::Stream::Stdout),
+            Self::Stderr => atty::is(atty::Stream::Stderr),
+        }
+    }
+}
+
+/// The terminal status of a [`Stream`] has not been checked yet
+const TTY_UNKNOWN: u8 = 0;
+/// The [`Stream`] is not a terminal
+const TTY_NO: u8 = 1;
+/// The [`Stream`] is a terminal
+const TTY_YES: u8 = 2;
+
+/// Whether to colorize, as chosen by the user of an application, typically
+/// with a `--color=auto|always|never` flag.
+///
+/// ```rust
+/// # use colored::*;
+/// let choice: ColorChoice = "never".parse().unwrap();
+/// control::set_color_choice(choice);
+/// assert_eq!(control::color_choice(), ColorChoice::Never);
+/// # control::set_color_choice(ColorChoice::Auto);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColorChoice {
+    /// Let the environment and the terminal decide
+    #[default]
+    Auto,
+    /// Always colorize
+    Always,
+    /// Never colorize
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = io::Error;
+
+    #[inline]
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        match src.trim().to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{src} is not one of auto, always or never"),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::Auto => "auto",
+            Self::Always => "always",
+            Self::Never => "never",
+        })
+    }
+}
+
+/// Renders colored strings according to its own [`ColorChoice`] instead of
+/// the global one, e.g. to colorize `stderr` but not a log file.
+///
+/// ```rust
+/// # use colored::*;
+/// let never = Colorizer::new(ColorChoice::Never);
+/// assert_eq!(never.paint(&"plain".red()).to_string(), "plain");
+/// ```
+#[derive(Clone, Default)]
+pub struct Colorizer {
+    /// Whether to colorize
+    choice:  ColorChoice,
+    /// Tag of everything painted, checked against the tag rules
+    tag:     Option<&'static str>,
+    /// Adjust the style of everything painted, in registration order
+    hooks:   Vec<Arc<StyleHook>>,
+    /// Renders everything painted, ANSI escape sequences if `None`
+    backend: Option<Arc<dyn RenderBackend>>,
+    /// How padding and truncation count the columns of emoji
+    width:   WidthPolicy,
+    /// Color names accepted by [`Colorizer::parse_color`], lowercase
+    names:   Option<Arc<HashMap<String, Color>>>,
+}
+
+/// A closure adjusting a [`ColoredString`] when it is painted
+type StyleHook = dyn Fn(&mut ColoredString) + Send + Sync;
+
+impl fmt::Debug for Colorizer {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Colorizer")
+            .field("choice", &self.choice)
+            .field("tag", &self.tag)
+            .field("hooks", &self.hooks.len())
+            .field("backend", &self.backend)
+            .field("width", &self.width)
+            .field("names", &self.names.as_ref().map_or(0, |names| names.len()))
+            .finish()
+    }
+}
+
+impl Colorizer {
+    /// Create a [`Colorizer`] following `choice`
+    #[inline]
+    #[must_use]
+    pub const fn new(choice: ColorChoice) -> Self {
+        Self {
+            choice,
+            tag: None,
+            hooks: Vec::new(),
+            backend: None,
+            width: WidthPolicy::wcwidth(),
+            names: None,
+        }
+    }
+
+    /// Pad and truncate everything painted to the width measured with
+    /// `policy`, so that both match the way the terminal displays emoji
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let modern = Colorizer::new(ColorChoice::Never).with_width_policy(WidthPolicy::grapheme());
+    /// assert_eq!(format!("[{:4}]", modern.paint(&"❤\u{FE0F}".red())), "[❤\u{FE0F}  ]");
+    /// assert_eq!(format!("[{:.2}]", modern.paint(&"❤\u{FE0F}!".red())), "[❤\u{FE0F}]");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn with_width_policy(mut self, policy: WidthPolicy) -> Self {
+        self.width = policy;
+        self
+    }
+
+    /// How padding and truncation count the columns of emoji
+    #[inline]
+    #[must_use]
+    pub const fn width_policy(&self) -> WidthPolicy {
+        self.width
+    }
+
+    /// Render everything painted with `backend` instead of ANSI escape
+    /// sequences, see [`render`](crate::render)
+    #[inline]
+    #[must_use]
+    pub fn with_backend<B: RenderBackend + 'static>(mut self, backend: B) -> Self {
+        self.backend = Some(Arc::new(backend));
+        self
+    }
+
+    /// Register a hook adjusting every string when it is painted, so that its
+    /// style can depend on runtime queries, e.g. the environment, focus
+    /// events or the time of day. Hooks run in registration order.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let decorations = Colorizer::new(ColorChoice::Auto).with_hook(|s| {
+    ///     if std::env::var_os("SCREEN_READER").is_some() {
+    ///         s.style_mut().insert(Styles::Dimmed);
+    ///     }
+    /// });
+    /// println!("{}", decorations.paint(&"──────".blue()));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&mut ColoredString) + Send + Sync + 'static,
+    {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Tag everything painted by the colorizer, so that it is only colorized
+    /// if the tag is enabled. See [`set_tag_enabled`]
+    #[inline]
+    #[must_use]
+    pub const fn tagged(mut self, tag: &'static str) -> Self {
+        self.tag = Some(tag);
+        self
+    }
+
+    /// The tag of the colorizer, if any
+    #[inline]
+    #[must_use]
+    pub const fn tag(&self) -> Option<&'static str> {
+        self.tag
+    }
+
+    /// The [`ColorChoice`] of the colorizer
+    #[inline]
+    #[must_use]
+    pub const fn choice(&self) -> ColorChoice {
+        self.choice
+    }
+
+    /// Change the [`ColorChoice`] of the colorizer
+    #[inline]
+    pub fn set_choice(&mut self, choice: ColorChoice) {
+        self.choice = choice;
+    }
+
+    /// Accept the color names of `names` in [`Colorizer::parse_color`], e.g.
+    /// the ones of the language of the user given by `locale::names` with the
+    /// `locale-names` feature.
+    /// The names are matched case-insensitively, before the English ones.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// # use std::collections::HashMap;
+    /// let mut colorizer = Colorizer::new(ColorChoice::Auto);
+    /// colorizer.set_color_names(HashMap::from([("Rot".to_owned(), Color::Red)]));
+    /// assert_eq!(colorizer.parse_color("rot"), Ok(Color::Red));
+    /// ```
+    #[inline]
+    pub fn set_color_names(&mut self, names: HashMap<String, Color>) {
+        let names = names
+            .into_iter()
+            .map(|(name, color)| (name.trim().to_lowercase(), color))
+            .collect();
+        self.names = Some(Arc::new(names));
+    }
+
+    /// Parse a color with the names given to [`Colorizer::set_color_names`],
+    /// falling back on [`Color::from_str`] for the English names, hex colors
+    /// and CSS functions
+    ///
+    /// # Errors
+    /// Will produce a [`ParseColorError`] if `src` is not a known color
+    #[inline]
+    pub fn parse_color(&self, src: &str) -> Result<Color, ParseColorError> {
+        self.names
+            .as_ref()
+            .and_then(|names| names.get(&src.trim().to_lowercase()))
+            .map_or_else(|| src.parse(), |color| Ok(*color))
+    }
+
+    /// Returns if strings painted by this colorizer are colorized. `Auto`
+    /// follows [`ShouldColorize::should_colorize`], and a disabled tag is
+    /// never colorized.
+    #[inline]
+    #[must_use]
+    pub fn should_colorize(&self) -> bool {
+        if let Some(scoped) = scoped_decision() {
+            return cfg!(not(any(feature = "no-color", feature = "strip-styles"))) && scoped;
+        }
+
+        cfg!(not(any(feature = "no-color", feature = "strip-styles")))
+            && self.tag.map_or(true, is_tag_enabled)
+            && match self.choice {
+                ColorChoice::Auto => SHOULD_COLORIZE.should_colorize(),
+                ColorChoice::Always => true,
+                ColorChoice::Never => false,
+            }
+    }
+
+    /// Display `s` according to the [`ColorChoice`] of the colorizer, after
+    /// running its hooks
+    #[inline]
+    #[must_use]
+    pub fn paint<'a>(&self, s: &'a ColoredString) -> Painted<'a> {
+        let colorize = self.should_colorize();
+        if self.hooks.is_empty() || !colorize {
+            return Painted {
+                s: Cow::Borrowed(s),
+                colorize,
+                backend: self.backend.clone(),
+                width: self.width,
+            };
+        }
+
+        let mut s = s.clone();
+        for hook in &self.hooks {
+            hook(&mut s);
+        }
+        Painted {
+            s: Cow::Owned(s),
+            colorize,
+            backend: self.backend.clone(),
+            width: self.width,
+        }
+    }
+}
+
+/// A [`ColoredString`] displayed by a [`Colorizer`], see
+/// [`Colorizer::paint`]
+#[derive(Clone, Debug)]
+pub struct Painted<'a> {
+    /// The string to display
+    s:        Cow<'a, ColoredString>,
+    /// Whether to display its colors and style
+    colorize: bool,
+    /// Renders the string, ANSI escape sequences if `None`
+    backend:  Option<Arc<dyn RenderBackend>>,
+    /// How padding and truncation count the columns of emoji
+    width:    WidthPolicy,
+}
+
+impl fmt::Display for Painted<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let backend = self.backend.as_deref().unwrap_or(&crate::render::Ansi);
+        self.s.fmt_with_backend(f, self.colorize, backend, self.width)
+    }
+}
+
+/// A [`ColoredString`] tagged with a target, which is only colorized if the
+/// tag is enabled, see [`ColoredString::tagged`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tagged {
+    /// The tagged string
+    s:   ColoredString,
+    /// The target of the string
+    tag: &'static str,
+}
+
+impl Tagged {
+    /// Tag `s` with `tag`
+    #[inline]
+    #[must_use]
+    pub const fn new(s: ColoredString, tag: &'static str) -> Self {
+        Self { s, tag }
+    }
+
+    /// The target of the string
+    #[inline]
+    #[must_use]
+    pub const fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    /// Remove the tag
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> ColoredString {
+        self.s
+    }
+}
+
+impl fmt::Display for Tagged {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.s
+            .fmt_with(f, self.s.has_colors() && is_tag_enabled(self.tag))
+    }
+}
+
+/// Whether strings tagged with each target are colorized
+#[derive(Debug)]
+struct TagRules {
+    /// Whether each tag with a rule is colorized
+    rules:   HashMap<String, bool>,
+    /// Whether the tags without a rule are colorized
+    default: bool,
+}
+
+impl Default for TagRules {
     #[inline]
     fn default() -> Self {
         Self {
-            clicolor:            true,
-            clicolor_force:      None,
-            has_manual_override: AtomicBool::new(false),
-            manual_override:     AtomicBool::new(false),
+            rules:   HashMap::new(),
+            default: true,
         }
     }
 }
 
-impl ShouldColorize {
-    /// Reads environment variables and checks if output is a tty to determine
-    /// whether colorization should be used or not.
-    /// `CLICOLOR_FORCE` takes highest priority, followed by `NO_COLOR`,
-    /// followed by `CLICOLOR` combined with tty check.
+/// A source deciding whether to colorize, see [`DecisionPolicy`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColorSource {
+    /// The manual override of the application, see [`set_override`]
+    Override,
+    /// `CLICOLOR_FORCE` set to anything but `0`, which enables colors
+    ClicolorForce,
+    /// `NO_COLOR` set, which disables colors
+    NoColor,
+    /// `CLICOLOR` set to `0`, which disables colors
+    Clicolor,
+}
+
+/// The precedence between the sources deciding whether to colorize. The
+/// first source which is set decides; when none is, colors are used if the
+/// output is a terminal and `CLICOLOR` is not `0`.
+///
+/// The default is the order of the [specs](http://bixense.com/clicolors/):
+/// the manual override, then `CLICOLOR_FORCE`, `NO_COLOR` and `CLICOLOR`.
+///
+/// ```rust
+/// # use colored::control::{self, ColorSource, DecisionPolicy};
+/// // `NO_COLOR` wins over everything, even the application flags
+/// control::set_decision_policy(DecisionPolicy::new(&[
+///     ColorSource::NoColor,
+///     ColorSource::Override,
+///     ColorSource::ClicolorForce,
+/// ]));
+/// # control::set_decision_policy(DecisionPolicy::default());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DecisionPolicy {
+    /// The sources, from the highest precedence to the lowest
+    order: [Option<ColorSource>; 4],
+}
+
+impl Default for DecisionPolicy {
     #[inline]
+    fn default() -> Self {
+        Self::new(&[
+            ColorSource::Override,
+            ColorSource::ClicolorForce,
+            ColorSource::NoColor,
+            ColorSource::Clicolor,
+        ])
+    }
+}
+
+impl DecisionPolicy {
+    /// Create a policy consulting `order` from the highest precedence to the
+    /// lowest. Sources which are not listed are ignored, and duplicates only
+    /// count once.
     #[must_use]
-    pub fn from_env() -> Self {
+    pub fn new(order: &[ColorSource]) -> Self {
+        let mut policy = Self { order: [None; 4] };
+        let mut len = 0;
+        for &source in order {
+            if len < policy.order.len() && !policy.order.contains(&Some(source)) {
+                policy.order[len] = Some(source);
+                len += 1;
+            }
+        }
+        policy
+    }
+
+    /// The sources, from the highest precedence to the lowest
+    #[inline]
+    pub fn order(&self) -> impl Iterator<Item = ColorSource> + '_ {
+        self.order.iter().flatten().copied()
+    }
+}
+
+/// Everything detected from the environment, which is cached until
+/// [`invalidate`] is called. Whether the output is a terminal is checked
+/// separately, see [`ShouldColorize::is_tty`].
+#[derive(Clone, Copy, Debug)]
+struct Detected {
+    /// `CLICOLOR` status
+    clicolor:         bool,
+    /// `CLICOLORFORCE` status
+    clicolor_force:   Option<bool>,
+    /// Number of colors the terminal is able to display
+    level:            ColorLevel,
+    /// Whether the terminal supports OSC 8 hyperlinks
+    hyperlinks:       bool,
+    /// Whether the terminal supports extended underline styles and colors
+    ext_underline:    bool,
+    /// Whether the terminal parses colon separated parameters
+    colon_sgr:        bool,
+    /// Whether `clicolor` only holds when the output is a terminal
+    tty_required:     bool,
+    /// Whether hyperlinks are forced even when the output is not a terminal
+    piped_hyperlinks: bool,
+    /// Whether `CLICOLOR_FORCE` is set to anything but `0`
+    env_force:        bool,
+    /// Whether `NO_COLOR` is set
+    env_no_color:     bool,
+    /// Whether `CLICOLOR` is set to `0`
+    env_clicolor_off: bool,
+    /// Columns and rows of the terminal
+    size:             Option<(u16, u16)>,
+}
+
+impl Default for Detected {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            clicolor:         true,
+            clicolor_force:   None,
+            level:            ColorLevel::Ansi16,
+            hyperlinks:       false,
+            ext_underline:    false,
+            colon_sgr:        false,
+            tty_required:     false,
+            piped_hyperlinks: false,
+            env_force:        false,
+            env_no_color:     false,
+            env_clicolor_off: false,
+            size:             None,
+        }
+    }
+}
+
+impl Detected {
+    /// Reads environment variables, assuming the output is a tty. See
+    /// [`ShouldColorize::from_env`]
+    fn from_env() -> Self {
+        let var = |name: &str| env::var(name).ok();
+        let hyperlinks = hyperlinks_from_env(var, true);
+        let piped_hyperlinks = hyperlinks_from_env(var, false);
+        let ext_underline = extended_underline_from_env(var);
+        // the terminals with extended underlines are the ones parsing colons
+        let colon_sgr = ext_underline && !in_multiplexer(var);
+        let env_force = ShouldColorize::normalize_env(env::var("CLICOLOR_FORCE")) == Some(true);
+        let env_no_color = env::var_os("NO_COLOR").is_some();
+        let env_clicolor_off = ShouldColorize::normalize_env(env::var("CLICOLOR")) == Some(false);
+        let size = terminal_size_from_env(var);
+
+        if cfg!(feature = "supports-color") {
+            let level = ColorLevel::supports_color(var, true);
+            return Self {
+                clicolor: level != ColorLevel::None,
+                level: level.max(ColorLevel::Ansi16),
+                hyperlinks,
+                ext_underline,
+                colon_sgr,
+                tty_required: ColorLevel::supports_color(var, false) == ColorLevel::None,
+                piped_hyperlinks,
+                env_force,
+                env_no_color,
+                env_clicolor_off,
+                size,
+                ..Self::default()
+            };
+        }
+
         Self {
-            clicolor: Self::normalize_env(env::var("CLICOLOR")).unwrap_or(true)
-                && atty::is(atty::Stream::Stdout),
-            clicolor_force: Self::resolve_clicolor_force(
+            clicolor: ShouldColorize::normalize_env(env::var("CLICOLOR")).unwrap_or(true)
+                && ShouldColorize::platform_supports_ansi(),
+            clicolor_force: ShouldColorize::resolve_clicolor_force(
                 env::var("NO_COLOR"),
                 env::var("CLICOLOR_FORCE"),
             ),
+            level: ColorLevel::from_env().max(ColorLevel::Ansi16),
+            hyperlinks,
+            ext_underline,
+            colon_sgr,
+            tty_required: true,
+            piped_hyperlinks,
+            env_force,
+            env_no_color,
+            env_clicolor_off,
+            size,
+        }
+    }
+}
+
+/// A flag to to if coloring should occur.
+pub struct ShouldColorize {
+    /// Capabilities detected from the environment
+    detected:            RwLock<Detected>,
+    /// Terminal status of each [`Stream`], checked on first use
+    tty:                 [AtomicU8; 2],
+    // XXX we can't use Option<Atomic> because we can't use &mut references to ShouldColorize
+    has_manual_override: AtomicBool,
+    manual_override:     AtomicBool,
+    /// [`Styles`] which are stripped from every string when it is rendered
+    disabled_styles:     AtomicU8,
+    /// Transformation applied to every [`Color`] when it is rendered
+    color_map:           RwLock<Option<fn(Color) -> Color>>,
+    /// Whether `color_map` is set, so that rendering only locks it then
+    has_color_map:       AtomicBool,
+    /// RGB values assumed for the indexed colors when approximating colors
+    palette:             RwLock<Palette>,
+    /// [`SgrSyntax`] set manually, if any
+    sgr_syntax:          AtomicU8,
+    /// Precedence between the sources deciding whether to colorize
+    policy:              RwLock<DecisionPolicy>,
+    /// Trade between bold and bright colors, as a [`BoldBright`] index
+    bold_bright:         AtomicU8,
+    /// Which streams must be terminals, as a [`TtyRule`]
+    tty_rule:            AtomicU8,
+    /// Whether tagged strings are colorized
+    tag_rules:           RwLock<TagRules>,
+    /// Escape sequences shared between strings, if enabled
+    interner:            StyleInterner,
+    /// Whether the terminal answered that it supports truecolors
+    truecolor_probe:     OnceLock<bool>,
+    /// Whether unknown color names are reported
+    strict_parsing:      AtomicBool,
+    /// Whether decorative styles and background colors are left out
+    accessibility:       AtomicBool,
+}
+
+/// Use this to force colored to ignore the environment and always/never
+/// colorize See `example/control.rs`
+#[inline]
+pub fn set_override(override_colorize: bool) {
+    SHOULD_COLORIZE.set_override(override_colorize);
+}
+
+thread_local! {
+    /// Whether to colorize on this thread, whatever anything else says, see
+    /// [`with_colors`] and [`without_colors`]
+    static SCOPED: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// Restores the scoped decision of the thread when dropped
+struct ScopeGuard(Option<bool>);
+
+impl Drop for ScopeGuard {
+    #[inline]
+    fn drop(&mut self) {
+        SCOPED.with(|scoped| scoped.set(self.0));
+    }
+}
+
+/// Run `f`, colorizing everything formatted on this thread meanwhile only if
+/// `colorize`
+fn scoped<T, F: FnOnce() -> T>(colorize: bool, f: F) -> T {
+    let _guard = ScopeGuard(SCOPED.with(|scoped| scoped.replace(Some(colorize))));
+    f()
+}
+
+/// The decision of the innermost [`with_colors`] or [`without_colors`]
+/// running on this thread, if any
+#[inline]
+fn scoped_decision() -> Option<bool> {
+    SCOPED.with(Cell::get)
+}
+
+/// Run `f` without colorizing anything formatted on this thread meanwhile,
+/// whatever the overrides, the environment or a [`Colorizer`] say, e.g. to
+/// guarantee plain `--porcelain` or JSON output. Scopes can be nested, and
+/// the previous decision is restored when `f` returns or panics.
+///
+/// ```rust
+/// # use colored::*;
+/// let json = without_colors(|| format!("{{\"status\": \"{}\"}}", "ok".green()));
+/// assert_eq!(json, r#"{"status": "ok"}"#);
+/// ```
+#[inline]
+pub fn without_colors<T, F: FnOnce() -> T>(f: F) -> T {
+    scoped(false, f)
+}
+
+/// Run `f`, colorizing everything formatted on this thread meanwhile,
+/// whatever the overrides, the environment or a [`Colorizer`] say. See
+/// [`without_colors`]
+#[inline]
+pub fn with_colors<T, F: FnOnce() -> T>(f: F) -> T {
+    scoped(true, f)
+}
+
+/// Remove the manual override and let the environment decide if it's ok to
+/// colorize See example/control.rs
+#[inline]
+pub fn unset_override() {
+    SHOULD_COLORIZE.unset_override();
+}
+
+/// Follow the user's `--color` choice: `Always` and `Never` override the
+/// environment, `Auto` removes the override. See [`set_override`]
+#[inline]
+pub fn set_color_choice(choice: ColorChoice) {
+    match choice {
+        ColorChoice::Auto => unset_override(),
+        ColorChoice::Always => set_override(true),
+        ColorChoice::Never => set_override(false),
+    }
+}
+
+/// The current [`ColorChoice`], derived from the manual override
+#[inline]
+#[must_use]
+pub fn color_choice() -> ColorChoice {
+    SHOULD_COLORIZE.color_choice()
+}
+
+/// Enable or disable colors for strings tagged with `tag`, e.g. to never
+/// colorize the `json` output. See [`ColoredString::tagged`]
+#[inline]
+pub fn set_tag_enabled(tag: &str, enabled: bool) {
+    SHOULD_COLORIZE.set_tag_enabled(tag, enabled);
+}
+
+/// Enable or disable colors for the tags without a rule, e.g. to only
+/// colorize the `diagnostics` tag
+///
+/// ```rust
+/// # use colored::*;
+/// control::set_tags_enabled_by_default(false);
+/// control::set_tag_enabled("diagnostics", true);
+/// assert!(!control::is_tag_enabled("json"));
+/// assert!(control::is_tag_enabled("diagnostics"));
+/// # control::clear_tag_rules();
+/// ```
+#[inline]
+pub fn set_tags_enabled_by_default(enabled: bool) {
+    SHOULD_COLORIZE.set_tags_enabled_by_default(enabled);
+}
+
+/// Remove every tag rule, enabling colors for every tag again
+#[inline]
+pub fn clear_tag_rules() {
+    SHOULD_COLORIZE.clear_tag_rules();
+}
+
+/// Whether strings tagged with `tag` may be colorized
+#[inline]
+#[must_use]
+pub fn is_tag_enabled(tag: &str) -> bool {
+    SHOULD_COLORIZE.is_tag_enabled(tag)
+}
+
+/// Strip the given [`Styles`] from every colored string when it is rendered,
+/// e.g. to never make the text blink. See `example/control.rs`
+#[inline]
+pub fn disable_style(style: Styles) {
+    SHOULD_COLORIZE.disable_style(style);
+}
+
+/// Allow the given [`Styles`] to be rendered again after a call to
+/// [`disable_style`]
+#[inline]
+pub fn enable_style(style: Styles) {
+    SHOULD_COLORIZE.enable_style(style);
+}
+
+/// Transform every [`Color`] with `map` when it is rendered, e.g. to route
+/// all output through a colorblind-safe palette. See `example/control.rs`
+#[inline]
+pub fn set_color_map(map: fn(Color) -> Color) {
+    SHOULD_COLORIZE.set_color_map(map);
+}
+
+/// Remove the [`Color`] transformation set by [`set_color_map`]
+#[inline]
+pub fn unset_color_map() {
+    SHOULD_COLORIZE.unset_color_map();
+}
+
+/// Set the precedence between `CLICOLOR`, `CLICOLOR_FORCE`, `NO_COLOR` and the
+/// manual override. See [`DecisionPolicy`]
+#[inline]
+pub fn set_decision_policy(policy: DecisionPolicy) {
+    SHOULD_COLORIZE.set_decision_policy(policy);
+}
+
+/// The precedence between the sources deciding whether to colorize
+#[inline]
+#[must_use]
+pub fn decision_policy() -> DecisionPolicy {
+    SHOULD_COLORIZE.decision_policy()
+}
+
+/// Trade bold and bright colors for each other when the terminal only displays
+/// 16 colors. See [`BoldBright`]
+#[inline]
+pub fn set_bold_bright(mode: BoldBright) {
+    SHOULD_COLORIZE.set_bold_bright(mode);
+}
+
+/// The trade between bold and bright colors. See [`BoldBright`]
+#[inline]
+#[must_use]
+pub fn bold_bright() -> BoldBright {
+    SHOULD_COLORIZE.bold_bright()
+}
+
+/// Require the streams of `rule` to be terminals for colors to be used, when
+/// the environment does not decide. See [`TtyRule`]
+#[inline]
+pub fn set_tty_rule(rule: TtyRule) {
+    SHOULD_COLORIZE.set_tty_rule(rule);
+}
+
+/// Which streams must be terminals for colors to be used, see [`TtyRule`]
+#[inline]
+#[must_use]
+pub fn tty_rule() -> TtyRule {
+    SHOULD_COLORIZE.tty_rule()
+}
+
+/// Report unknown color names converted to a [`Color`], e.g.
+/// `"typo".color("bleu")`, instead of silently using white: debug builds
+/// panic, release builds print a warning to `stderr`. Off by default
+#[inline]
+pub fn set_strict_parsing(strict: bool) {
+    SHOULD_COLORIZE.set_strict_parsing(strict);
+}
+
+/// Whether unknown color names are reported, see [`set_strict_parsing`]
+#[inline]
+#[must_use]
+pub fn strict_parsing() -> bool {
+    SHOULD_COLORIZE.strict_parsing()
+}
+
+/// Leave out the decorative styling when rendering, for screen readers and
+/// accessibility requirements: blink, dimmed, strikethrough and background
+/// colors. The foreground colors and the other styles, which usually carry
+/// meaning, are kept. Off by default
+///
+/// ```rust
+/// # use colored::*;
+/// control::set_accessibility_mode(true);
+/// // rendered as bold red text, without the blinking and the background
+/// println!("{}", "failed".red().bold().blink().on_white());
+/// # control::set_accessibility_mode(false);
+/// ```
+#[inline]
+pub fn set_accessibility_mode(enabled: bool) {
+    SHOULD_COLORIZE.set_accessibility_mode(enabled);
+}
+
+/// Whether decorative styling is left out, see [`set_accessibility_mode`]
+#[inline]
+#[must_use]
+pub fn accessibility_mode() -> bool {
+    SHOULD_COLORIZE.accessibility_mode()
+}
+
+/// Separate the parameters of truecolor codes with `syntax`, whatever the
+/// terminal is detected to support
+#[inline]
+pub fn set_sgr_syntax(syntax: SgrSyntax) {
+    SHOULD_COLORIZE.set_sgr_syntax(syntax);
+}
+
+/// Let the environment decide how the parameters of truecolor codes are
+/// separated
+#[inline]
+pub fn unset_sgr_syntax() {
+    SHOULD_COLORIZE.unset_sgr_syntax();
+}
+
+/// How the parameters of truecolor codes are separated
+#[inline]
+#[must_use]
+pub fn sgr_syntax() -> SgrSyntax {
+    SHOULD_COLORIZE.sgr_syntax()
+}
+
+/// Set the RGB values assumed for the indexed colors of the terminal, used
+/// when a truecolor is approximated by one of them in [`Color::to_ansi16`]
+/// and [`Color::to_ansi256`]
+///
+/// ```rust
+/// # use colored::{control, Color, Palette};
+/// let mut palette = Palette::xterm();
+/// palette.set(4, [0x26, 0x8B, 0xD2]);
+/// control::set_palette(palette);
+/// assert_eq!(Color::truecolor(0x20, 0x80, 0xD0).to_ansi16(), Color::Blue);
+/// ```
+#[inline]
+pub fn set_palette(palette: Palette) {
+    SHOULD_COLORIZE.set_palette(palette);
+}
+
+/// The RGB values assumed for the indexed colors of the terminal
+#[inline]
+#[must_use]
+pub fn palette() -> Palette {
+    SHOULD_COLORIZE.palette()
+}
+
+/// How many colors will be displayed, taking the manual override into
+/// account
+#[inline]
+#[must_use]
+pub fn color_level() -> ColorLevel {
+    SHOULD_COLORIZE.color_level()
+}
+
+/// Whether the terminal is known to support the extended underline styles
+/// and underline colors. Elsewhere, they should degrade to a plain underline.
+#[inline]
+#[must_use]
+pub fn supports_extended_underline() -> bool {
+    SHOULD_COLORIZE.supports_extended_underline()
+}
+
+/// Whether the terminal is known to support OSC 8 hyperlinks
+#[inline]
+#[must_use]
+pub fn supports_hyperlinks() -> bool {
+    SHOULD_COLORIZE.supports_hyperlinks()
+}
+
+/// Forget the cached terminal capabilities, and detect them again. Call this
+/// after redirecting the output or when the terminal may have changed.
+#[inline]
+pub fn invalidate() {
+    SHOULD_COLORIZE.invalidate();
+}
+
+/// The columns and rows of the terminal, cached until [`invalidate`] is called
+/// or the terminal is resized
+///
+/// # Notes
+/// > With the `sigwinch` feature, the size is asked to the terminal and
+/// > `invalidate_on_resize` refreshes it. Without it, the size is read from
+/// > `COLUMNS` and `LINES`, which shells only export on request.
+#[inline]
+#[must_use]
+pub fn terminal_size() -> Option<(u16, u16)> {
+    SHOULD_COLORIZE.terminal_size()
+}
+
+/// Check again whether the output streams are terminals, e.g. after
+/// daemonizing or re-opening `stdout`. Unlike [`invalidate`], the environment
+/// is not read again.
+#[inline]
+pub fn refresh_tty() {
+    SHOULD_COLORIZE.refresh_tty();
+}
+
+/// Detect the terminal capabilities and size again whenever the terminal is
+/// resized, by listening to `SIGWINCH`.
+///
+/// # Notes
+/// > This replaces any `SIGWINCH` handler previously installed by the
+/// > application. Only available on Unix with the `sigwinch` feature.
+#[cfg(all(unix, feature = "sigwinch"))]
+#[inline]
+pub fn invalidate_on_resize() {
+    extern "C" fn on_sigwinch(_: libc::c_int) {
+        STALE.store(true, Ordering::Relaxed);
+    }
+
+    unsafe {
+        libc::signal(libc::SIGWINCH, on_sigwinch as *const () as libc::sighandler_t);
+    }
+}
+
+/// Ask the terminal whether it supports truecolors, when the environment
+/// does not tell, e.g. over `ssh` where `COLORTERM` is usually not forwarded.
+/// On success, the detected level is raised to [`ColorLevel::TrueColor`].
+/// See [`ShouldColorize::probe_truecolor`]
+///
+/// ```rust,no_run
+/// # use colored::*;
+/// # use std::time::Duration;
+/// if control::color_level() < ColorLevel::TrueColor {
+///     let _ = control::probe_truecolor(Duration::from_millis(100));
+/// }
+/// ```
+///
+/// # Errors
+/// Will produce an error if the process has no controlling terminal, or if
+/// it cannot be switched to non-canonical mode
+///
+/// # Notes
+/// > Only available on Unix with the `probe` feature.
+#[cfg(all(unix, feature = "probe"))]
+#[inline]
+pub fn probe_truecolor(timeout: std::time::Duration) -> io::Result<bool> {
+    SHOULD_COLORIZE.probe_truecolor(timeout)
+}
+
+/// Every environment variable which may take part in the decision to
+/// colorize or in the detection of the terminal capabilities
+const REPORTED_VARS: [&str; 17] = [
+    "CLICOLOR",
+    "CLICOLOR_FORCE",
+    "NO_COLOR",
+    "FORCE_COLOR",
+    "TERM",
+    "COLORTERM",
+    "TERM_PROGRAM",
+    "TERM_PROGRAM_VERSION",
+    "TMUX",
+    "STY",
+    "VTE_VERSION",
+    "KITTY_WINDOW_ID",
+    "CI",
+    "WT_SESSION",
+    "ConEmuANSI",
+    "ANSICON",
+    "COLORFGBG",
+];
+
+/// Everything that took part in the decision to colorize, see
+/// [`diagnostics`]. Its `Display` lists it one item per line, for e.g. a
+/// `--debug-colors` flag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DetectionReport {
+    /// The environment variables, when the report was made
+    env:             Vec<(&'static str, Option<String>)>,
+    /// Whether stdout is a terminal
+    stdout_tty:      bool,
+    /// Whether stderr is a terminal
+    stderr_tty:      bool,
+    /// Whether the platform interprets ANSI escape codes, i.e. whether the
+    /// Windows console supports virtual terminal processing
+    platform_ansi:   bool,
+    /// The manual override
+    choice:          ColorChoice,
+    /// Whether the precedence of the sources was changed
+    custom_policy:   bool,
+    /// The colors the terminal is detected to display, whether or not colors
+    /// are enabled
+    level:           ColorLevel,
+    /// Whether strings are colorized
+    should_colorize: bool,
+    /// What decided whether strings are colorized
+    reason:          &'static str,
+}
+
+impl DetectionReport {
+    /// The environment variables, `None` when unset
+    #[inline]
+    pub fn env(&self) -> impl Iterator<Item = (&'static str, Option<&str>)> + '_ {
+        self.env.iter().map(|(name, value)| (*name, value.as_deref()))
+    }
+
+    /// Whether `stream` is a terminal
+    #[inline]
+    #[must_use]
+    pub const fn is_tty(&self, stream: Stream) -> bool {
+        match stream {
+            Stream::Stdout => self.stdout_tty,
+            Stream::Stderr => self.stderr_tty,
+        }
+    }
+
+    /// Whether the platform interprets ANSI escape codes. This is only false
+    /// on Windows consoles without virtual terminal processing
+    #[inline]
+    #[must_use]
+    pub const fn platform_supports_ansi(&self) -> bool {
+        self.platform_ansi
+    }
+
+    /// The manual override, see [`set_color_choice`]
+    #[inline]
+    #[must_use]
+    pub const fn color_choice(&self) -> ColorChoice {
+        self.choice
+    }
+
+    /// Whether the precedence of the sources was changed, see
+    /// [`set_decision_policy`]
+    #[inline]
+    #[must_use]
+    pub const fn has_custom_policy(&self) -> bool {
+        self.custom_policy
+    }
+
+    /// The colors the terminal is detected to display, whether or not colors
+    /// are enabled
+    #[inline]
+    #[must_use]
+    pub const fn level(&self) -> ColorLevel {
+        self.level
+    }
+
+    /// Whether strings are colorized
+    #[inline]
+    #[must_use]
+    pub const fn should_colorize(&self) -> bool {
+        self.should_colorize
+    }
+
+    /// A short explanation of what decided whether strings are colorized
+    #[inline]
+    #[must_use]
+    pub const fn reason(&self) -> &'static str {
+        self.reason
+    }
+}
+
+impl fmt::Display for DetectionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let yes_no = |b: bool| if b { "yes" } else { "no" };
+        writeln!(f, "colorize: {} ({})", yes_no(self.should_colorize), self.reason)?;
+        writeln!(f, "color choice: {}", self.choice)?;
+        writeln!(f, "custom decision policy: {}", yes_no(self.custom_policy))?;
+        writeln!(f, "detected level: {:?}", self.level)?;
+        writeln!(f, "stdout is a tty: {}", yes_no(self.stdout_tty))?;
+        writeln!(f, "stderr is a tty: {}", yes_no(self.stderr_tty))?;
+        writeln!(f, "platform supports ANSI: {}", yes_no(self.platform_ansi))?;
+        for (name, value) in &self.env {
+            match value {
+                Some(value) => writeln!(f, "{name}={value:?}")?,
+                None => writeln!(f, "{name} unset")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Report everything that takes part in the decision to colorize, to find
+/// out why colors are or aren't showing.
+///
+/// ```rust
+/// # use colored::*;
+/// let report = control::diagnostics();
+/// assert_eq!(report.should_colorize(), control::SHOULD_COLORIZE.should_colorize());
+/// eprint!("{report}");
+/// ```
+#[inline]
+#[must_use]
+pub fn diagnostics() -> DetectionReport {
+    SHOULD_COLORIZE.diagnostics()
+}
+
+/// The most escape sequences kept by the style interner, so that e.g.
+/// gradients with a different truecolor for every character cannot grow it
+/// forever
+const INTERNER_CAPACITY: usize = 4096;
+
+/// What decides the escape sequence switching on a style and colors: the
+/// style, the background, the foreground and the syntax of truecolor codes
+pub(crate) type StyleKey = (Style, Option<Color>, Option<Color>, SgrSyntax);
+
+/// Escape sequences shared between the strings with the same style and
+/// colors, see [`set_style_interning`]
+#[derive(Debug, Default)]
+struct StyleInterner {
+    /// Whether escape sequences are interned
+    enabled: AtomicBool,
+    /// The interned escape sequences
+    table:   RwLock<HashMap<StyleKey, Arc<str>>>,
+    /// Number of escape sequences found in the table
+    hits:    AtomicU64,
+    /// Number of escape sequences built because they were not in the table
+    misses:  AtomicU64,
+}
+
+/// How well the style interner did, see [`interner_stats`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct InternerStats {
+    /// Number of escape sequences found in the table
+    hits:    u64,
+    /// Number of escape sequences built because they were not in the table
+    misses:  u64,
+    /// Number of escape sequences in the table
+    entries: usize,
+}
+
+impl InternerStats {
+    /// Number of escape sequences found in the table
+    #[inline]
+    #[must_use]
+    pub const fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of escape sequences built because they were not in the table
+    #[inline]
+    #[must_use]
+    pub const fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Number of escape sequences in the table
+    #[inline]
+    #[must_use]
+    pub const fn entries(&self) -> usize {
+        self.entries
+    }
+}
+
+/// Reuse the escape sequence switching on the style and colors of strings
+/// which were rendered before, instead of building it again. Useful when
+/// millions of lines are written with a handful of styles. Disabled by
+/// default.
+///
+/// ```rust
+/// # use colored::*;
+/// control::set_style_interning(true);
+/// for i in 0..3 {
+///     let _ = format!("{}", i.to_string().red().bold());
+/// }
+/// println!("{:?}", control::interner_stats());
+/// # control::set_style_interning(false);
+/// ```
+#[inline]
+pub fn set_style_interning(enabled: bool) {
+    SHOULD_COLORIZE.set_style_interning(enabled);
+}
+
+/// How often the style interner reused an escape sequence, see
+/// [`set_style_interning`]
+#[inline]
+#[must_use]
+pub fn interner_stats() -> InternerStats {
+    SHOULD_COLORIZE.interner_stats()
+}
+
+/// Forget the interned escape sequences and reset the statistics
+#[inline]
+pub fn clear_interner() {
+    SHOULD_COLORIZE.clear_interner();
+}
+
+/// The persistent [`ShouldColorize`].
+pub static SHOULD_COLORIZE: GlobalColorize = GlobalColorize(LazyLock::new(ShouldColorize::from_env));
+
+/// The type of [`SHOULD_COLORIZE`], a [`ShouldColorize`] detected from the
+/// environment on first use. It hides whether `std::sync::LazyLock` or
+/// `once_cell` is used, so that the `once_cell` feature doesn't change the
+/// public API.
+pub struct GlobalColorize(LazyLock<ShouldColorize>);
+
+impl std::ops::Deref for GlobalColorize {
+    type Target = ShouldColorize;
+
+    #[inline]
+    fn deref(&self) -> &ShouldColorize {
+        &self.0
+    }
+}
+
+impl Default for ShouldColorize {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            detected:            RwLock::new(Detected::default()),
+            tty:                 [AtomicU8::new(TTY_UNKNOWN), AtomicU8::new(TTY_UNKNOWN)],
+            has_manual_override: AtomicBool::new(false),
+            manual_override:     AtomicBool::new(false),
+            disabled_styles:     AtomicU8::new(0),
+            color_map:           RwLock::new(None),
+            has_color_map:       AtomicBool::new(false),
+            palette:             RwLock::new(Palette::xterm()),
+            sgr_syntax:          AtomicU8::new(SGR_AUTO),
+            policy:              RwLock::new(DecisionPolicy::default()),
+            bold_bright:         AtomicU8::new(BoldBright::Off as u8),
+            tty_rule:            AtomicU8::new(TtyRule::Stdout as u8),
+            tag_rules:           RwLock::new(TagRules::default()),
+            interner:            StyleInterner::default(),
+            truecolor_probe:     OnceLock::new(),
+            strict_parsing:      AtomicBool::new(false),
+            accessibility:       AtomicBool::new(false),
+        }
+    }
+}
+
+impl ShouldColorize {
+    /// Reads environment variables to determine whether colorization should be
+    /// used or not. Whether the output is a tty is only checked when first
+    /// needed, see [`ShouldColorize::is_tty`].
+    /// `CLICOLOR_FORCE` takes highest priority, followed by `NO_COLOR`,
+    /// followed by `CLICOLOR` combined with tty check. On Windows, the console
+    /// must also be able to interpret ANSI escape codes.
+    #[inline]
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self {
+            detected: RwLock::new(Detected::from_env()),
             ..Self::default()
         }
     }
 
-    /// Returns if the current coloring is expected.
-    #[inline]
-    pub fn should_colorize(&self) -> bool {
-        if self.has_manual_override.load(Ordering::Relaxed) {
-            return self.manual_override.load(Ordering::Relaxed);
+    /// Forget the cached capabilities, and detect them again from the
+    /// environment and the terminal
+    #[inline]
+    pub fn invalidate(&self) {
+        let mut detected = Detected::from_env();
+        if self.truecolor_probe.get() == Some(&true) {
+            detected.level = ColorLevel::TrueColor;
+        }
+        *self.detected.write().expect("detected lock poisoned") = detected;
+        self.refresh_tty();
+    }
+
+    /// Ask the terminal whether it supports truecolors, by setting a
+    /// truecolor background and reading it back with DECRQSS. The terminal
+    /// is given `timeout` to answer. The answer is cached, and raises the
+    /// detected level to [`ColorLevel::TrueColor`] if positive.
+    ///
+    /// # Errors
+    /// Will produce an error if the process has no controlling terminal, or
+    /// if it cannot be switched to non-canonical mode
+    #[cfg(all(unix, feature = "probe"))]
+    pub fn probe_truecolor(&self, timeout: std::time::Duration) -> io::Result<bool> {
+        if let Some(&supported) = self.truecolor_probe.get() {
+            return Ok(supported);
+        }
+
+        let supported = crate::probe::query_truecolor(timeout)?;
+        let supported = *self.truecolor_probe.get_or_init(|| supported);
+        if supported {
+            self.detected.write().expect("detected lock poisoned").level = ColorLevel::TrueColor;
+        }
+        Ok(supported)
+    }
+
+    /// Returns if `stream` is a terminal. This is checked on first use and
+    /// cached until [`ShouldColorize::refresh_tty`] is called.
+    #[inline]
+    pub fn is_tty(&self, stream: Stream) -> bool {
+        let cached = &self.tty[stream as usize];
+        match cached.load(Ordering::Relaxed) {
+            TTY_YES => true,
+            TTY_NO => false,
+            _ => {
+                let is_tty = stream.check_tty();
+                cached.store(if is_tty { TTY_YES } else { TTY_NO }, Ordering::Relaxed);
+                is_tty
+            },
+        }
+    }
+
+    /// Forget whether the streams are terminals, so that it is checked again
+    #[inline]
+    pub fn refresh_tty(&self) {
+        for cached in &self.tty {
+            cached.store(TTY_UNKNOWN, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns how many colors will be displayed. This is
+    /// [`ColorLevel::None`] whenever coloring is not expected.
+    #[inline]
+    pub fn color_level(&self) -> ColorLevel {
+        if self.should_colorize() {
+            self.detected().level
+        } else {
+            ColorLevel::None
+        }
+    }
+
+    /// The number of colors the terminal is detected to display, whether or
+    /// not coloring is expected
+    #[inline]
+    pub(crate) fn detected_level(&self) -> ColorLevel {
+        self.detected().level
+    }
+
+    /// Returns if the current coloring is expected. Inside
+    /// [`with_colors`] or [`without_colors`], their decision wins.
+    #[inline]
+    pub fn should_colorize(&self) -> bool {
+        if let Some(scoped) = scoped_decision() {
+            return scoped;
+        }
+
+        let policy = self.decision_policy();
+        if policy != DecisionPolicy::default() {
+            return self.decide(policy);
+        }
+
+        if self.has_manual_override.load(Ordering::Relaxed) {
+            return self.manual_override.load(Ordering::Relaxed);
+        }
+
+        let detected = self.detected();
+        if let Some(forced_value) = detected.clicolor_force {
+            return forced_value;
+        }
+
+        detected.clicolor && (!detected.tty_required || self.tty_rule_holds())
+    }
+
+    /// Returns if the terminal is known to support the extended underline
+    /// styles and underline colors. This is false whenever coloring is not
+    /// expected.
+    #[inline]
+    pub fn supports_extended_underline(&self) -> bool {
+        self.should_colorize() && self.detected().ext_underline
+    }
+
+    /// The columns and rows of the terminal, cached with the capabilities
+    #[inline]
+    pub fn terminal_size(&self) -> Option<(u16, u16)> {
+        self.detected().size
+    }
+
+    /// Returns if the terminal is known to support OSC 8 hyperlinks. A manual
+    /// override disabling colors also disables hyperlinks.
+    #[inline]
+    pub fn supports_hyperlinks(&self) -> bool {
+        if self.has_manual_override.load(Ordering::Relaxed)
+            && !self.manual_override.load(Ordering::Relaxed)
+        {
+            return false;
+        }
+
+        let detected = self.detected();
+        if self.is_tty(Stream::Stdout) {
+            detected.hyperlinks
+        } else {
+            detected.piped_hyperlinks
+        }
+    }
+
+    /// Reuse the escape sequences of the styles which were rendered before
+    #[inline]
+    pub fn set_style_interning(&self, enabled: bool) {
+        self.interner.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// How often the style interner reused an escape sequence
+    #[inline]
+    pub fn interner_stats(&self) -> InternerStats {
+        InternerStats {
+            hits:    self.interner.hits.load(Ordering::Relaxed),
+            misses:  self.interner.misses.load(Ordering::Relaxed),
+            entries: self.interner.table.read().expect("interner lock poisoned").len(),
+        }
+    }
+
+    /// Forget the interned escape sequences and reset the statistics
+    #[inline]
+    pub fn clear_interner(&self) {
+        self.interner.table.write().expect("interner lock poisoned").clear();
+        self.interner.hits.store(0, Ordering::Relaxed);
+        self.interner.misses.store(0, Ordering::Relaxed);
+    }
+
+    /// The escape sequence for `key`, taken from the interner if enabled or
+    /// built with `build`
+    pub(crate) fn intern_style(&self, key: StyleKey, build: impl FnOnce() -> String) -> Arc<str> {
+        let interner = &self.interner;
+        if !interner.enabled.load(Ordering::Relaxed) {
+            return Arc::from(build());
+        }
+
+        if let Some(style) = interner.table.read().expect("interner lock poisoned").get(&key) {
+            interner.hits.fetch_add(1, Ordering::Relaxed);
+            return Arc::clone(style);
+        }
+
+        interner.misses.fetch_add(1, Ordering::Relaxed);
+        let style: Arc<str> = Arc::from(build());
+        let mut table = interner.table.write().expect("interner lock poisoned");
+        if table.len() < INTERNER_CAPACITY {
+            table.insert(key, Arc::clone(&style));
+        }
+        style
+    }
+
+    /// Report everything that takes part in the decision to colorize. The
+    /// environment variables are read again, while the capabilities are the
+    /// cached ones
+    pub fn diagnostics(&self) -> DetectionReport {
+        let detected = self.detected();
+        let stdout_tty = self.is_tty(Stream::Stdout);
+        let custom_policy = self.decision_policy() != DecisionPolicy::default();
+        let reason = if let Some(scoped) = scoped_decision() {
+            if scoped {
+                "forced by with_colors"
+            } else {
+                "disabled by without_colors"
+            }
+        } else if custom_policy {
+            "decided by the custom decision policy"
+        } else if self.has_manual_override.load(Ordering::Relaxed) {
+            "set by the manual override"
+        } else if detected.clicolor_force == Some(true) {
+            "forced by CLICOLOR_FORCE"
+        } else if detected.clicolor_force == Some(false) {
+            "disabled by NO_COLOR"
+        } else if !detected.clicolor {
+            if cfg!(feature = "supports-color") {
+                "the terminal doesn't support colors"
+            } else if Self::platform_supports_ansi() {
+                "disabled by CLICOLOR"
+            } else {
+                "the console doesn't interpret ANSI escape codes"
+            }
+        } else if detected.tty_required && !self.tty_rule_holds() {
+            match self.tty_rule() {
+                TtyRule::Stdout => "stdout is not a terminal",
+                TtyRule::Stderr => "stderr is not a terminal",
+                TtyRule::Both => "stdout or stderr is not a terminal",
+                TtyRule::Either => "neither stdout nor stderr is a terminal",
+            }
+        } else {
+            "colors are supported"
+        };
+
+        DetectionReport {
+            env: REPORTED_VARS
+                .iter()
+                .map(|&name| (name, env::var(name).ok()))
+                .collect(),
+            stdout_tty,
+            stderr_tty: self.is_tty(Stream::Stderr),
+            platform_ansi: Self::platform_supports_ansi(),
+            choice: self.color_choice(),
+            custom_policy,
+            level: detected.level,
+            should_colorize: self.should_colorize(),
+            reason,
+        }
+    }
+
+    /// Use this to force colored to ignore the environment and always/never
+    /// colorize
+    #[inline]
+    pub fn set_override(&self, override_colorize: bool) {
+        self.has_manual_override.store(true, Ordering::Relaxed);
+        self.manual_override
+            .store(override_colorize, Ordering::Relaxed);
+    }
+
+    /// Remove the manual override and let the environment decide if it's ok to
+    /// colorize
+    #[inline]
+    pub fn unset_override(&self) {
+        self.has_manual_override.store(false, Ordering::Relaxed);
+    }
+
+    /// The [`ColorChoice`] matching the manual override
+    #[inline]
+    pub fn color_choice(&self) -> ColorChoice {
+        if !self.has_manual_override.load(Ordering::Relaxed) {
+            ColorChoice::Auto
+        } else if self.manual_override.load(Ordering::Relaxed) {
+            ColorChoice::Always
+        } else {
+            ColorChoice::Never
+        }
+    }
+
+    /// Strip the given [`Styles`] from every colored string when it is
+    /// rendered
+    #[inline]
+    pub fn disable_style(&self, style: Styles) {
+        self.disabled_styles
+            .fetch_or(style.to_u8(), Ordering::Relaxed);
+    }
+
+    /// Allow the given [`Styles`] to be rendered again
+    #[inline]
+    pub fn enable_style(&self, style: Styles) {
+        self.disabled_styles
+            .fetch_and(!style.to_u8(), Ordering::Relaxed);
+    }
+
+    /// Returns if the given [`Styles`] is allowed to be rendered
+    #[inline]
+    pub fn is_style_enabled(&self, style: Styles) -> bool {
+        self.disabled_styles.load(Ordering::Relaxed) & style.to_u8() == 0
+    }
+
+    /// Remove the disabled [`Styles`] from a [`Style`]
+    pub(crate) fn filter_style(&self, style: Style) -> Style {
+        style.without(self.disabled_styles.load(Ordering::Relaxed))
+    }
+
+    /// Transform every [`Color`] with `map` when it is rendered
+    #[inline]
+    pub fn set_color_map(&self, map: fn(Color) -> Color) {
+        *self.color_map.write().expect("color map lock poisoned") = Some(map);
+        self.has_color_map.store(true, Ordering::Release);
+    }
+
+    /// Remove the [`Color`] transformation
+    #[inline]
+    pub fn unset_color_map(&self) {
+        self.has_color_map.store(false, Ordering::Release);
+        *self.color_map.write().expect("color map lock poisoned") = None;
+    }
+
+    /// Apply the [`Color`] transformation, if any
+    pub(crate) fn map_color(&self, color: Color) -> Color {
+        if !self.has_color_map.load(Ordering::Acquire) {
+            return color;
+        }
+
+        self.color_map
+            .read()
+            .expect("color map lock poisoned")
+            .map_or(color, |map| map(color))
+    }
+
+    /// Enable or disable colors for strings tagged with `tag`
+    #[inline]
+    pub fn set_tag_enabled(&self, tag: &str, enabled: bool) {
+        self.tag_rules
+            .write()
+            .expect("tag rules lock poisoned")
+            .rules
+            .insert(tag.to_owned(), enabled);
+    }
+
+    /// Enable or disable colors for the tags without a rule
+    #[inline]
+    pub fn set_tags_enabled_by_default(&self, enabled: bool) {
+        self.tag_rules.write().expect("tag rules lock poisoned").default = enabled;
+    }
+
+    /// Remove every tag rule
+    #[inline]
+    pub fn clear_tag_rules(&self) {
+        *self.tag_rules.write().expect("tag rules lock poisoned") = TagRules::default();
+    }
+
+    /// Whether strings tagged with `tag` may be colorized
+    #[inline]
+    pub fn is_tag_enabled(&self, tag: &str) -> bool {
+        let rules = self.tag_rules.read().expect("tag rules lock poisoned");
+        rules.rules.get(tag).copied().unwrap_or(rules.default)
+    }
+
+    /// Set the precedence between the sources deciding whether to colorize
+    #[inline]
+    pub fn set_decision_policy(&self, policy: DecisionPolicy) {
+        *self.policy.write().expect("policy lock poisoned") = policy;
+    }
+
+    /// The precedence between the sources deciding whether to colorize
+    #[inline]
+    pub fn decision_policy(&self) -> DecisionPolicy {
+        *self.policy.read().expect("policy lock poisoned")
+    }
+
+    /// Trade bold and bright colors for each other when the terminal only
+    /// displays 16 colors
+    #[inline]
+    pub fn set_bold_bright(&self, mode: BoldBright) {
+        self.bold_bright.store(mode as u8, Ordering::Relaxed);
+    }
+
+    /// The trade between bold and bright colors
+    #[inline]
+    pub fn bold_bright(&self) -> BoldBright {
+        match self.bold_bright.load(Ordering::Relaxed) {
+            1 => BoldBright::BrightToBold,
+            2 => BoldBright::BoldToBright,
+            _ => BoldBright::Off,
+        }
+    }
+
+    /// Require the streams of `rule` to be terminals for colors to be used,
+    /// when the environment does not decide
+    #[inline]
+    pub fn set_tty_rule(&self, rule: TtyRule) {
+        self.tty_rule.store(rule as u8, Ordering::Relaxed);
+    }
+
+    /// Which streams must be terminals for colors to be used
+    #[inline]
+    pub fn tty_rule(&self) -> TtyRule {
+        match self.tty_rule.load(Ordering::Relaxed) {
+            1 => TtyRule::Stderr,
+            2 => TtyRule::Both,
+            3 => TtyRule::Either,
+            _ => TtyRule::Stdout,
+        }
+    }
+
+    /// Report unknown color names converted to a [`Color`] instead of
+    /// silently using white
+    #[inline]
+    pub fn set_strict_parsing(&self, strict: bool) {
+        self.strict_parsing.store(strict, Ordering::Relaxed);
+    }
+
+    /// Whether unknown color names are reported
+    #[inline]
+    pub fn strict_parsing(&self) -> bool {
+        self.strict_parsing.load(Ordering::Relaxed)
+    }
+
+    /// Leave out blink, dimmed, strikethrough and background colors when
+    /// rendering
+    #[inline]
+    pub fn set_accessibility_mode(&self, enabled: bool) {
+        self.accessibility.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether decorative styling is left out
+    #[inline]
+    pub fn accessibility_mode(&self) -> bool {
+        self.accessibility.load(Ordering::Relaxed)
+    }
+
+    /// Remove the decorative styles and the background color in
+    /// accessibility mode
+    pub(crate) fn adjust_accessibility(&self, style: Style, bg: Option<Color>) -> (Style, Option<Color>) {
+        if !self.accessibility_mode() {
+            return (style, bg);
+        }
+
+        let decorative = Styles::Blink.to_u8() | Styles::Dimmed.to_u8() | Styles::Strikethrough.to_u8();
+        (style.without(decorative), None)
+    }
+
+    /// Apply the [`BoldBright`] mode to a style and its foreground color,
+    /// for a terminal displaying `level`
+    pub(crate) fn adjust_bold_bright(
+        &self,
+        mut style: Style,
+        fg: Option<Color>,
+        level: ColorLevel,
+    ) -> (Style, Option<Color>) {
+        let mode = self.bold_bright();
+        if mode == BoldBright::Off || level != ColorLevel::Ansi16 {
+            return (style, fg);
+        }
+
+        match (mode, fg) {
+            (BoldBright::BrightToBold, Some(color)) if color.is_bright() => {
+                style.insert(Styles::Bold);
+                return (style, Some(color.to_normal()));
+            },
+            (BoldBright::BoldToBright, Some(color)) if style.contains(Styles::Bold) => {
+                let bright = color.to_bright();
+                if bright != color {
+                    style.remove(Styles::Bold);
+                    return (style, Some(bright));
+                }
+            },
+            _ => {},
+        }
+        (style, fg)
+    }
+
+    /// Separate the parameters of truecolor codes with `syntax`
+    #[inline]
+    pub fn set_sgr_syntax(&self, syntax: SgrSyntax) {
+        let value = match syntax {
+            SgrSyntax::Semicolon => SGR_SEMICOLON,
+            SgrSyntax::Colon => SGR_COLON,
+        };
+        self.sgr_syntax.store(value, Ordering::Relaxed);
+    }
+
+    /// Let the environment decide how the parameters of truecolor codes are
+    /// separated
+    #[inline]
+    pub fn unset_sgr_syntax(&self) {
+        self.sgr_syntax.store(SGR_AUTO, Ordering::Relaxed);
+    }
+
+    /// How the parameters of truecolor codes are separated. Colons are only
+    /// used by default on terminals known to parse them.
+    #[inline]
+    pub fn sgr_syntax(&self) -> SgrSyntax {
+        self.sgr_syntax_with(self.detected().colon_sgr)
+    }
+
+    /// The number of colors and the [`SgrSyntax`] to render with, reading
+    /// the detected capabilities once for both
+    #[inline]
+    pub(crate) fn render_caps(&self) -> (ColorLevel, SgrSyntax) {
+        let detected = self.detected();
+        (detected.level, self.sgr_syntax_with(detected.colon_sgr))
+    }
+
+    /// Set the RGB values assumed for the indexed colors of the terminal
+    #[inline]
+    pub fn set_palette(&self, palette: Palette) {
+        *self.palette.write().expect("palette lock poisoned") = palette;
+    }
+
+    /// The RGB values assumed for the indexed colors of the terminal
+    #[inline]
+    pub fn palette(&self) -> Palette {
+        *self.palette.read().expect("palette lock poisoned")
+    }
+
+    // private
+
+    /// Whether to colorize according to a custom [`DecisionPolicy`]
+    fn decide(&self, policy: DecisionPolicy) -> bool {
+        let detected = self.detected();
+        for source in policy.order() {
+            let decision = match source {
+                ColorSource::Override => self
+                    .has_manual_override
+                    .load(Ordering::Relaxed)
+                    .then(|| self.manual_override.load(Ordering::Relaxed)),
+                ColorSource::ClicolorForce => detected.env_force.then_some(true),
+                ColorSource::NoColor => detected.env_no_color.then_some(false),
+                ColorSource::Clicolor => detected.env_clicolor_off.then_some(false),
+            };
+            if let Some(decision) = decision {
+                return decision;
+            }
         }
 
-        if let Some(forced_value) = self.clicolor_force {
-            return forced_value;
+        detected.clicolor && (!detected.tty_required || self.tty_rule_holds())
+    }
+
+    /// Whether the streams required by the [`TtyRule`] are terminals
+    fn tty_rule_holds(&self) -> bool {
+        match self.tty_rule() {
+            TtyRule::Stdout => self.is_tty(Stream::Stdout),
+            TtyRule::Stderr => self.is_tty(Stream::Stderr),
+            TtyRule::Both => self.is_tty(Stream::Stdout) && self.is_tty(Stream::Stderr),
+            TtyRule::Either => self.is_tty(Stream::Stdout) || self.is_tty(Stream::Stderr),
         }
+    }
 
-        self.clicolor
+    /// How the parameters of truecolor codes are separated, `colon_sgr`
+    /// telling whether the terminal parses colons
+    fn sgr_syntax_with(&self, colon_sgr: bool) -> SgrSyntax {
+        match self.sgr_syntax.load(Ordering::Relaxed) {
+            SGR_SEMICOLON => SgrSyntax::Semicolon,
+            SGR_COLON => SgrSyntax::Colon,
+            _ if colon_sgr => SgrSyntax::Colon,
+            _ => SgrSyntax::Semicolon,
+        }
     }
 
-    /// Use this to force colored to ignore the environment and always/never
-    /// colorize
-    #[inline]
-    pub fn set_override(&self, override_colorize: bool) {
-        self.has_manual_override.store(true, Ordering::Relaxed);
-        self.manual_override
-            .store(override_colorize, Ordering::Relaxed);
+    /// The cached capabilities, detected again if the terminal was resized.
+    /// The flag is only swapped once it is seen set, so that rendering does
+    /// not write to it.
+    fn detected(&self) -> Detected {
+        if STALE.load(Ordering::Relaxed) && STALE.swap(false, Ordering::Relaxed) {
+            self.invalidate();
+        }
+        *self.detected.read().expect("detected lock poisoned")
     }
 
-    /// Remove the manual override and let the environment decide if it's ok to
-    /// colorize
-    #[inline]
-    pub fn unset_override(&self) {
-        self.has_manual_override.store(false, Ordering::Relaxed);
+    /// Windows consoles only interpret ANSI escape codes since Windows 10
+    #[cfg(windows)]
+    fn platform_supports_ansi() -> bool {
+        windows_supports_ansi()
     }
 
-    // private
+    /// Every other platform interprets ANSI escape codes
+    #[cfg(not(windows))]
+    const fn platform_supports_ansi() -> bool {
+        true
+    }
 
     fn normalize_env(env_res: Result<String, env::VarError>) -> Option<bool> {
         env_res.map_or(None, |string| Some(string != "0"))
@@ -169,7 +2256,39 @@ impl ShouldColorize {
 
 #[cfg(test)]
 mod specs {
-    use super::{AtomicBool, Default, Ordering, ShouldColorize};
+    use super::{
+        extended_underline_from_env,
+        hyperlinks_from_env,
+        scoped_decision,
+        terminal_size_from_env,
+        tmux_version_at_least,
+        with_colors,
+        without_colors,
+        wrap_passthrough,
+        AtomicBool,
+        AtomicU8,
+        BoldBright,
+        Color,
+        ColorChoice,
+        ColorLevel,
+        ColorSource,
+        Colorizer,
+        DecisionPolicy,
+        Default,
+        Detected,
+        InternerStats,
+        Ordering,
+        ParseColorError,
+        RwLock,
+        SgrSyntax,
+        ShouldColorize,
+        Stream,
+        Style,
+        Styles,
+        TtyRule,
+        TTY_NO,
+        TTY_YES,
+    };
     use rspec::{self, describe};
     use std::{env, sync::Arc};
 
@@ -280,6 +2399,478 @@ mod specs {
                 );
             });
 
+            ctx.context("ColorLevel::from_term", |ctx| {
+                ctx.it("should detect truecolor from COLORTERM", |_| {
+                    let level = ColorLevel::from_term(|name| match name {
+                        "COLORTERM" => Some(String::from("truecolor")),
+                        _ => None,
+                    });
+                    assert_eq!(ColorLevel::TrueColor, level);
+                });
+
+                ctx.it("should detect 256 colors from TERM", |_| {
+                    let level = ColorLevel::from_term(|name| match name {
+                        "TERM" => Some(String::from("xterm-256color")),
+                        _ => None,
+                    });
+                    assert_eq!(ColorLevel::Ansi256, level);
+                });
+
+                ctx.it("should not trust COLORTERM for 256 color terminals", |_| {
+                    let level = ColorLevel::from_term(|name| match name {
+                        "COLORTERM" => Some(String::from("truecolor")),
+                        "TERM" => Some(String::from("screen-256color")),
+                        _ => None,
+                    });
+                    assert_eq!(ColorLevel::Ansi256, level);
+                });
+
+                ctx.it("should only trust truecolor inside tmux 3.2 or later", |_| {
+                    let env = |version: &'static str| {
+                        move |name: &str| match name {
+                            "COLORTERM" => Some(String::from("truecolor")),
+                            "TERM" => Some(String::from("tmux-256color")),
+                            "TERM_PROGRAM" => Some(String::from("tmux")),
+                            "TERM_PROGRAM_VERSION" => Some(String::from(version)),
+                            _ => None,
+                        }
+                    };
+                    assert_eq!(ColorLevel::TrueColor, ColorLevel::from_term(env("3.3a")));
+                    assert_eq!(ColorLevel::Ansi256, ColorLevel::from_term(env("3.1")));
+                    assert_eq!(
+                        ColorLevel::Ansi16,
+                        ColorLevel::from_term(|name| match name {
+                            "COLORTERM" => Some(String::from("truecolor")),
+                            "TERM" => Some(String::from("screen")),
+                            _ => None,
+                        })
+                    );
+                });
+
+                ctx.it("should parse tmux versions", |_| {
+                    assert!(tmux_version_at_least("3.2", (3, 2)));
+                    assert!(tmux_version_at_least("next-3.4", (3, 2)));
+                    assert!(tmux_version_at_least("4", (3, 2)));
+                    assert!(!tmux_version_at_least("2.9a", (3, 2)));
+                    assert!(!tmux_version_at_least("master", (3, 2)));
+                });
+
+                ctx.it("should wrap sequences for tmux passthrough", |_| {
+                    assert_eq!(
+                        "\x1BPtmux;\x1B\x1B]8;;u\x1B\x1B\\\x1B\\",
+                        wrap_passthrough("\x1B]8;;u\x1B\\")
+                    );
+                });
+
+                ctx.it("should default to 16 colors", |_| {
+                    assert_eq!(ColorLevel::Ansi16, ColorLevel::from_term(|_| None));
+                });
+            });
+
+            ctx.context("ColorLevel::supports_color", |ctx| {
+                ctx.it("should honor FORCE_COLOR even without a tty", |_| {
+                    let level = ColorLevel::supports_color(
+                        |name| (name == "FORCE_COLOR").then(|| String::from("2")),
+                        false,
+                    );
+                    assert_eq!(ColorLevel::Ansi256, level);
+                });
+
+                ctx.it("should honor CLICOLOR_FORCE even without a tty", |_| {
+                    let level = ColorLevel::supports_color(
+                        |name| match name {
+                            "CLICOLOR_FORCE" => Some(String::from("1")),
+                            "TERM" => Some(String::from("xterm-256color")),
+                            _ => None,
+                        },
+                        false,
+                    );
+                    assert_eq!(ColorLevel::Ansi16, level);
+                    let level = ColorLevel::supports_color(
+                        |name| (name == "CLICOLOR_FORCE").then(|| String::from("0")),
+                        false,
+                    );
+                    assert_eq!(ColorLevel::None, level);
+                });
+
+                ctx.it("should not raise a forced level", |_| {
+                    let level = ColorLevel::supports_color(
+                        |name| match name {
+                            "FORCE_COLOR" => Some(String::from("1")),
+                            "COLORTERM" => Some(String::from("truecolor")),
+                            _ => None,
+                        },
+                        false,
+                    );
+                    assert_eq!(ColorLevel::Ansi16, level);
+                });
+
+                ctx.it("should return None without a tty", |_| {
+                    let level = ColorLevel::supports_color(
+                        |name| (name == "TERM").then(|| String::from("xterm-256color")),
+                        false,
+                    );
+                    assert_eq!(ColorLevel::None, level);
+                });
+
+                ctx.it("should return None if NO_COLOR is set or TERM is dumb", |_| {
+                    let level = ColorLevel::supports_color(
+                        |name| (name == "NO_COLOR").then(|| String::from("1")),
+                        true,
+                    );
+                    assert_eq!(ColorLevel::None, level);
+                    let level = ColorLevel::supports_color(
+                        |name| (name == "TERM").then(|| String::from("dumb")),
+                        true,
+                    );
+                    assert_eq!(ColorLevel::None, level);
+                });
+
+                ctx.it("should detect the level from TERM", |_| {
+                    let level = ColorLevel::supports_color(
+                        |name| (name == "TERM").then(|| String::from("xterm-direct")),
+                        true,
+                    );
+                    assert_eq!(ColorLevel::TrueColor, level);
+                    let level = ColorLevel::supports_color(
+                        |name| (name == "TERM").then(|| String::from("xterm-256color")),
+                        true,
+                    );
+                    assert_eq!(ColorLevel::Ansi256, level);
+                    let level = ColorLevel::supports_color(
+                        |name| (name == "TERM").then(|| String::from("xterm")),
+                        true,
+                    );
+                    assert_eq!(ColorLevel::Ansi16, level);
+                });
+            });
+
+            ctx.context("::set_decision_policy", |ctx| {
+                ctx.it("should let NO_COLOR win over the manual override", |_| {
+                    let colorize_control = ShouldColorize {
+                        detected: RwLock::new(Detected {
+                            env_no_color: true,
+                            ..Detected::default()
+                        }),
+                        has_manual_override: AtomicBool::new(true),
+                        manual_override: AtomicBool::new(true),
+                        ..ShouldColorize::default()
+                    };
+                    assert!(colorize_control.should_colorize());
+                    colorize_control.set_decision_policy(DecisionPolicy::new(&[
+                        ColorSource::NoColor,
+                        ColorSource::Override,
+                    ]));
+                    assert!(!colorize_control.should_colorize());
+                });
+
+                ctx.it("should let CLICOLOR=0 win over CLICOLOR_FORCE", |_| {
+                    let colorize_control = ShouldColorize {
+                        detected: RwLock::new(Detected {
+                            env_force: true,
+                            env_clicolor_off: true,
+                            ..Detected::default()
+                        }),
+                        ..ShouldColorize::default()
+                    };
+                    colorize_control.set_decision_policy(DecisionPolicy::new(&[
+                        ColorSource::Clicolor,
+                        ColorSource::ClicolorForce,
+                    ]));
+                    assert!(!colorize_control.should_colorize());
+                });
+
+                ctx.it("should ignore duplicated sources", |_| {
+                    let policy = DecisionPolicy::new(&[
+                        ColorSource::NoColor,
+                        ColorSource::NoColor,
+                        ColorSource::Override,
+                    ]);
+                    assert_eq!(
+                        vec![ColorSource::NoColor, ColorSource::Override],
+                        policy.order().collect::<Vec<_>>()
+                    );
+                });
+            });
+
+            ctx.context("::set_tty_rule", |ctx| {
+                ctx.it("should require the streams of the rule to be terminals", |_| {
+                    // stdout is a terminal, stderr is piped
+                    let colorize_control = ShouldColorize {
+                        detected: RwLock::new(Detected {
+                            clicolor: true,
+                            tty_required: true,
+                            ..Detected::default()
+                        }),
+                        tty: [AtomicU8::new(TTY_YES), AtomicU8::new(TTY_NO)],
+                        ..ShouldColorize::default()
+                    };
+                    assert_eq!(TtyRule::Stdout, colorize_control.tty_rule());
+                    for (rule, expected) in [
+                        (TtyRule::Stdout, true),
+                        (TtyRule::Stderr, false),
+                        (TtyRule::Both, false),
+                        (TtyRule::Either, true),
+                    ] {
+                        colorize_control.set_tty_rule(rule);
+                        assert_eq!(rule, colorize_control.tty_rule());
+                        assert_eq!(expected, colorize_control.should_colorize());
+                    }
+                    colorize_control.set_tty_rule(TtyRule::Both);
+                    assert_eq!(
+                        "stdout or stderr is not a terminal",
+                        colorize_control.diagnostics().reason()
+                    );
+                });
+
+                ctx.it("should not matter when the environment decides", |_| {
+                    let colorize_control = ShouldColorize {
+                        detected: RwLock::new(Detected {
+                            clicolor_force: Some(true),
+                            ..Detected::default()
+                        }),
+                        tty: [AtomicU8::new(TTY_NO), AtomicU8::new(TTY_NO)],
+                        ..ShouldColorize::default()
+                    };
+                    colorize_control.set_tty_rule(TtyRule::Both);
+                    assert!(colorize_control.should_colorize());
+                });
+            });
+
+            ctx.context("::set_bold_bright", |ctx| {
+                ctx.it("should trade bright colors for bold on 16 colors", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    let level = ColorLevel::Ansi16;
+                    let mut bold = Style::default();
+                    bold.insert(Styles::Bold);
+                    assert_eq!(
+                        (Style::default(), Some(Color::BrightRed)),
+                        colorize_control.adjust_bold_bright(Style::default(), Some(Color::BrightRed), level)
+                    );
+                    colorize_control.set_bold_bright(BoldBright::BrightToBold);
+                    assert_eq!(
+                        (bold, Some(Color::Red)),
+                        colorize_control.adjust_bold_bright(Style::default(), Some(Color::BrightRed), level)
+                    );
+                    colorize_control.set_bold_bright(BoldBright::BoldToBright);
+                    assert_eq!(
+                        (Style::default(), Some(Color::BrightRed)),
+                        colorize_control.adjust_bold_bright(bold, Some(Color::Red), level)
+                    );
+                });
+
+                ctx.it("should leave other color levels alone", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    let level = ColorLevel::Ansi256;
+                    colorize_control.set_bold_bright(BoldBright::BrightToBold);
+                    assert_eq!(
+                        (Style::default(), Some(Color::BrightRed)),
+                        colorize_control.adjust_bold_bright(Style::default(), Some(Color::BrightRed), level)
+                    );
+                });
+            });
+
+            ctx.context("::set_tag_enabled", |ctx| {
+                ctx.it("should only disable the given tag", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    colorize_control.set_tag_enabled("json", false);
+                    assert!(!colorize_control.is_tag_enabled("json"));
+                    assert!(colorize_control.is_tag_enabled("diagnostics"));
+                    colorize_control.clear_tag_rules();
+                    assert!(colorize_control.is_tag_enabled("json"));
+                });
+
+                ctx.it("should fall back to the default for unknown tags", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    colorize_control.set_tags_enabled_by_default(false);
+                    colorize_control.set_tag_enabled("diagnostics", true);
+                    assert!(colorize_control.is_tag_enabled("diagnostics"));
+                    assert!(!colorize_control.is_tag_enabled("json"));
+                });
+            });
+
+            ctx.context("::sgr_syntax", |ctx| {
+                ctx.it("should follow the detection unless set", |_| {
+                    let colorize_control = ShouldColorize {
+                        detected: RwLock::new(Detected {
+                            colon_sgr: true,
+                            ..Detected::default()
+                        }),
+                        ..ShouldColorize::default()
+                    };
+                    assert_eq!(SgrSyntax::Colon, colorize_control.sgr_syntax());
+                    colorize_control.set_sgr_syntax(SgrSyntax::Semicolon);
+                    assert_eq!(SgrSyntax::Semicolon, colorize_control.sgr_syntax());
+                    colorize_control.unset_sgr_syntax();
+                    assert_eq!(SgrSyntax::Colon, colorize_control.sgr_syntax());
+                    assert_eq!(SgrSyntax::Semicolon, ShouldColorize::default().sgr_syntax());
+                });
+            });
+
+            ctx.context("::extended_underline_from_env", |ctx| {
+                ctx.it("should detect kitty and recent VTE", |_| {
+                    assert!(extended_underline_from_env(
+                        |name| (name == "KITTY_WINDOW_ID").then(|| String::from("1"))
+                    ));
+                    assert!(extended_underline_from_env(
+                        |name| (name == "VTE_VERSION").then(|| String::from("6003"))
+                    ));
+                    assert!(!extended_underline_from_env(
+                        |name| (name == "VTE_VERSION").then(|| String::from("5000"))
+                    ));
+                    assert!(!extended_underline_from_env(
+                        |name| (name == "TERM").then(|| String::from("xterm-256color"))
+                    ));
+                });
+
+                ctx.it("should be disabled when not colorizing", |_| {
+                    let colorize_control = ShouldColorize {
+                        detected: RwLock::new(Detected {
+                            ext_underline: true,
+                            ..Detected::default()
+                        }),
+                        ..ShouldColorize::default()
+                    };
+                    assert!(colorize_control.supports_extended_underline());
+                    colorize_control.set_override(false);
+                    assert!(!colorize_control.supports_extended_underline());
+                });
+            });
+
+            ctx.context("::hyperlinks_from_env", |ctx| {
+                ctx.it("should not support hyperlinks without a tty", |_| {
+                    assert!(!hyperlinks_from_env(
+                        |name| (name == "TERM_PROGRAM").then(|| String::from("WezTerm")),
+                        false
+                    ));
+                });
+
+                ctx.it("should detect known terminals", |_| {
+                    assert!(hyperlinks_from_env(
+                        |name| (name == "TERM_PROGRAM").then(|| String::from("WezTerm")),
+                        true
+                    ));
+                    assert!(hyperlinks_from_env(
+                        |name| (name == "VTE_VERSION").then(|| String::from("6003")),
+                        true
+                    ));
+                    assert!(!hyperlinks_from_env(
+                        |name| (name == "VTE_VERSION").then(|| String::from("4200")),
+                        true
+                    ));
+                    assert!(!hyperlinks_from_env(|_| None, true));
+                });
+
+                ctx.it("should honor FORCE_HYPERLINK", |_| {
+                    assert!(hyperlinks_from_env(
+                        |name| (name == "FORCE_HYPERLINK").then(|| String::from("1")),
+                        false
+                    ));
+                    assert!(!hyperlinks_from_env(
+                        |name| match name {
+                            "FORCE_HYPERLINK" => Some(String::from("0")),
+                            "TERM_PROGRAM" => Some(String::from("WezTerm")),
+                            _ => None,
+                        },
+                        true
+                    ));
+                });
+            });
+
+            ctx.context("::terminal_size_from_env", |ctx| {
+                ctx.it("should fall back to COLUMNS and LINES", |_| {
+                    let size = |columns: &'static str| {
+                        terminal_size_from_env(|name| match name {
+                            "COLUMNS" => Some(String::from(columns)),
+                            "LINES" => Some(String::from("24")),
+                            _ => None,
+                        })
+                    };
+                    if !atty::is(atty::Stream::Stdout) {
+                        assert_eq!(Some((80, 24)), size("80"));
+                        assert_eq!(None, size("0"));
+                        assert_eq!(None, terminal_size_from_env(|_| None));
+                    }
+                });
+
+                ctx.it("should be cached until invalidated", |_| {
+                    let colorize_control = ShouldColorize {
+                        detected: RwLock::new(Detected {
+                            size: Some((80, 24)),
+                            ..Detected::default()
+                        }),
+                        ..ShouldColorize::default()
+                    };
+                    assert_eq!(Some((80, 24)), colorize_control.terminal_size());
+                    colorize_control.invalidate();
+                    assert_eq!(Detected::from_env().size, colorize_control.terminal_size());
+                });
+            });
+
+            ctx.context("::color_level", |ctx| {
+                ctx.it("should be None when not colorizing", |_| {
+                    let colorize_control = ShouldColorize {
+                        detected: RwLock::new(Detected {
+                            clicolor: false,
+                            level: ColorLevel::TrueColor,
+                            ..Detected::default()
+                        }),
+                        ..ShouldColorize::default()
+                    };
+                    assert_eq!(ColorLevel::None, colorize_control.color_level());
+                    colorize_control.set_override(true);
+                    assert_eq!(ColorLevel::TrueColor, colorize_control.color_level());
+                });
+            });
+
+            ctx.context("::invalidate", |ctx| {
+                ctx.it("should detect the capabilities again", |_| {
+                    let colorize_control = ShouldColorize {
+                        detected: RwLock::new(Detected {
+                            clicolor_force: Some(false),
+                            ..Detected::default()
+                        }),
+                        ..ShouldColorize::default()
+                    };
+                    colorize_control.invalidate();
+                    let detected = Detected::from_env();
+                    let is_tty = atty::is(atty::Stream::Stdout);
+                    assert_eq!(
+                        detected
+                            .clicolor_force
+                            .unwrap_or(detected.clicolor && (!detected.tty_required || is_tty)),
+                        colorize_control.should_colorize()
+                    );
+                });
+            });
+
+            ctx.context("::is_tty", |ctx| {
+                ctx.it("should require a tty only when detected so", |_| {
+                    let colorize_control = ShouldColorize {
+                        detected: RwLock::new(Detected {
+                            tty_required: true,
+                            ..Detected::default()
+                        }),
+                        ..ShouldColorize::default()
+                    };
+                    colorize_control.tty[Stream::Stdout as usize].store(TTY_NO, Ordering::Relaxed);
+                    assert!(!colorize_control.should_colorize());
+                    colorize_control.tty[Stream::Stdout as usize].store(TTY_YES, Ordering::Relaxed);
+                    assert!(colorize_control.should_colorize());
+                });
+
+                ctx.it("should check the stream again after a refresh", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    colorize_control.tty[Stream::Stderr as usize].store(TTY_YES, Ordering::Relaxed);
+                    assert!(colorize_control.is_tty(Stream::Stderr));
+                    colorize_control.refresh_tty();
+                    assert_eq!(
+                        atty::is(atty::Stream::Stderr),
+                        colorize_control.is_tty(Stream::Stderr)
+                    );
+                });
+            });
+
             ctx.context("constructors", |ctx| {
                 ctx.it("should have a default constructor", |_| {
                     ShouldColorize::default();
@@ -294,7 +2885,10 @@ mod specs {
             ctx.context("when only changing clicolors", |ctx| {
                 ctx.it("clicolor == false means no colors", |_| {
                     let colorize_control = ShouldColorize {
-                        clicolor: false,
+                        detected: RwLock::new(Detected {
+                            clicolor: false,
+                            ..Detected::default()
+                        }),
                         ..ShouldColorize::default()
                     };
                     !colorize_control.should_colorize()
@@ -302,7 +2896,10 @@ mod specs {
 
                 ctx.it("clicolor == true means colors !", |_| {
                     let colorize_control = ShouldColorize {
-                        clicolor: true,
+                        detected: RwLock::new(Detected {
+                            clicolor: true,
+                            ..Detected::default()
+                        }),
                         ..ShouldColorize::default()
                     };
                     colorize_control.should_colorize()
@@ -318,8 +2915,11 @@ mod specs {
                     "clicolor_force should force to true no matter clicolor",
                     |_| {
                         let colorize_control = ShouldColorize {
-                            clicolor: false,
-                            clicolor_force: Some(true),
+                            detected: RwLock::new(Detected {
+                                clicolor: false,
+                                clicolor_force: Some(true),
+                                ..Detected::default()
+                            }),
                             ..ShouldColorize::default()
                         };
 
@@ -331,8 +2931,11 @@ mod specs {
                     "clicolor_force should force to false no matter clicolor",
                     |_| {
                         let colorize_control = ShouldColorize {
-                            clicolor: true,
-                            clicolor_force: Some(false),
+                            detected: RwLock::new(Detected {
+                                clicolor: true,
+                                clicolor_force: Some(false),
+                                ..Detected::default()
+                            }),
                             ..ShouldColorize::default()
                         };
 
@@ -347,10 +2950,14 @@ mod specs {
                      clicolor_force also false",
                     |_| {
                         let colorize_control = ShouldColorize {
-                            clicolor:            false,
-                            clicolor_force:      None,
+                            detected:            RwLock::new(Detected {
+                                clicolor:       false,
+                                clicolor_force: None,
+                                ..Detected::default()
+                            }),
                             has_manual_override: AtomicBool::new(true),
                             manual_override:     AtomicBool::new(true),
+                            ..ShouldColorize::default()
                         };
 
                         colorize_control.should_colorize()
@@ -362,10 +2969,14 @@ mod specs {
                      clicolor_force is true",
                     |_| {
                         let colorize_control = ShouldColorize {
-                            clicolor:            true,
-                            clicolor_force:      Some(true),
+                            detected:            RwLock::new(Detected {
+                                clicolor:       true,
+                                clicolor_force: Some(true),
+                                ..Detected::default()
+                            }),
                             has_manual_override: AtomicBool::new(true),
                             manual_override:     AtomicBool::new(false),
+                            ..ShouldColorize::default()
                         };
 
                         !colorize_control.should_colorize()
@@ -409,6 +3020,175 @@ mod specs {
                     assert!(!colorize_control.has_manual_override.load(Ordering::Relaxed));
                 });
             });
+
+            ctx.context("::without_colors", |ctx| {
+                ctx.it("wins over the manual override", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    colorize_control.set_override(true);
+                    assert!(!without_colors(|| colorize_control.should_colorize()));
+                    assert!(colorize_control.should_colorize());
+                });
+
+                ctx.it("wins over an always colorizer", |_| {
+                    let always = Colorizer::new(ColorChoice::Always);
+                    assert!(!without_colors(|| always.should_colorize()));
+                });
+
+                ctx.it("nests and restores the previous decision", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    colorize_control.set_override(false);
+                    without_colors(|| {
+                        assert!(with_colors(|| colorize_control.should_colorize()));
+                        assert!(!colorize_control.should_colorize());
+                    });
+                    assert_eq!(scoped_decision(), None);
+                });
+
+                ctx.it("restores the previous decision on panic", |_| {
+                    let res = std::panic::catch_unwind(|| without_colors(|| panic!()));
+                    assert!(res.is_err());
+                    assert_eq!(scoped_decision(), None);
+                });
+            });
+
+            ctx.context("Colorizer::set_color_names", |ctx| {
+                ctx.it("parses the English names without a table", |_| {
+                    let colorizer = Colorizer::new(ColorChoice::Auto);
+                    assert_eq!(Ok(Color::Red), colorizer.parse_color("red"));
+                    assert_eq!(
+                        Err(ParseColorError::UnknownName("rot".to_owned())),
+                        colorizer.parse_color("rot")
+                    );
+                });
+
+                ctx.it("prefers the names of the table", |_| {
+                    let mut colorizer = Colorizer::new(ColorChoice::Auto);
+                    colorizer.set_color_names(std::collections::HashMap::from([
+                        ("Rot".to_owned(), Color::Red),
+                        ("blue".to_owned(), Color::Green),
+                    ]));
+                    assert_eq!(Ok(Color::Red), colorizer.parse_color(" ROT "));
+                    assert_eq!(Ok(Color::Green), colorizer.parse_color("blue"));
+                    assert_eq!(Ok(Color::Yellow), colorizer.parse_color("yellow"));
+                });
+            });
+
+            ctx.context("::disable_style", |ctx| {
+                ctx.it("all styles are enabled by default", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    assert!(colorize_control.is_style_enabled(Styles::Blink));
+                    assert!(colorize_control.is_style_enabled(Styles::Dimmed));
+                });
+
+                ctx.it("disable and re-enable a style", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    colorize_control.disable_style(Styles::Blink);
+                    assert!(!colorize_control.is_style_enabled(Styles::Blink));
+                    assert!(colorize_control.is_style_enabled(Styles::Bold));
+                    colorize_control.enable_style(Styles::Blink);
+                    assert!(colorize_control.is_style_enabled(Styles::Blink));
+                });
+            });
+
+            ctx.context("::set_accessibility_mode", |ctx| {
+                ctx.it("is off by default", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    assert!(!colorize_control.accessibility_mode());
+                    let mut style = Style::default();
+                    style.insert(Styles::Blink);
+                    assert_eq!(
+                        (style, Some(Color::Red)),
+                        colorize_control.adjust_accessibility(style, Some(Color::Red))
+                    );
+                });
+
+                ctx.it("removes decorative styles and the background", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    colorize_control.set_accessibility_mode(true);
+                    let mut style = Style::default();
+                    Styles::all().for_each(|s| style.insert(s));
+                    let (style, bg) = colorize_control.adjust_accessibility(style, Some(Color::Red));
+                    assert_eq!(bg, None);
+                    for removed in [Styles::Blink, Styles::Dimmed, Styles::Strikethrough] {
+                        assert!(!style.contains(removed));
+                    }
+                    for kept in [Styles::Bold, Styles::Italic, Styles::Underline, Styles::Reversed, Styles::Hidden] {
+                        assert!(style.contains(kept));
+                    }
+                });
+            });
+
+            ctx.context("::diagnostics", |ctx| {
+                ctx.it("explains the manual override", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    colorize_control.set_override(false);
+                    let report = colorize_control.diagnostics();
+                    assert!(!report.should_colorize());
+                    assert_eq!(report.color_choice(), ColorChoice::Never);
+                    assert_eq!(report.reason(), "set by the manual override");
+                    assert!(report.to_string().starts_with("colorize: no (set by the manual override)\n"));
+                });
+
+                ctx.it("explains the scoped decision first", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    colorize_control.set_override(false);
+                    let report = with_colors(|| colorize_control.diagnostics());
+                    assert!(report.should_colorize());
+                    assert_eq!(report.reason(), "forced by with_colors");
+                    let report = without_colors(|| colorize_control.diagnostics());
+                    assert_eq!(report.reason(), "disabled by without_colors");
+                });
+
+                ctx.it("lists the environment", |_| {
+                    let report = ShouldColorize::default().diagnostics();
+                    assert!(report.env().any(|(name, _)| name == "NO_COLOR"));
+                    assert_eq!(report.env().count(), report.to_string().lines().count() - 7);
+                });
+            });
+
+            ctx.context("::set_style_interning", |ctx| {
+                ctx.it("builds every escape sequence by default", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    let key = (Style::default(), None, Some(Color::Red), SgrSyntax::Semicolon);
+                    let first = colorize_control.intern_style(key, || "\x1B[31m".to_owned());
+                    let second = colorize_control.intern_style(key, || "\x1B[31m".to_owned());
+                    assert!(!Arc::ptr_eq(&first, &second));
+                    assert_eq!(colorize_control.interner_stats(), InternerStats::default());
+                });
+
+                ctx.it("reuses interned escape sequences", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    colorize_control.set_style_interning(true);
+                    let key = (Style::default(), None, Some(Color::Red), SgrSyntax::Semicolon);
+                    let first = colorize_control.intern_style(key, || "\x1B[31m".to_owned());
+                    let second = colorize_control.intern_style(key, || unreachable!());
+                    assert!(Arc::ptr_eq(&first, &second));
+
+                    let stats = colorize_control.interner_stats();
+                    assert_eq!((stats.hits(), stats.misses(), stats.entries()), (1, 1, 1));
+                    colorize_control.clear_interner();
+                    assert_eq!(colorize_control.interner_stats(), InternerStats::default());
+                });
+            });
+
+            ctx.context("::set_color_map", |ctx| {
+                ctx.it("colors are untouched by default", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    assert_eq!(Color::Blue, colorize_control.map_color(Color::Blue));
+                });
+
+                ctx.it("set and unset the color map", |_| {
+                    let colorize_control = ShouldColorize::default();
+                    colorize_control.set_color_map(|c| match c {
+                        Color::Blue => Color::BrightBlue,
+                        c => c,
+                    });
+                    assert_eq!(Color::BrightBlue, colorize_control.map_color(Color::Blue));
+                    assert_eq!(Color::Red, colorize_control.map_color(Color::Red));
+                    colorize_control.unset_color_map();
+                    assert_eq!(Color::Blue, colorize_control.map_color(Color::Blue));
+                });
+            });
         });
 
         runner.run(&suite);
@@ -0,0 +1,85 @@
+//! Pairing items with colors taken in turn from a list
+
+use std::{iter::Cycle, slice};
+
+use crate::Color;
+
+/// An [`Iterator`] adapter pairing every item with the next color of a list,
+/// starting over once every color has been used.
+///
+/// ```rust
+/// # use colored::*;
+/// let workers = ["alpha", "beta", "gamma"];
+/// let colors = [Color::Cyan, Color::Magenta];
+/// let colored: Vec<_> = workers
+///     .iter()
+///     .cycle_colors(&colors)
+///     .map(|(name, color)| name.color(color))
+///     .collect();
+/// assert_eq!(colored, ["alpha".cyan(), "beta".magenta(), "gamma".cyan()]);
+/// ```
+pub trait CycleColors: Iterator + Sized {
+    /// Pair every item with the next color of `colors`. Nothing is yielded
+    /// when `colors` is empty.
+    fn cycle_colors(self, colors: &[Color]) -> CycledColors<'_, Self>;
+}
+
+impl<I: Iterator> CycleColors for I {
+    #[inline]
+    fn cycle_colors(self, colors: &[Color]) -> CycledColors<'_, Self> {
+        CycledColors {
+            iter:   self,
+            colors: colors.iter().cycle(),
+        }
+    }
+}
+
+/// The [`Iterator`] returned by [`CycleColors::cycle_colors`]
+#[derive(Clone, Debug)]
+pub struct CycledColors<'a, I> {
+    /// The items to pair with a color
+    iter:   I,
+    /// The colors, repeated endlessly
+    colors: Cycle<slice::Iter<'a, Color>>,
+}
+
+impl<I: Iterator> Iterator for CycledColors<'_, I> {
+    type Item = (I::Item, Color);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let color = *self.colors.next()?;
+        self.iter.next().map(|item| (item, color))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // an empty list of colors cycles to nothing
+        match self.colors.size_hint() {
+            (0, Some(0)) => (0, Some(0)),
+            _ => self.iter.size_hint(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_around() {
+        let colors = [Color::Red, Color::Green];
+        let paired: Vec<_> = (0..5).cycle_colors(&colors).map(|(_, c)| c).collect();
+        assert_eq!(
+            paired,
+            [Color::Red, Color::Green, Color::Red, Color::Green, Color::Red]
+        );
+    }
+
+    #[test]
+    fn no_colors() {
+        assert_eq!((0..5).cycle_colors(&[]).next(), None);
+        assert_eq!((0..5).cycle_colors(&[]).size_hint(), (0, Some(0)));
+        assert_eq!((0..5).cycle_colors(&[Color::Red]).size_hint(), (5, Some(5)));
+    }
+}
@@ -0,0 +1,84 @@
+//! Headers of compiler-style diagnostics, e.g. `error: mismatched types`,
+//! styled the way `rustc` does.
+//!
+//! The style of each label can be changed in the [`theme`]
+//! under `diag.error`, `diag.warning`, `diag.note` and `diag.help`.
+//!
+//! ```rust
+//! # use colored::*;
+//! eprintln!("{}", diag::error("could not find `Cargo.toml`"));
+//! eprintln!("{}", diag::help("run `cargo init` to create one"));
+//! ```
+
+use crate::{theme, Color, ColoredText, Colorize, StyleSpec, Styles};
+
+/// Default style of `error`
+const ERROR: StyleSpec = StyleSpec::new().fg(Color::BrightRed).with(Styles::Bold);
+/// Default style of `warning`
+const WARNING: StyleSpec = StyleSpec::new().fg(Color::BrightYellow).with(Styles::Bold);
+/// Default style of `note`
+const NOTE: StyleSpec = StyleSpec::new().fg(Color::BrightGreen).with(Styles::Bold);
+/// Default style of `help`
+const HELP: StyleSpec = StyleSpec::new().fg(Color::BrightCyan).with(Styles::Bold);
+
+/// The `label: message` header, with the label styled by `diag.<label>` in
+/// the theme and the rest in bold
+fn header(label: &str, default: StyleSpec, message: &str) -> ColoredText {
+    let spec = theme::get(&format!("diag.{label}")).unwrap_or(default);
+    label
+        .with_spec(spec)
+        .push_colored(format!(": {message}").bold())
+}
+
+/// An `error: message` header, `error` being bold bright red by default
+#[inline]
+#[must_use]
+pub fn error(message: &str) -> ColoredText {
+    header("error", ERROR, message)
+}
+
+/// A `warning: message` header, `warning` being bold bright yellow by
+/// default
+#[inline]
+#[must_use]
+pub fn warning(message: &str) -> ColoredText {
+    header("warning", WARNING, message)
+}
+
+/// A `note: message` header, `note` being bold bright green by default
+#[inline]
+#[must_use]
+pub fn note(message: &str) -> ColoredText {
+    header("note", NOTE, message)
+}
+
+/// A `help: message` header, `help` being bold bright cyan by default
+#[inline]
+#[must_use]
+pub fn help(message: &str) -> ColoredText {
+    header("help", HELP, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headers() {
+        let text = warning("unused variable");
+        assert_eq!(text.to_plain(), "warning: unused variable");
+        assert_eq!(text.segments(), &[
+            "warning".bright_yellow().bold(),
+            ": unused variable".bold()
+        ]);
+        assert_eq!(error("x").segments()[0], "error".bright_red().bold());
+        assert_eq!(note("x").segments()[0], "note".bright_green().bold());
+        assert_eq!(help("x").segments()[0], "help".bright_cyan().bold());
+    }
+
+    #[test]
+    fn themed() {
+        theme::set("diag.hint", StyleSpec::new().fg(Color::Blue));
+        assert_eq!(header("hint", NOTE, "x").segments()[0], "hint".blue());
+    }
+}
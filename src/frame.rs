@@ -0,0 +1,141 @@
+//! Redrawing only the lines which changed between two frames, for
+//! `watch`-like tools.
+//!
+//! ```rust
+//! # use colored::*;
+//! let prev = ColoredText::from("cpu  12%\nmem  40%".normal());
+//! let next = "cpu  12%\nmem  ".normal().push_colored("91%".red());
+//! let changed = frame::diff(&prev, &next);
+//! assert_eq!(changed.len(), 1);
+//! assert_eq!(changed[0].index(), 1);
+//! ```
+
+#[cfg(feature = "cursor")]
+use std::io;
+
+use crate::{ColoredString, ColoredText};
+
+/// A line of the next frame which differs from the previous one, see
+/// [`diff`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangedLine {
+    /// Index of the line in the frame, from 0
+    index:    usize,
+    /// The line, escape sequences included but without its newline
+    rendered: String,
+}
+
+impl ChangedLine {
+    /// Index of the line in the frame, from 0
+    #[inline]
+    #[must_use]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The line, escape sequences included but without its newline. Empty if
+    /// the next frame has fewer lines, so that the line is cleared
+    #[inline]
+    #[must_use]
+    pub fn rendered(&self) -> &str {
+        &self.rendered
+    }
+}
+
+/// Render each line of `text`. A segment spanning several lines is styled on
+/// each of them, so that every line can be drawn on its own.
+fn render_lines(text: &ColoredText) -> Vec<String> {
+    let mut lines = vec![String::new()];
+    for segment in text.segments() {
+        for (i, piece) in segment.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(String::new());
+            }
+            if !piece.is_empty() {
+                let line = lines.last_mut().expect("there is always a line");
+                line.push_str(&ColoredString::from(piece).copy_style_from(segment).to_string());
+            }
+        }
+    }
+    lines
+}
+
+/// The lines of `next` which are rendered differently from those of `prev`,
+/// in order. If `next` has fewer lines than `prev`, its extra lines are
+/// returned empty so that they are cleared.
+#[must_use]
+pub fn diff(prev: &ColoredText, next: &ColoredText) -> Vec<ChangedLine> {
+    let prev = render_lines(prev);
+    let next = render_lines(next);
+    (0..prev.len().max(next.len()))
+        .filter(|&index| prev.get(index) != next.get(index))
+        .map(|index| ChangedLine {
+            index,
+            rendered: next.get(index).cloned().unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Write the lines of `next` which differ from `prev`, each one after moving
+/// the cursor to its row and followed by a clear to the end of the line. The
+/// frames are assumed to be drawn from the top left corner of the screen.
+///
+/// The whole update is rendered first and written to `out` at once, so that
+/// the terminal never shows half of a frame.
+///
+/// # Errors
+/// Will produce an error if writing to `out` fails
+///
+/// # Notes
+/// > Only available with the `cursor` feature.
+#[cfg(feature = "cursor")]
+pub fn redraw<W: io::Write>(prev: &ColoredText, next: &ColoredText, out: &mut W) -> io::Result<()> {
+    use std::fmt::Write;
+
+    let mut update = String::new();
+    for line in diff(prev, next) {
+        write!(update, "\x1B[{};1H{}\x1B[K", line.index + 1, line.rendered)
+            .expect("failed to write the update to a string");
+    }
+    out.write_all(update.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Colorize;
+
+    #[test]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    fn lines_keep_their_style() {
+        let text = "a\nb".red().push_colored("c".blue());
+        assert_eq!(render_lines(&text), [
+            "a".red().to_string(),
+            format!("{}{}", "b".red(), "c".blue())
+        ]);
+    }
+
+    #[test]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    fn only_changed_lines() {
+        let prev = ColoredText::from("a\nb\nc\nd".normal());
+        let mut next = "a\n".normal().push_colored("b".red());
+        next.push("\nc".normal());
+        let changed = diff(&prev, &next);
+        assert_eq!(
+            changed.iter().map(ChangedLine::index).collect::<Vec<_>>(),
+            [1, 3]
+        );
+        assert_eq!(changed[0].rendered(), "b".red().to_string());
+        assert_eq!(changed[1].rendered(), "");
+        assert!(diff(&next, &next).is_empty());
+    }
+
+    #[cfg(feature = "cursor")]
+    #[test]
+    fn redraw_moves_the_cursor() {
+        let mut out = Vec::new();
+        redraw(&"a\nb".normal().into(), &"a\nc".normal().into(), &mut out).unwrap();
+        assert_eq!(out, b"\x1B[2;1Hc\x1B[K");
+    }
+}
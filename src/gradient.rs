@@ -0,0 +1,197 @@
+//! Multi-stop color gradients, with a few well-known presets
+
+use std::borrow::Cow;
+
+use crate::Color;
+
+/// A gradient going through colors placed at positions from `0.0` to `1.0`,
+/// interpolated linearly in between.
+///
+/// ```rust
+/// # use colored::*;
+/// let traffic = Gradient::new(&[(0.0, Color::Green), (0.8, Color::Yellow), (1.0, Color::Red)]);
+/// assert_eq!(traffic.at(0.4), Color::truecolor(128, 192, 0));
+/// println!("{}", "disk usage".color(Gradient::VIRIDIS.at(0.7)));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    /// The positions and colors, sorted by position
+    stops: Cow<'static, [(f32, Color)]>,
+}
+
+impl Gradient {
+    /// The perceptually uniform `viridis` colormap, from purple to yellow
+    pub const VIRIDIS: Self = Self::from_static(&[
+        (0.0, Color::truecolor(0x44, 0x01, 0x54)),
+        (0.25, Color::truecolor(0x3B, 0x52, 0x8B)),
+        (0.5, Color::truecolor(0x21, 0x91, 0x8C)),
+        (0.75, Color::truecolor(0x5E, 0xC9, 0x62)),
+        (1.0, Color::truecolor(0xFD, 0xE7, 0x25)),
+    ]);
+
+    /// The perceptually uniform `magma` colormap, from black to pale yellow
+    pub const MAGMA: Self = Self::from_static(&[
+        (0.0, Color::truecolor(0x00, 0x00, 0x04)),
+        (0.2, Color::truecolor(0x3B, 0x0F, 0x70)),
+        (0.4, Color::truecolor(0x8C, 0x29, 0x81)),
+        (0.6, Color::truecolor(0xDE, 0x49, 0x68)),
+        (0.8, Color::truecolor(0xFE, 0x9F, 0x6D)),
+        (1.0, Color::truecolor(0xFC, 0xFD, 0xBF)),
+    ]);
+
+    /// The `turbo` colormap, a smoother rainbow from dark blue to dark red
+    pub const TURBO: Self = Self::from_static(&[
+        (0.0, Color::truecolor(0x30, 0x12, 0x3B)),
+        (0.125, Color::truecolor(0x46, 0x62, 0xD7)),
+        (0.25, Color::truecolor(0x36, 0xAA, 0xF9)),
+        (0.375, Color::truecolor(0x1A, 0xE4, 0xB6)),
+        (0.5, Color::truecolor(0x72, 0xFE, 0x5E)),
+        (0.625, Color::truecolor(0xC7, 0xEF, 0x34)),
+        (0.75, Color::truecolor(0xFA, 0xBA, 0x39)),
+        (0.875, Color::truecolor(0xE4, 0x46, 0x0A)),
+        (1.0, Color::truecolor(0x7A, 0x04, 0x03)),
+    ]);
+
+    /// The colors of the rainbow, from red to violet
+    pub const RAINBOW: Self = Self::from_static(&[
+        (0.0, Color::truecolor(0xFF, 0x00, 0x00)),
+        (1.0 / 6.0, Color::truecolor(0xFF, 0x7F, 0x00)),
+        (2.0 / 6.0, Color::truecolor(0xFF, 0xFF, 0x00)),
+        (3.0 / 6.0, Color::truecolor(0x00, 0xFF, 0x00)),
+        (4.0 / 6.0, Color::truecolor(0x00, 0x00, 0xFF)),
+        (5.0 / 6.0, Color::truecolor(0x4B, 0x00, 0x82)),
+        (1.0, Color::truecolor(0x94, 0x00, 0xD3)),
+    ]);
+
+    /// A gradient of stops already sorted by position
+    const fn from_static(stops: &'static [(f32, Color)]) -> Self {
+        Self {
+            stops: Cow::Borrowed(stops),
+        }
+    }
+
+    /// Create a gradient going through `stops`, each a position from `0.0` to
+    /// `1.0` and a color. The stops are sorted by position, and positions out
+    /// of range are clamped.
+    #[must_use]
+    pub fn new(stops: &[(f32, Color)]) -> Self {
+        let mut stops: Vec<_> = stops
+            .iter()
+            .map(|&(pos, color)| (if pos.is_nan() { 0.0 } else { pos.clamp(0.0, 1.0) }, color))
+            .collect();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self {
+            stops: Cow::Owned(stops),
+        }
+    }
+
+    /// Create a gradient going evenly through `colors`, as used by
+    /// [`Color::heat_with`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let stops = [Color::Blue, Color::Green, Color::Red];
+    /// assert_eq!(Gradient::evenly(&stops).at(0.3), Color::heat_with(0.3, &stops));
+    /// ```
+    #[must_use]
+    pub fn evenly(colors: &[Color]) -> Self {
+        let last = colors.len().saturating_sub(1).max(1);
+        #[allow(clippy::cast_precision_loss)]
+        let stops: Vec<_> = colors
+            .iter()
+            .enumerate()
+            .map(|(i, &color)| (i as f32 / last as f32, color))
+            .collect();
+        Self {
+            stops: Cow::Owned(stops),
+        }
+    }
+
+    /// The positions and colors of the gradient, sorted by position
+    #[inline]
+    #[must_use]
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+
+    /// The color at `t` (`0.0..=1.0`) on the gradient, always a
+    /// [`Color::TrueColor`]. Before the first stop and after the last one,
+    /// the color is that of the closest stop. An empty gradient is white.
+    #[must_use]
+    pub fn at(&self, t: f32) -> Color {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return Color::truecolor(0xFF, 0xFF, 0xFF),
+        };
+        if t <= first.0 {
+            return to_truecolor(first.1);
+        }
+
+        self.stops
+            .windows(2)
+            .find(|pair| t <= pair[1].0)
+            .map_or(to_truecolor(last.1), |pair| {
+                let ((from, start), (to, end)) = (pair[0], pair[1]);
+                let span = to - from;
+                let local = if span > 0.0 { (t - from) / span } else { 1.0 };
+                start.lerp(end, local)
+            })
+    }
+}
+
+/// The same color as a [`Color::TrueColor`]
+const fn to_truecolor(color: Color) -> Color {
+    let [r, g, b] = color.to_hex_array();
+    Color::truecolor(r, g, b)
+}
+
+impl From<&[Color]> for Gradient {
+    /// A gradient going evenly through the colors, see [`Gradient::evenly`]
+    #[inline]
+    fn from(colors: &[Color]) -> Self {
+        Self::evenly(colors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_sorted() {
+        for preset in [Gradient::VIRIDIS, Gradient::MAGMA, Gradient::TURBO, Gradient::RAINBOW] {
+            let stops = preset.stops();
+            assert_eq!(0.0, stops[0].0);
+            assert_eq!(1.0, stops[stops.len() - 1].0);
+            assert!(stops.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        }
+    }
+
+    #[test]
+    fn custom_stops() {
+        let black = Color::truecolor(0, 0, 0);
+        let white = Color::truecolor(255, 255, 255);
+        let gradient = Gradient::new(&[(1.0, white), (0.5, black), (f32::NAN, white)]);
+        assert_eq!(gradient.stops(), [(0.0, white), (0.5, black), (1.0, white)]);
+        assert_eq!(black, gradient.at(0.5));
+        assert_eq!(Color::truecolor(128, 128, 128), gradient.at(0.25));
+        assert_eq!(white, gradient.at(2.0));
+    }
+
+    #[test]
+    fn outside_the_stops() {
+        let gradient = Gradient::new(&[(0.2, Color::Red), (0.6, Color::Blue)]);
+        assert_eq!(Color::truecolor(0xFF, 0, 0), gradient.at(0.0));
+        assert_eq!(Color::truecolor(0, 0, 0xFF), gradient.at(0.9));
+        assert_eq!(Color::truecolor(0xFF, 0xFF, 0xFF), Gradient::new(&[]).at(0.5));
+    }
+
+    #[test]
+    fn evenly() {
+        let gradient = Gradient::evenly(&[Color::Green, Color::Yellow, Color::Red]);
+        assert_eq!(gradient.stops()[1].0, 0.5);
+        assert_eq!(Color::truecolor(0xFF, 0xFF, 0), gradient.at(0.5));
+        assert_eq!(Color::truecolor(0, 0, 0xFF), Gradient::evenly(&[Color::Blue]).at(0.7));
+    }
+}
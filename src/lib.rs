@@ -24,7 +24,6 @@
 //!
 //! See [the `Colorize` trait](./trait.Colorize.html) for all the methods.
 
-#![feature(lazy_cell)]
 #![warn(missing_docs)]
 #![deny(
     clippy::all,
@@ -92,15 +91,93 @@
     // clippy::missing_docs_in_private_items,
 )]
 
+pub mod ansi;
 mod color;
+mod columns;
 pub mod control;
+#[cfg(feature = "css-names")]
+pub mod css;
+mod cycle;
+pub mod diag;
+pub mod frame;
+mod gradient;
+#[cfg(feature = "locale-names")]
+pub mod locale;
+mod palette;
+#[cfg(all(unix, feature = "probe"))]
+mod probe;
+mod prompt;
+pub mod render;
+mod severity;
 mod style;
-use std::{borrow::Cow, fmt, ops::Deref};
+mod text;
+pub mod theme;
+mod width;
+#[cfg(windows)]
+pub mod windows;
+pub mod writers;
+pub mod xterm;
+
+// `std::sync::LazyLock` is only stable since 1.80
+#[cfg(feature = "once_cell")]
+pub(crate) use once_cell::sync::Lazy as LazyLock;
+#[cfg(not(feature = "once_cell"))]
+pub(crate) use std::sync::LazyLock;
+
+// the code generated by `embed_theme!` refers to the crate as `::colored`
+#[cfg(all(test, feature = "embed-theme"))]
+extern crate self as colored;
+
+/// Items used by the code generated by [`embed_theme!`]
+#[cfg(feature = "embed-theme")]
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "once_cell")]
+    pub use once_cell::sync::Lazy as LazyLock;
+    #[cfg(not(feature = "once_cell"))]
+    pub use std::sync::LazyLock;
+}
+
+/// Parses a theme file at compile time into a `&'static`
+/// [`Theme`](theme::Theme), so that applications shipping a fixed theme
+/// need no file IO and get syntax errors and unknown colors as build errors.
+///
+/// The path is relative to the `Cargo.toml` of the crate using the macro.
+/// The file is a subset of TOML: `name = "style"` entries, `[table]` headers
+/// prefixing the names that follow with `table.`, and `#` comments. Styles
+/// use the syntax of [`StyleSpec::from_git`], without the `xterm` color
+/// names.
+///
+/// ```rust
+/// # use colored::*;
+/// // ok = "brightgreen"
+/// // [severity]
+/// // error = "red bold"
+/// let dark = embed_theme!("tests/themes/dark.toml");
+/// assert_eq!(dark.get("ok"), Some(StyleSpec::new().fg(Color::BrightGreen)));
+/// assert_eq!(dark.get("severity.error"), Some(StyleSpec::new().fg(Color::Red).with(Styles::Bold)));
+/// theme::set_theme(dark.clone());
+/// # theme::set_theme(theme::Theme::default());
+/// ```
+#[cfg(feature = "embed-theme")]
+#[allow(clippy::pub_use)]
+pub use colored_macros::embed_theme;
+use std::{borrow::Cow, fmt, ops::Deref, rc::Rc, sync::Arc};
 
 #[allow(clippy::pub_use)]
 pub use crate::{
-    color::Color,
-    style::{Style, Styles},
+    color::{Color, ParseColorError},
+    columns::format_columns,
+    control::{with_colors, without_colors, ColorChoice, ColorLevel, Colorizer},
+    cycle::{CycleColors, CycledColors},
+    gradient::Gradient,
+    palette::Palette,
+    prompt::PromptDialect,
+    render::RenderBackend,
+    severity::Severity,
+    style::{AppliedAll, ApplyStyle, Style, StyleMut, StyleSpec, StyledChars, Styles},
+    text::ColoredText,
+    width::WidthPolicy,
 };
 
 /// A string that may have color and/or style applied to it.
@@ -274,8 +351,49 @@ pub trait Colorize {
     {
         self.color(Color::TrueColor { r, g, b })
     }
-    /// Return the color of the text
+    /// Foreground color `idx` of the 256 color palette, see
+    /// [`Color::Ansi256`]
+    #[inline]
+    fn ansi256(self, idx: u8) -> ColoredString
+    where
+        Self: Sized,
+    {
+        self.color(Color::Ansi256(idx))
+    }
+    /// The default foreground color of the terminal, see [`Color::Default`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let note = "see the log".default_color().on_default();
+    /// println!("{}", format!("failed: {note}").white().on_red());
+    /// ```
+    #[inline]
+    fn default_color(self) -> ColoredString
+    where
+        Self: Sized,
+    {
+        self.color(Color::Default)
+    }
+    /// Return the color of the text. Strings are parsed with
+    /// [`Color::from_str`](std::str::FromStr), unknown colors being white.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!("hot".color("#ff0088"), "hot".truecolor(255, 0, 136));
+    /// assert_eq!("hot".color("rgb(255, 0, 136)"), "hot".truecolor(255, 0, 136));
+    /// ```
     fn color<S: Into<Color>>(self, color: S) -> ColoredString;
+    /// Set the color of the text if one is given, otherwise leave it as is
+    #[inline]
+    fn color_opt<S: Into<Color>>(self, color: Option<S>) -> ColoredString
+    where
+        Self: Sized + Into<ColoredString>,
+    {
+        match color {
+            Some(color) => self.color(color),
+            None => self.into(),
+        }
+    }
     /// `Black` background color
     #[inline]
     fn on_black(self) -> ColoredString
@@ -428,8 +546,37 @@ pub trait Colorize {
     {
         self.on_color(Color::TrueColor { r, g, b })
     }
+    /// Background color `idx` of the 256 color palette, see
+    /// [`Color::Ansi256`]
+    #[inline]
+    fn on_ansi256(self, idx: u8) -> ColoredString
+    where
+        Self: Sized,
+    {
+        self.on_color(Color::Ansi256(idx))
+    }
+    /// The default background color of the terminal, see [`Color::Default`]
+    #[inline]
+    fn on_default(self) -> ColoredString
+    where
+        Self: Sized,
+    {
+        self.on_color(Color::Default)
+    }
     /// Return the color of the background
     fn on_color<S: Into<Color>>(self, color: S) -> ColoredString;
+    /// Set the color of the background if one is given, otherwise leave it as
+    /// is
+    #[inline]
+    fn on_color_opt<S: Into<Color>>(self, color: Option<S>) -> ColoredString
+    where
+        Self: Sized + Into<ColoredString>,
+    {
+        match color {
+            Some(color) => self.on_color(color),
+            None => self.into(),
+        }
+    }
     /// Clear the text
     fn clear(self) -> ColoredString;
     /// Normalize the text
@@ -452,11 +599,173 @@ pub trait Colorize {
     fn reversed(self) -> ColoredString;
     /// Hide the text
     fn hidden(self) -> ColoredString;
+    /// Hide the text, alias of [`Colorize::hidden`]
+    #[inline]
+    fn conceal(self) -> ColoredString
+    where
+        Self: Sized,
+    {
+        self.hidden()
+    }
+    /// Show text hidden by [`Colorize::hidden`] again
+    #[inline]
+    fn reveal(self) -> ColoredString
+    where
+        Self: Sized + Into<ColoredString>,
+    {
+        ColoredString::reveal(self.into())
+    }
     /// Strikethrough the text
     fn strikethrough(self) -> ColoredString;
+
+    /// Apply the colors and style of a [`StyleSpec`], keeping the ones it does
+    /// not set
+    #[inline]
+    fn with_spec(self, spec: StyleSpec) -> ColoredString
+    where
+        Self: Sized + Into<ColoredString>,
+    {
+        ColoredString::with_spec(self.into(), spec)
+    }
+    /// Style of a successful result, `ok` in the [`theme`] (green by default)
+    #[inline]
+    fn ok(self) -> ColoredString
+    where
+        Self: Sized + Into<ColoredString>,
+    {
+        self.with_spec(theme::get("ok").unwrap_or_default())
+    }
+    /// Style of an error, `err` in the [`theme`] (red by default)
+    #[inline]
+    fn err(self) -> ColoredString
+    where
+        Self: Sized + Into<ColoredString>,
+    {
+        self.with_spec(theme::get("err").unwrap_or_default())
+    }
+    /// Style of a warning, `warn` in the [`theme`] (yellow by default)
+    #[inline]
+    fn warn(self) -> ColoredString
+    where
+        Self: Sized + Into<ColoredString>,
+    {
+        self.with_spec(theme::get("warn").unwrap_or_default())
+    }
+    /// Style of a [`Severity`] level, `severity.<name>` in the [`theme`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!("disk full".by_severity(Severity::Error), "disk full".red().bold());
+    /// assert_eq!("93%".by_severity(Severity::from_value(0.93)), "93%".white().on_red());
+    /// ```
+    #[inline]
+    fn by_severity(self, level: Severity) -> ColoredString
+    where
+        Self: Sized + Into<ColoredString>,
+    {
+        self.with_spec(level.spec())
+    }
+    /// Apply `f` only if the terminal displays at least `level` colors, so
+    /// that an explicit fallback can be chosen otherwise.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let accent = "x"
+    ///     .if_supports_color(ColorLevel::TrueColor, |s| s.truecolor(255, 136, 0))
+    ///     .unwrap_or_else(|| "x".yellow());
+    /// ```
+    #[inline]
+    fn if_supports_color<F>(self, level: ColorLevel, f: F) -> Option<ColoredString>
+    where
+        Self: Sized,
+        F: FnOnce(Self) -> ColoredString,
+    {
+        (control::color_level() >= level).then(|| f(self))
+    }
 }
 
 impl ColoredString {
+    /// Create an unstyled string from untrusted input, e.g. user-controlled
+    /// text echoed back to the terminal. Every control character and escape
+    /// sequence but the SGR ones (colors and styles) is removed, so that the
+    /// input cannot move the cursor, change the title, clear the screen, etc.
+    /// A reset is appended if the SGR sequences leave a style on.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let echoed = ColoredString::untrusted("\x1B]0;pwned\x07\x1B[2Jhi \x1B[1mthere").red();
+    /// assert_eq!(&*echoed, "hi \x1B[1mthere\x1B[0m");
+    /// ```
+    #[must_use]
+    pub fn untrusted(input: &str) -> Self {
+        let mut input = strip_non_sgr(input);
+        ensure_suffix_reset(&mut input);
+        Self {
+            input,
+            ..Self::default()
+        }
+    }
+
+    /// Create an empty unstyled string with room for exactly `capacity`
+    /// bytes of text
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let line = ColoredString::with_exact_capacity(80);
+    /// assert!(line.is_empty());
+    /// assert_eq!(line.capacity(), 80);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_exact_capacity(capacity: usize) -> Self {
+        let mut input = String::new();
+        input.reserve_exact(capacity);
+        Self {
+            input,
+            ..Self::default()
+        }
+    }
+
+    /// Create an unstyled string of `s`, copied into the allocation of
+    /// `buf` whose contents are discarded, e.g. the buffer recovered from the
+    /// previous frame of a render loop with [`ColoredString::into_buffer`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let mut buf = String::with_capacity(64);
+    /// for frame in 0..3 {
+    ///     let line = ColoredString::from_buffer(buf, &format!("frame {frame}")).green();
+    ///     println!("{line}");
+    ///     buf = line.into_buffer();
+    /// }
+    /// assert_eq!(buf.capacity(), 64);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_buffer(mut buf: String, s: &str) -> Self {
+        buf.clear();
+        buf.push_str(s);
+        Self {
+            input: buf,
+            ..Self::default()
+        }
+    }
+
+    /// The buffer holding the text, without the colors and style, to reuse
+    /// its allocation with [`ColoredString::from_buffer`]
+    #[inline]
+    #[must_use]
+    pub fn into_buffer(self) -> String {
+        self.input
+    }
+
+    /// The number of bytes of text the string can hold without reallocating
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.input.capacity()
+    }
+
     /// Get the current background color applied.
     ///
     /// ```rust
@@ -503,6 +812,21 @@ impl ColoredString {
         self.style
     }
 
+    /// Get a mutable reference to the [`Style`], to switch attributes on or
+    /// off in place.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let mut cstr = "warning".yellow().bold();
+    /// cstr.style_mut().remove(Styles::Bold);
+    /// cstr.style_mut().insert(Styles::Underline);
+    /// assert_eq!(cstr, "warning".yellow().underline());
+    /// ```
+    #[inline]
+    pub fn style_mut(&mut self) -> StyleMut<'_> {
+        StyleMut::new(&mut self.style)
+    }
+
     /// Checks if the colored string has no color or styling.
     ///
     /// ```rust
@@ -518,101 +842,912 @@ impl ColoredString {
         self.bgcolor.is_none() && self.fgcolor.is_none() && self.style == style::CLEAR
     }
 
-    /// Should the text be colorized?
-    #[cfg(not(feature = "no-color"))]
-    #[allow(clippy::unused_self)]
-    fn has_colors(&self) -> bool {
-        control::SHOULD_COLORIZE.should_colorize()
-    }
-
-    /// Should the text be colorized?
-    #[cfg(feature = "no-color")]
-    #[allow(clippy::unused_self)]
-    const fn has_colors(&self) -> bool {
-        false
+    /// The text without any color or style. Hidden text is masked with
+    /// spaces, the way a terminal displays it.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!("secret".red().to_plain(), "secret");
+    /// assert_eq!("secret".hidden().to_plain(), "      ");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_plain(&self) -> String {
+        if self.style.contains(Styles::Hidden) {
+            self.input.chars().map(|_| ' ').collect()
+        } else {
+            self.input.clone()
+        }
     }
 
-    /// Find the [`Style`] of the string
-    fn compute_style(&self) -> String {
-        if !self.has_colors() || self.is_plain() {
-            return String::new();
+    /// The number of bytes of the rendering, escape sequences included, see
+    /// [`ColoredString::to_ansi_string`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let cstr = "日本".red();
+    /// assert_eq!(cstr.byte_len_raw(), cstr.to_string().len());
+    /// ```
+    #[must_use]
+    pub fn byte_len_raw(&self) -> usize {
+        let style = self.compute_style_with(self.has_colors());
+        if style.is_empty() {
+            return self.input.len();
         }
 
-        let mut res = String::from("\x1B[");
-        let mut has_wrote = if self.style == style::CLEAR {
-            false
-        } else {
-            res.push_str(&self.style.to_str());
-            true
-        };
+        let inner_resets = self.input.matches(RESET).count();
+        style.len() * (1 + inner_resets) + self.input.len() + RESET.len()
+    }
 
-        if let Some(ref bgcolor) = self.bgcolor {
-            if has_wrote {
-                res.push(';');
-            }
+    /// The number of bytes of the text, without the escape sequences added
+    /// when rendering. Same as `len()` through [`Deref`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!("日本".red().byte_len_text(), 6);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn byte_len_text(&self) -> usize {
+        self.input.len()
+    }
 
-            res.push_str(&bgcolor.to_bg_str());
-            has_wrote = true;
-        }
+    /// The number of characters of the text
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!("日本".red().char_count(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn char_count(&self) -> usize {
+        self.input.chars().count()
+    }
 
-        if let Some(ref fgcolor) = self.fgcolor {
-            if has_wrote {
-                res.push(';');
-            }
+    /// The number of terminal columns taken by the text. Wide characters take
+    /// two columns, combining marks and escape sequences embedded in the text
+    /// take none.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!("日本".red().display_width(), 4);
+    /// assert_eq!(format!("a{}", "b".blue()).normal().display_width(), 2);
+    /// ```
+    #[must_use]
+    pub fn display_width(&self) -> usize {
+        StyledChars::new(&self.input, StyleSpec::new())
+            .map(|(c, _)| width::char_width(c))
+            .sum()
+    }
 
-            res.push_str(&fgcolor.to_fg_str());
+    /// The number of terminal columns taken by the text, counting emoji as
+    /// `policy` says. See [`ColoredString::display_width`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let cstr = "❤\u{FE0F} ok".green();
+    /// assert_eq!(cstr.display_width_with(WidthPolicy::default()), 4);
+    /// assert_eq!(cstr.display_width_with(WidthPolicy::grapheme()), 5);
+    /// ```
+    #[must_use]
+    pub fn display_width_with(&self, policy: WidthPolicy) -> usize {
+        policy.chars_width(StyledChars::new(&self.input, StyleSpec::new()).map(|(c, _)| c))
+    }
+
+    /// Render the string with its escape sequences, if it should be
+    /// colorized. Same as `to_string()`, but sizes the buffer up front.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let cstr = "warning".yellow();
+    /// assert_eq!(cstr.to_ansi_string(), cstr.to_string());
+    /// ```
+    #[must_use]
+    pub fn to_ansi_string(&self) -> String {
+        let style = self.compute_style_with(self.has_colors());
+        if style.is_empty() {
+            return self.input.clone();
         }
 
-        res.push('m');
+        let escaped_input = self.escape_inner_reset_sequences_with(true);
+        let mut res = String::with_capacity(style.len() + escaped_input.len() + RESET.len());
+        res.push_str(&style);
+        res.push_str(&escaped_input);
+        res.push_str(RESET);
         res
     }
 
-    fn escape_inner_reset_sequences(&self) -> Cow<str> {
-        if !self.has_colors() || self.is_plain() {
-            return self.input.as_str().into();
-        }
+    /// Render the string with its escape sequences as bytes, if it should be
+    /// colorized. Same as [`ColoredString::to_ansi_string`], for writers that
+    /// take bytes such as raw file descriptors or sockets.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let cstr = "shipped".green();
+    /// assert_eq!(cstr.as_bytes_styled(), cstr.to_string().into_bytes());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn as_bytes_styled(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.extend_rendered(&self.compute_style_with(self.has_colors()), &mut buf);
+        buf
+    }
 
-        // TODO: BoyScoutRule
-        let reset = "\x1B[0m";
-        let style = self.compute_style();
-        let matches: Vec<usize> = self
-            .input
-            .match_indices(reset)
-            .map(|(idx, _)| idx)
-            .collect();
-        if matches.is_empty() {
-            return self.input.as_str().into();
+    /// Write the string with its escape sequences to `writer`, if it should
+    /// be colorized, without building the rendering first. The escape
+    /// sequences and the text are handed to `writer` in a single vectored
+    /// write, so that a terminal shared with other threads gets the whole
+    /// string at once. Writing to a `&mut [u8]` fails if it is too short.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let mut buf = [0; 64];
+    /// let mut out = &mut buf[..];
+    /// "sent".blue().write_styled(&mut out).unwrap();
+    /// let len = 64 - out.len();
+    /// assert_eq!(&buf[..len], "sent".blue().to_string().as_bytes());
+    /// ```
+    ///
+    /// # Errors
+    /// Will produce an error if writing to `writer` fails
+    pub fn write_styled<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let style = self.compute_style_with(self.has_colors());
+        if style.is_empty() {
+            return writer.write_all(self.input.as_bytes());
         }
 
-        let mut input = self.input.clone();
-        input.reserve(matches.len() * style.len());
-
-        for (idx_in_matches, offset) in matches.into_iter().enumerate() {
-            // shift the offset to the end of the reset sequence and take in account
-            // the number of matches we have escaped (which shift the index to insert)
-            let mut offset = offset + reset.len() + idx_in_matches * style.len();
+        let escaped_input = self.escape_inner_reset_sequences_with(true);
+        writers::write_all_vectored(writer, &[
+            style.as_bytes(),
+            escaped_input.as_bytes(),
+            RESET.as_bytes(),
+        ])
+    }
 
-            for cchar in style.chars() {
-                input.insert(offset, cchar);
-                offset += 1;
-            }
+    /// Append the rendering of the string with the effective `style` to
+    /// `buf`
+    fn extend_rendered(&self, style: &str, buf: &mut Vec<u8>) {
+        if style.is_empty() {
+            buf.extend_from_slice(self.input.as_bytes());
+            return;
         }
 
-        input.into()
+        let escaped_input = self.escape_inner_reset_sequences_with(true);
+        buf.reserve(style.len() + escaped_input.len() + RESET.len());
+        buf.extend_from_slice(style.as_bytes());
+        buf.extend_from_slice(escaped_input.as_bytes());
+        buf.extend_from_slice(RESET.as_bytes());
     }
-}
 
-impl Default for ColoredString {
+    /// Append a reset to the text if it contains an escape sequence which is
+    /// not reset at its end, e.g. when it was built from raw escapes. See
+    /// [`ensure_suffix_reset`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let mut cstr = ColoredString::from(format!("{}red", fg_escape(Color::Red)).as_str());
+    /// cstr.ensure_suffix_reset();
+    /// assert_eq!(&*cstr, "\x1B[31mred\x1B[0m");
+    /// ```
     #[inline]
-    fn default() -> Self {
-        Self {
-            input:   String::default(),
-            fgcolor: None,
-            bgcolor: None,
-            style:   style::CLEAR,
-        }
+    pub fn ensure_suffix_reset(&mut self) {
+        ensure_suffix_reset(&mut self.input);
     }
-}
+
+    /// Replace the colors and style with the ones of `other`, keeping the
+    /// text.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let original = "original".red().on_blue().bold();
+    /// let cstr = "rewrapped".green().italic().copy_style_from(&original);
+    /// assert_eq!(cstr, "rewrapped".red().on_blue().bold());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn copy_style_from(mut self, other: &Self) -> Self {
+        if cfg!(not(feature = "strip-styles")) {
+            self.fgcolor = other.fgcolor;
+            self.bgcolor = other.bgcolor;
+            self.style = other.style;
+        }
+        self
+    }
+
+    /// Append plain text, which inherits the color and style of the string.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let mut cstr = "hello".red();
+    /// cstr.push_str(" world");
+    /// assert_eq!(cstr, "hello world".red());
+    /// ```
+    #[inline]
+    pub fn push_str(&mut self, s: &str) {
+        self.input.push_str(s);
+    }
+
+    /// Append another colored string, keeping the color and style of both.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let text = "hello".red().push_colored(" world".blue());
+    /// assert_eq!(text.segments(), &["hello".red(), " world".blue()]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn push_colored(self, other: Self) -> ColoredText {
+        let mut text = ColoredText::from(self);
+        text.push(other);
+        text
+    }
+
+    /// Iterate over the characters and the [`StyleSpec`] each of them is
+    /// displayed with, taking the escape sequences embedded in the text (e.g.
+    /// nested colored strings) into account.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let cstr = format!("a{}", "b".blue()).red();
+    /// let specs: Vec<_> = cstr.chars_styled().map(|(_, spec)| spec.fgcolor()).collect();
+    /// # if cstr.contains('\x1B') {
+    /// assert_eq!(specs, [Some(Color::Red), Some(Color::Blue)]);
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn chars_styled(&self) -> StyledChars<'_> {
+        StyledChars::new(&self.input, StyleSpec::from(self))
+    }
+
+    /// Tag the string with a target, so that it is only colorized if the tag
+    /// is enabled, see [`control::set_tag_enabled`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// control::set_tag_enabled("json", false);
+    /// assert_eq!("null".red().tagged("json").to_string(), "null");
+    /// # control::clear_tag_rules();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn tagged(self, tag: &'static str) -> control::Tagged {
+        control::Tagged::new(self, tag)
+    }
+
+    /// Prepend `prefix` to every line. Each line keeps the color and style of
+    /// the string, and is reset before its newline so that the style never
+    /// bleeds into the next prefix. Styles switched on by escape sequences
+    /// embedded in the text are switched on again after the prefix of the
+    /// following lines.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let quoted = "first\nsecond".red().prefix_lines(&"│ ".blue());
+    /// assert_eq!(quoted.to_plain(), "│ first\n│ second");
+    /// assert_eq!(quoted.segments()[1], "first".red());
+    /// ```
+    #[must_use]
+    pub fn prefix_lines(&self, prefix: &Self) -> ColoredText {
+        let mut text = ColoredText::new();
+        if self.input.is_empty() {
+            text.push(prefix.clone());
+            return text;
+        }
+
+        let base = StyleSpec::from(self);
+        let mut spec = base;
+        for line in self.input.split_inclusive('\n') {
+            let (line, newline) = match line.strip_suffix('\n') {
+                Some(line) => (line, true),
+                None => (line, false),
+            };
+
+            text.push(prefix.clone());
+            if !line.is_empty() {
+                let mut part = Self::from(line);
+                if cfg!(not(feature = "strip-styles")) {
+                    part.fgcolor = spec.fgcolor();
+                    part.bgcolor = spec.bgcolor();
+                    part.style = spec.style();
+                }
+                part.ensure_suffix_reset();
+                text.push(part);
+            }
+            for event in ansi::SgrParser::new(line) {
+                if let ansi::AnsiEvent::Sgr(params) = event {
+                    spec = spec.apply_sgr(params, base).unwrap_or(spec);
+                }
+            }
+            if newline {
+                text.push(Self::from("\n"));
+            }
+        }
+        text
+    }
+
+    /// Give every line a background going from `from` on the first line to
+    /// `to` on the last one, e.g. for banners. Lines are padded with spaces to
+    /// the width of the widest one so that the background forms a rectangle,
+    /// and keep the foreground and style of the string. Line endings are kept
+    /// as is, outside of the background.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let banner = "colored\nv2".bold().bg_gradient(Color::Blue, Color::Magenta);
+    /// assert_eq!(banner.to_plain(), "colored\nv2     ");
+    /// assert_eq!(banner.segments()[2], "v2     ".bold().on_truecolor(255, 0, 255));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn bg_gradient(&self, from: Color, to: Color) -> ColoredText {
+        self.bg_gradient_with(&Gradient::new(&[(0.0, from), (1.0, to)]))
+    }
+
+    /// Give every line a background going along `gradient` from the first
+    /// line to the last one, see [`ColoredString::bg_gradient`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let banner = ColoredString::from("colored\nv2").bg_gradient_with(&Gradient::VIRIDIS);
+    /// assert_eq!(banner.segments()[0], "colored".on_truecolor(0x44, 0x01, 0x54));
+    /// ```
+    #[must_use]
+    pub fn bg_gradient_with(&self, gradient: &Gradient) -> ColoredText {
+        let lines: Vec<(&str, &str)> = self
+            .input
+            .split_inclusive('\n')
+            .map(|line| {
+                let body = match line.strip_suffix('\n') {
+                    Some(body) => body.strip_suffix('\r').unwrap_or(body),
+                    None => line,
+                };
+                line.split_at(body.len())
+            })
+            .collect();
+        let width = lines
+            .iter()
+            .map(|(line, _)| Self::from(*line).display_width())
+            .max()
+            .unwrap_or(0);
+
+        let mut text = ColoredText::new();
+        for (i, (line, newline)) in lines.iter().enumerate() {
+            #[allow(clippy::cast_precision_loss)]
+            let t = if lines.len() > 1 { i as f32 / (lines.len() - 1) as f32 } else { 0.0 };
+            let pad = width - Self::from(*line).display_width();
+            let padded = format!("{line}{}", " ".repeat(pad));
+            text.push(Self::from(padded.as_str()).copy_style_from(self).on_color(gradient.at(t)));
+            if !newline.is_empty() {
+                text.push(Self::from(*newline));
+            }
+        }
+        text
+    }
+
+    /// Give every character a foreground going along `gradient` from the
+    /// first character to the last one, keeping the background and style of
+    /// the string. Escape sequences embedded in the text take no step of the
+    /// gradient, and the styles they switch on are kept.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let title = "rainbow".bold().gradient(&Gradient::RAINBOW);
+    /// assert_eq!(title.to_plain(), "rainbow");
+    /// assert_eq!(title.segments()[0], "r".bold().truecolor(0xFF, 0, 0));
+    /// assert_eq!(title.segments()[6], "w".bold().truecolor(0x94, 0, 0xD3));
+    /// ```
+    #[must_use]
+    pub fn gradient(&self, gradient: &Gradient) -> ColoredText {
+        let count = self.chars_styled().count();
+        let mut text = ColoredText::new();
+        let mut buf = [0; 4];
+        for (i, (c, spec)) in self.chars_styled().enumerate() {
+            #[allow(clippy::cast_precision_loss)]
+            let t = if count > 1 { i as f32 / (count - 1) as f32 } else { 0.0 };
+            let ch = Self::from(&*c.encode_utf8(&mut buf));
+            text.push(ch.with_spec(spec).color(gradient.at(t)));
+        }
+        text
+    }
+
+    /// Transform the text while keeping the color and style.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let cstr = "hello".red().bold().map_text(str::to_uppercase);
+    /// assert_eq!(cstr, "HELLO".red().bold());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn map_text<F: FnOnce(&str) -> String>(mut self, f: F) -> Self {
+        self.input = f(&self.input);
+        self
+    }
+
+    /// Transform the text with a fallible function while keeping the color
+    /// and style.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let cstr = "42".blue().try_map_text(|s| s.parse::<u8>().map(|n| (n + 1).to_string()));
+    /// assert_eq!(cstr, Ok("43".blue()));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the error of `f` if the transformation fails
+    #[inline]
+    pub fn try_map_text<E, F: FnOnce(&str) -> Result<String, E>>(mut self, f: F) -> Result<Self, E> {
+        self.input = f(&self.input)?;
+        Ok(self)
+    }
+
+    /// Remove leading and trailing whitespace while keeping the color and
+    /// style. Unlike [`str::trim`] reached through `Deref`, this returns a
+    /// [`ColoredString`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!("  hello  ".red().trim(), "hello".red());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn trim(self) -> Self {
+        self.map_text(|s| s.trim().to_owned())
+    }
+
+    /// Remove leading whitespace while keeping the color and style.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!("  hello  ".red().trim_start(), "hello  ".red());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn trim_start(self) -> Self {
+        self.map_text(|s| s.trim_start().to_owned())
+    }
+
+    /// Remove trailing whitespace while keeping the color and style.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!("  hello  ".red().trim_end(), "  hello".red());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn trim_end(self) -> Self {
+        self.map_text(|s| s.trim_end().to_owned())
+    }
+
+    /// Split the string at the visible column `col`. Escape sequences embedded
+    /// in the text take no column and wide characters two; a wide character
+    /// straddling `col` goes to the second half. Both halves keep the colors
+    /// and style of the string, and an embedded style still on at the split
+    /// is reset at the end of the first half and reapplied at the start of
+    /// the second.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let (left, right) = "日本語".red().split_at_visible(3);
+    /// assert_eq!((&*left, &*right), ("日", "本語"));
+    /// assert_eq!(right.fgcolor(), Some(Color::Red));
+    ///
+    /// let nested = format!("a{}", "bc".blue()).normal();
+    /// let (left, right) = nested.split_at_visible(2);
+    /// assert_eq!(&*left, "a\x1B[34mb\x1B[0m");
+    /// assert_eq!(&*right, "\x1B[34mc\x1B[0m");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn split_at_visible(&self, col: usize) -> (Self, Self) {
+        self.split_at_visible_with(col, WidthPolicy::default())
+    }
+
+    /// Split the string at the visible column `col`, counting emoji as
+    /// `policy` says. See [`ColoredString::split_at_visible`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let cstr = "❤\u{FE0F}!".red();
+    /// let (left, right) = cstr.split_at_visible_with(1, WidthPolicy::default());
+    /// assert_eq!((&*left, &*right), ("❤\u{FE0F}", "!"));
+    /// let (left, right) = cstr.split_at_visible_with(1, WidthPolicy::grapheme());
+    /// assert_eq!((&*left, &*right), ("", "❤\u{FE0F}!"));
+    /// ```
+    #[must_use]
+    pub fn split_at_visible_with(&self, col: usize, policy: WidthPolicy) -> (Self, Self) {
+        let mut columns = width::Columns::new(policy);
+        let mut width = 0;
+        // the start of the last character taking a column, which a VS16 may
+        // widen
+        let mut last = 0;
+        // the embedded SGR sequences still on
+        let mut open = String::new();
+        let mut pos = 0;
+        let mut split = self.input.len();
+        'scan: for event in ansi::SgrParser::new(&self.input) {
+            match event {
+                ansi::AnsiEvent::Text(text) => {
+                    for (i, c) in text.char_indices() {
+                        let char_width = columns.push(c);
+                        if width + char_width > col {
+                            split = if c == '\u{FE0F}' { last } else { pos + i };
+                            break 'scan;
+                        }
+                        if char_width > 0 {
+                            last = pos + i;
+                        }
+                        width += char_width;
+                    }
+                    pos += text.len();
+                },
+                ansi::AnsiEvent::Sgr(params) => {
+                    if params.is_empty() || params == "0" {
+                        open.clear();
+                    } else {
+                        open.push_str("\x1B[");
+                        open.push_str(params);
+                        open.push('m');
+                    }
+                    pos += params.len() + 3;
+                },
+                // OSC payloads such as hyperlinks take no column
+                ansi::AnsiEvent::Escape(seq) => pos += seq.len(),
+            }
+        }
+
+        let mut left = self.input[..split].to_owned();
+        if !open.is_empty() {
+            left.push_str(RESET);
+        }
+        open.push_str(&self.input[split..]);
+        let half = |input| Self {
+            input,
+            fgcolor: self.fgcolor,
+            bgcolor: self.bgcolor,
+            style: self.style,
+        };
+        (half(left), half(open))
+    }
+
+    /// Should the text be colorized?
+    #[cfg(not(any(feature = "no-color", feature = "strip-styles")))]
+    #[allow(clippy::unused_self)]
+    fn has_colors(&self) -> bool {
+        control::SHOULD_COLORIZE.should_colorize()
+    }
+
+    /// Should the text be colorized?
+    #[cfg(any(feature = "no-color", feature = "strip-styles"))]
+    #[allow(clippy::unused_self)]
+    const fn has_colors(&self) -> bool {
+        false
+    }
+
+    /// Add a [`Styles`] to the string, unless styling is compiled out
+    #[inline]
+    fn add_style(&mut self, style: style::Styles) {
+        if cfg!(not(feature = "strip-styles")) {
+            self.style.add(style);
+        }
+    }
+
+    /// Find the [`Style`] of the string
+    #[cfg(test)]
+    fn compute_style(&self) -> String {
+        self.compute_style_with(self.has_colors()).to_string()
+    }
+
+    /// Find the [`Style`] of the string, which is empty unless `colorize`.
+    /// The escape sequence is shared when style interning is enabled, see
+    /// [`control::set_style_interning`]
+    fn compute_style_with(&self, colorize: bool) -> Arc<str> {
+        if !colorize || self.is_plain() {
+            return Arc::clone(&EMPTY_STYLE);
+        }
+
+        let (level, syntax) = control::SHOULD_COLORIZE.render_caps();
+        let (style, fgcolor, bgcolor) = self.effective_style();
+        let (style, fgcolor) = control::SHOULD_COLORIZE.adjust_bold_bright(style, fgcolor, level);
+        if style == style::CLEAR && bgcolor.is_none() && fgcolor.is_none() {
+            return Arc::clone(&EMPTY_STYLE);
+        }
+
+        control::SHOULD_COLORIZE.intern_style((style, bgcolor, fgcolor, syntax), || {
+            Self::build_style(style, bgcolor, fgcolor, syntax)
+        })
+    }
+
+    /// The style and colors of the string once the global settings apply:
+    /// the color mapping, the disabled styles and the accessibility mode
+    pub(crate) fn effective_style(&self) -> (Style, Option<Color>, Option<Color>) {
+        let fgcolor = self.fgcolor.map(|c| control::SHOULD_COLORIZE.map_color(c));
+        let bgcolor = self.bgcolor.map(|c| control::SHOULD_COLORIZE.map_color(c));
+        let style = control::SHOULD_COLORIZE.filter_style(self.style);
+        let (style, bgcolor) = control::SHOULD_COLORIZE.adjust_accessibility(style, bgcolor);
+        (style, fgcolor, bgcolor)
+    }
+
+    /// Build the escape sequence switching on `style` and the colors
+    fn build_style(
+        style: Style,
+        bgcolor: Option<Color>,
+        fgcolor: Option<Color>,
+        syntax: control::SgrSyntax,
+    ) -> String {
+        let mut res = String::from("\x1B[");
+        let mut has_wrote = if style == style::CLEAR {
+            false
+        } else {
+            res.push_str(&style.to_str());
+            true
+        };
+
+        if let Some(bgcolor) = bgcolor {
+            if has_wrote {
+                res.push(';');
+            }
+
+            res.push_str(&bgcolor.to_bg_str_in(syntax));
+            has_wrote = true;
+        }
+
+        if let Some(fgcolor) = fgcolor {
+            if has_wrote {
+                res.push(';');
+            }
+
+            res.push_str(&fgcolor.to_fg_str_in(syntax));
+        }
+
+        res.push('m');
+        res
+    }
+
+    #[cfg(test)]
+    fn escape_inner_reset_sequences(&self) -> Cow<str> {
+        self.escape_inner_reset_sequences_with(self.has_colors())
+    }
+
+    fn escape_inner_reset_sequences_with(&self, colorize: bool) -> Cow<'_, str> {
+        if !colorize || self.is_plain() {
+            return self.input.as_str().into();
+        }
+
+        // TODO: BoyScoutRule
+        let reset = RESET;
+        let style = self.compute_style_with(colorize);
+        let matches: Vec<usize> = self
+            .input
+            .match_indices(reset)
+            .map(|(idx, _)| idx)
+            .collect();
+        if matches.is_empty() {
+            return self.input.as_str().into();
+        }
+
+        let mut input = self.input.clone();
+        input.reserve(matches.len() * style.len());
+
+        for (idx_in_matches, offset) in matches.into_iter().enumerate() {
+            // shift the offset to the end of the reset sequence and take in account
+            // the number of matches we have escaped (which shift the index to insert)
+            let mut offset = offset + reset.len() + idx_in_matches * style.len();
+
+            for cchar in style.chars() {
+                input.insert(offset, cchar);
+                offset += 1;
+            }
+        }
+
+        input.into()
+    }
+}
+
+/// The escape sequence resetting every color and style
+pub const RESET: &str = "\x1B[0m";
+
+/// The escape sequence of unstyled strings, shared to avoid allocating it
+static EMPTY_STYLE: LazyLock<Arc<str>> = LazyLock::new(|| Arc::from(""));
+
+/// The escape sequence setting the foreground to `color`, regardless of
+/// whether colors are enabled. Useful for prompt builders (e.g. `PS1`) where
+/// a [`ColoredString`] is overkill.
+///
+/// ```rust
+/// # use colored::*;
+/// assert_eq!(fg_escape(Color::Red), "\x1B[31m");
+/// assert_eq!(fg_escape(Color::TrueColor { r: 1, g: 2, b: 3 }), "\x1B[38;2;1;2;3m");
+/// ```
+#[inline]
+#[must_use]
+pub fn fg_escape(color: Color) -> String {
+    format!("\x1B[{}m", color.to_fg_str())
+}
+
+/// The escape sequence setting the background to `color`, see [`fg_escape`]
+#[inline]
+#[must_use]
+pub fn bg_escape(color: Color) -> String {
+    format!("\x1B[{}m", color.to_bg_str())
+}
+
+/// Write the escape sequence setting the foreground to `color` into `w`,
+/// without allocating. See [`fg_escape`].
+///
+/// ```rust
+/// # use colored::*;
+/// let mut prompt = String::new();
+/// write_fg(&mut prompt, Color::Green).unwrap();
+/// prompt.push_str("$ ");
+/// prompt.push_str(RESET);
+/// assert_eq!(prompt, "\x1B[32m$ \x1B[0m");
+/// ```
+///
+/// # Errors
+/// Will produce an error if `w` fails to write
+#[inline]
+pub fn write_fg<W: fmt::Write>(w: &mut W, color: Color) -> fmt::Result {
+    write!(w, "\x1B[{}m", color.to_fg_str())
+}
+
+/// Write the escape sequence setting the background to `color` into `w`,
+/// see [`write_fg`]
+///
+/// # Errors
+/// Will produce an error if `w` fails to write
+#[inline]
+pub fn write_bg<W: fmt::Write>(w: &mut W, color: Color) -> fmt::Result {
+    write!(w, "\x1B[{}m", color.to_bg_str())
+}
+
+/// Wrap `text` in an OSC 8 hyperlink pointing to `url`.
+///
+/// When the terminal is not known to support hyperlinks, the text is returned
+/// as is so that no escape sequence leaks into logs or pipes.
+///
+/// ```rust
+/// # use colored::*;
+/// control::set_override(false);
+/// assert_eq!(hyperlink("docs", "https://docs.rs"), "docs");
+/// # control::unset_override();
+/// ```
+#[inline]
+pub fn hyperlink<T: fmt::Display>(text: T, url: &str) -> String {
+    if control::SHOULD_COLORIZE.supports_hyperlinks() {
+        format!("\x1B]8;;{url}\x1B\\{text}\x1B]8;;\x1B\\")
+    } else {
+        text.to_string()
+    }
+}
+
+/// Join values which may contain foreign escape sequences, e.g. strings
+/// colored by another library or a child process, with `separator`.
+///
+/// A reset is inserted after every value containing an escape sequence which
+/// does not already end with one, so that its styling cannot bleed into the
+/// separator or the following values.
+///
+/// ```rust
+/// # use colored::*;
+/// let foreign = "\x1B[1;31merror";
+/// assert_eq!(
+///     join_with_resets([foreign, "plain"], ", "),
+///     "\x1B[1;31merror\x1B[0m, plain"
+/// );
+/// ```
+#[inline]
+pub fn join_with_resets<I>(values: I, separator: &str) -> String
+where
+    I: IntoIterator,
+    I::Item: fmt::Display,
+{
+    let mut res = String::new();
+    for (i, value) in values.into_iter().enumerate() {
+        if i > 0 {
+            res.push_str(separator);
+        }
+
+        let mut value = value.to_string();
+        ensure_suffix_reset(&mut value);
+        res.push_str(&value);
+    }
+    res
+}
+
+/// Append a reset to `buf` if it contains an escape sequence but does not
+/// already end with a reset, so that its styling cannot bleed into whatever
+/// is written after it.
+///
+/// ```rust
+/// # use colored::*;
+/// let mut line = format!("{}{}", fg_escape(Color::Red), "error");
+/// ensure_suffix_reset(&mut line);
+/// assert_eq!(line, "\x1B[31merror\x1B[0m");
+/// ensure_suffix_reset(&mut line);
+/// assert_eq!(line, "\x1B[31merror\x1B[0m");
+/// ```
+#[inline]
+pub fn ensure_suffix_reset(buf: &mut String) {
+    if buf.contains('\x1B') && !buf.ends_with(RESET) {
+        buf.push_str(RESET);
+    }
+}
+
+/// Remove every control character and escape sequence of `input` but the
+/// SGR ones, keeping newlines and tabs. See [`ColoredString::untrusted`]
+fn strip_non_sgr(input: &str) -> String {
+    let mut res = String::with_capacity(input.len());
+    for event in ansi::SgrParser::new(input) {
+        match event {
+            ansi::AnsiEvent::Text(text) => res.extend(
+                text.chars()
+                    .filter(|&c| !c.is_control() || c == '\n' || c == '\t'),
+            ),
+            ansi::AnsiEvent::Sgr(params) => {
+                res.push_str("\x1B[");
+                res.push_str(params);
+                res.push('m');
+            },
+            ansi::AnsiEvent::Escape(_) => {},
+        }
+    }
+    res
+}
+
+/// Remove the SGR sequences of `buf` which have no effect because a reset
+/// follows them before any text, e.g. the doubled reset left when rendered
+/// strings are concatenated. The rendering is the same, only shorter.
+///
+/// ```rust
+/// # use colored::*;
+/// let mut log = format!("{}{}{}", "a".red(), RESET, "b".blue());
+/// compact_resets(&mut log);
+/// assert_eq!(log, format!("{}{}", "a".red(), "b".blue()));
+/// ```
+pub fn compact_resets(buf: &mut String) {
+    let mut res = String::with_capacity(buf.len());
+    // where the SGR sequences written right before the current position start
+    let mut run_start = None;
+    for event in ansi::SgrParser::new(buf) {
+        match event {
+            ansi::AnsiEvent::Text(text) => {
+                run_start = None;
+                res.push_str(text);
+            },
+            ansi::AnsiEvent::Sgr(params) => {
+                if params.is_empty() || params == "0" {
+                    let start = *run_start.get_or_insert(res.len());
+                    res.truncate(start);
+                } else if run_start.is_none() {
+                    run_start = Some(res.len());
+                }
+                res.push_str("\x1B[");
+                res.push_str(params);
+                res.push('m');
+            },
+            ansi::AnsiEvent::Escape(seq) => {
+                run_start = None;
+                res.push_str(seq);
+            },
+        }
+    }
+    *buf = res;
+}
+
+impl Default for ColoredString {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            input:   String::default(),
+            fgcolor: None,
+            bgcolor: None,
+            style:   style::CLEAR,
+        }
+    }
+}
 
 impl Deref for ColoredString {
     type Target = str;
@@ -633,16 +1768,58 @@ impl<'a> From<&'a str> for ColoredString {
     }
 }
 
+impl From<Box<str>> for ColoredString {
+    /// Reuses the buffer of the [`Box`]
+    #[inline]
+    fn from(s: Box<str>) -> Self {
+        Self {
+            input: String::from(s),
+            ..Self::default()
+        }
+    }
+}
+
+impl From<Rc<str>> for ColoredString {
+    #[inline]
+    fn from(s: Rc<str>) -> Self {
+        Self::from(&*s)
+    }
+}
+
+impl From<Arc<str>> for ColoredString {
+    #[inline]
+    fn from(s: Arc<str>) -> Self {
+        Self::from(&*s)
+    }
+}
+
+impl From<ColoredString> for String {
+    /// Renders the escape sequences, see [`ColoredString::to_ansi_string`].
+    /// Reuses the buffer of the text when nothing needs to be added
+    #[inline]
+    fn from(s: ColoredString) -> Self {
+        if s.compute_style_with(s.has_colors()).is_empty() {
+            s.input
+        } else {
+            s.to_ansi_string()
+        }
+    }
+}
+
 impl Colorize for ColoredString {
     #[inline]
     fn color<S: Into<Color>>(mut self, color: S) -> ColoredString {
-        self.fgcolor = Some(color.into());
+        if cfg!(not(feature = "strip-styles")) {
+            self.fgcolor = Some(color.into());
+        }
         self
     }
 
     #[inline]
     fn on_color<S: Into<Color>>(mut self, color: S) -> ColoredString {
-        self.bgcolor = Some(color.into());
+        if cfg!(not(feature = "strip-styles")) {
+            self.bgcolor = Some(color.into());
+        }
         self
     }
 
@@ -661,31 +1838,31 @@ impl Colorize for ColoredString {
 
     #[inline]
     fn bold(mut self) -> ColoredString {
-        self.style.add(style::Styles::Bold);
+        self.add_style(style::Styles::Bold);
         self
     }
 
     #[inline]
     fn dimmed(mut self) -> ColoredString {
-        self.style.add(style::Styles::Dimmed);
+        self.add_style(style::Styles::Dimmed);
         self
     }
 
     #[inline]
     fn italic(mut self) -> ColoredString {
-        self.style.add(style::Styles::Italic);
+        self.add_style(style::Styles::Italic);
         self
     }
 
     #[inline]
     fn underline(mut self) -> ColoredString {
-        self.style.add(style::Styles::Underline);
+        self.add_style(style::Styles::Underline);
         self
     }
 
     #[inline]
     fn blink(mut self) -> ColoredString {
-        self.style.add(style::Styles::Blink);
+        self.add_style(style::Styles::Blink);
         self
     }
 
@@ -696,116 +1873,194 @@ impl Colorize for ColoredString {
 
     #[inline]
     fn reversed(mut self) -> ColoredString {
-        self.style.add(style::Styles::Reversed);
+        self.add_style(style::Styles::Reversed);
         self
     }
 
     #[inline]
     fn hidden(mut self) -> ColoredString {
-        self.style.add(style::Styles::Hidden);
+        self.add_style(style::Styles::Hidden);
         self
     }
 
     #[inline]
-    fn strikethrough(mut self) -> ColoredString {
-        self.style.add(style::Styles::Strikethrough);
+    fn reveal(mut self) -> ColoredString {
+        self.style = self.style.without(style::Styles::Hidden.to_u8());
         self
     }
-}
 
-impl Colorize for &'_ str {
     #[inline]
-    fn color<S: Into<Color>>(self, color: S) -> ColoredString {
-        ColoredString {
-            fgcolor: Some(color.into()),
-            input: String::from(self),
-            ..ColoredString::default()
-        }
-    }
-
-    #[inline]
-    fn on_color<S: Into<Color>>(self, color: S) -> ColoredString {
-        ColoredString {
-            bgcolor: Some(color.into()),
-            input: String::from(self),
-            ..ColoredString::default()
-        }
+    fn strikethrough(mut self) -> ColoredString {
+        self.add_style(style::Styles::Strikethrough);
+        self
     }
 
     #[inline]
-    fn clear(self) -> ColoredString {
-        ColoredString {
-            input: String::from(self),
-            style: style::CLEAR,
-            ..ColoredString::default()
+    fn with_spec(mut self, spec: StyleSpec) -> ColoredString {
+        if cfg!(not(feature = "strip-styles")) {
+            self.style = self.style.union(spec.style());
         }
+        self.color_opt(spec.fgcolor())
+            .on_color_opt(spec.bgcolor())
     }
+}
 
-    #[inline]
-    fn normal(self) -> ColoredString {
-        self.clear()
-    }
-
-    #[inline]
-    fn bold(self) -> ColoredString {
-        ColoredString::from(self).bold()
-    }
-
-    #[inline]
-    fn dimmed(self) -> ColoredString {
-        ColoredString::from(self).dimmed()
-    }
-
-    #[inline]
-    fn italic(self) -> ColoredString {
-        ColoredString::from(self).italic()
-    }
-
-    #[inline]
-    fn underline(self) -> ColoredString {
-        ColoredString::from(self).underline()
-    }
+/// Implement [`Colorize`] for a type by converting it to a [`ColoredString`]
+macro_rules! impl_colorize_from {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl Colorize for $t {
+                #[inline]
+                fn color<S: Into<Color>>(self, color: S) -> ColoredString {
+                    ColoredString::from(self).color(color)
+                }
+
+                #[inline]
+                fn on_color<S: Into<Color>>(self, color: S) -> ColoredString {
+                    ColoredString::from(self).on_color(color)
+                }
+
+                #[inline]
+                fn clear(self) -> ColoredString {
+                    ColoredString::from(self)
+                }
+
+                #[inline]
+                fn normal(self) -> ColoredString {
+                    self.clear()
+                }
+
+                #[inline]
+                fn bold(self) -> ColoredString {
+                    ColoredString::from(self).bold()
+                }
+
+                #[inline]
+                fn dimmed(self) -> ColoredString {
+                    ColoredString::from(self).dimmed()
+                }
+
+                #[inline]
+                fn italic(self) -> ColoredString {
+                    ColoredString::from(self).italic()
+                }
+
+                #[inline]
+                fn underline(self) -> ColoredString {
+                    ColoredString::from(self).underline()
+                }
+
+                #[inline]
+                fn blink(self) -> ColoredString {
+                    ColoredString::from(self).blink()
+                }
+
+                #[inline]
+                fn reverse(self) -> ColoredString {
+                    self.reversed()
+                }
+
+                #[inline]
+                fn reversed(self) -> ColoredString {
+                    ColoredString::from(self).reversed()
+                }
+
+                #[inline]
+                fn hidden(self) -> ColoredString {
+                    ColoredString::from(self).hidden()
+                }
+
+                #[inline]
+                fn strikethrough(self) -> ColoredString {
+                    ColoredString::from(self).strikethrough()
+                }
+            }
+        )*
+    };
+}
 
-    #[inline]
-    fn blink(self) -> ColoredString {
-        ColoredString::from(self).blink()
-    }
+impl_colorize_from!(&'_ str, Box<str>, Rc<str>, Arc<str>);
 
+impl fmt::Display for ColoredString {
+    /// The alternate flag (`{:#}`) renders the escape codes visibly, e.g.
+    /// `\x1B[31mred\x1B[0m`, which is useful for debugging and golden files.
     #[inline]
-    fn reverse(self) -> ColoredString {
-        self.reversed()
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with(f, self.has_colors())
     }
+}
 
-    #[inline]
-    fn reversed(self) -> ColoredString {
-        ColoredString::from(self).reversed()
-    }
+impl ColoredString {
+    /// Format the string, with its colors and style only if `colorize`
+    #[inline]
+    pub(crate) fn fmt_with(&self, f: &mut fmt::Formatter, colorize: bool) -> fmt::Result {
+        self.fmt_with_backend(f, colorize, &render::Ansi, WidthPolicy::default())
+    }
+
+    /// Format the string with `backend`, with its colors and style only if
+    /// `colorize`, padding it to the width measured with `policy`. The
+    /// alternate flag always shows the escape sequences.
+    pub(crate) fn fmt_with_backend(
+        &self,
+        f: &mut fmt::Formatter,
+        colorize: bool,
+        backend: &dyn RenderBackend,
+        policy: WidthPolicy,
+    ) -> fmt::Result {
+        if f.alternate() {
+            let style = self.compute_style_with(colorize);
+            let rendered = if style.is_empty() {
+                Cow::from(self.input.as_str())
+            } else {
+                let escaped_input = self.escape_inner_reset_sequences_with(colorize);
+                format!("{style}{escaped_input}{RESET}").into()
+            };
+            return f.write_str(&rendered.replace('\x1B', "\\x1B"));
+        }
 
-    #[inline]
-    fn hidden(self) -> ColoredString {
-        ColoredString::from(self).hidden()
-    }
+        // the precision truncates the text to as many columns, before it is
+        // styled
+        let truncated;
+        let this = match f.precision() {
+            Some(precision) if self.display_width_with(policy) > precision => {
+                truncated = self.split_at_visible_with(precision, policy).0;
+                &truncated
+            },
+            _ => self,
+        };
 
-    #[inline]
-    fn strikethrough(self) -> ColoredString {
-        ColoredString::from(self).strikethrough()
+        // the padding is written outside of the style, so that the fill
+        // characters are never styled
+        let pad = f.width().map_or(0, |width| width.saturating_sub(this.display_width_with(policy)));
+        let (before, after) = match f.align() {
+            Some(fmt::Alignment::Right) => (pad, 0),
+            Some(fmt::Alignment::Center) => (pad / 2, pad - pad / 2),
+            _ => (0, pad),
+        };
+        let fill = f.fill();
+        for _ in 0..before {
+            fmt::Write::write_char(f, fill)?;
+        }
+        backend.render(this, colorize, f)?;
+        for _ in 0..after {
+            fmt::Write::write_char(f, fill)?;
+        }
+        Ok(())
     }
-}
 
-impl fmt::Display for ColoredString {
-    #[inline]
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if !self.has_colors() || self.is_plain() {
-            return <String as fmt::Display>::fmt(&self.input, f);
+    /// Write the text, styled only if `colorize`, without any padding
+    pub(crate) fn write_styled_text(&self, f: &mut dyn fmt::Write, colorize: bool) -> fmt::Result {
+        let style = self.compute_style_with(colorize);
+        if style.is_empty() {
+            return f.write_str(&self.input);
         }
 
         // XXX: see tests. Useful when nesting colored strings
-        let escaped_input = self.escape_inner_reset_sequences();
+        let escaped_input = self.escape_inner_reset_sequences_with(colorize);
 
-        f.write_str(&self.compute_style())?;
-        escaped_input.fmt(f)?;
-        f.write_str("\x1B[0m")?;
-        Ok(())
+        f.write_str(&style)?;
+        f.write_str(&escaped_input)?;
+        f.write_str(RESET)
     }
 }
 
@@ -824,6 +2079,18 @@ mod tests {
         );
     }
 
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn padding_is_unstyled() {
+        let cstr = "ab".on_red();
+        assert_eq!(format!("{cstr:5}"), format!("{cstr}   "));
+        assert_eq!(format!("{cstr:>5}"), format!("   {cstr}"));
+        assert_eq!(format!("{cstr:-^6}"), format!("--{cstr}--"));
+        assert_eq!(format!("{:4.2}", "日本".red()), format!("{}  ", "日".red()));
+        assert_eq!(format!("{:.3}", "日本".red()), "日".red().to_string());
+        assert_eq!(format!("{:3}", "abcd".red()), "abcd".red().to_string());
+    }
+
     #[test]
     fn it_works() {
         let toto = "toto";
@@ -866,12 +2133,19 @@ mod tests {
         // assert!(false)
     }
 
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn alternate_display_shows_escape_codes() {
+        assert_eq!(r"\x1B[31mred\x1B[0m", format!("{:#}", "red".red()));
+        assert_eq!("plain", format!("{:#}", "plain".normal()));
+    }
+
     #[test]
     fn compute_style_empty_string() {
         assert_eq!("", "".clear().compute_style());
     }
 
-    #[cfg_attr(feature = "no-color", ignore)]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
     #[test]
     fn compute_style_simple_fg_blue() {
         let blue = "\x1B[34m";
@@ -879,7 +2153,7 @@ mod tests {
         assert_eq!(blue, "".blue().compute_style());
     }
 
-    #[cfg_attr(feature = "no-color", ignore)]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
     #[test]
     fn compute_style_simple_bg_blue() {
         let on_blue = "\x1B[44m";
@@ -887,7 +2161,7 @@ mod tests {
         assert_eq!(on_blue, "".on_blue().compute_style());
     }
 
-    #[cfg_attr(feature = "no-color", ignore)]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
     #[test]
     fn compute_style_blue_on_blue() {
         let blue_on_blue = "\x1B[44;34m";
@@ -895,7 +2169,7 @@ mod tests {
         assert_eq!(blue_on_blue, "".blue().on_blue().compute_style());
     }
 
-    #[cfg_attr(feature = "no-color", ignore)]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
     #[test]
     fn compute_style_simple_fg_bright_blue() {
         let blue = "\x1B[94m";
@@ -903,7 +2177,7 @@ mod tests {
         assert_eq!(blue, "".bright_blue().compute_style());
     }
 
-    #[cfg_attr(feature = "no-color", ignore)]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
     #[test]
     fn compute_style_simple_bg_bright_blue() {
         let on_blue = "\x1B[104m";
@@ -911,7 +2185,7 @@ mod tests {
         assert_eq!(on_blue, "".on_bright_blue().compute_style());
     }
 
-    #[cfg_attr(feature = "no-color", ignore)]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
     #[test]
     fn compute_style_bright_blue_on_bright_blue() {
         let blue_on_blue = "\x1B[104;94m";
@@ -922,7 +2196,7 @@ mod tests {
         );
     }
 
-    #[cfg_attr(feature = "no-color", ignore)]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
     #[test]
     fn compute_style_simple_bold() {
         let bold = "\x1B[1m";
@@ -930,7 +2204,7 @@ mod tests {
         assert_eq!(bold, "".bold().compute_style());
     }
 
-    #[cfg_attr(feature = "no-color", ignore)]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
     #[test]
     fn compute_style_blue_bold() {
         let blue_bold = "\x1B[1;34m";
@@ -938,7 +2212,7 @@ mod tests {
         assert_eq!(blue_bold, "".blue().bold().compute_style());
     }
 
-    #[cfg_attr(feature = "no-color", ignore)]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
     #[test]
     fn compute_style_blue_bold_on_blue() {
         let blue_bold_on_blue = "\x1B[1;44;34m";
@@ -972,7 +2246,7 @@ mod tests {
         assert_eq!(expected, output);
     }
 
-    #[cfg_attr(feature = "no-color", ignore)]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
     #[test]
     fn escape_reset_sequence_spec_should_replace_inner_reset_sequence_with_current_style() {
         let input = format!("start {} end", String::from("hello world !").red());
@@ -986,7 +2260,7 @@ mod tests {
         assert_eq!(expected, output);
     }
 
-    #[cfg_attr(feature = "no-color", ignore)]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
     #[test]
     fn escape_reset_sequence_spec_should_replace_multiple_inner_reset_sequences_with_current_style() {
         let italic_str = String::from("yo").italic();
@@ -1015,6 +2289,222 @@ mod tests {
         assert_eq!("blue".blue(), "blue".color("blue"));
     }
 
+    #[test]
+    fn color_fn_hex() {
+        assert_eq!("red".truecolor(255, 0, 0), "red".color("#ff0000"));
+    }
+
+    #[test]
+    fn color_opt_fn() {
+        assert_eq!("blue".blue(), "blue".color_opt(Some(Color::Blue)));
+        assert_eq!("blue".blue(), "blue".blue().color_opt(None::<Color>));
+        assert_eq!(
+            "blue".on_blue(),
+            "blue".on_color_opt(Some("blue")).on_color_opt(None::<Color>)
+        );
+    }
+
+    #[test]
+    fn semantic_fn() {
+        assert_eq!("ok".green(), "ok".ok());
+        assert_eq!("err".red().bold(), "err".bold().err());
+        assert_eq!("warn".yellow(), "warn".warn());
+    }
+
+    #[test]
+    fn conceal_reveal_fn() {
+        assert_eq!("secret".hidden(), "secret".conceal());
+        assert_eq!("secret".red(), "secret".red().conceal().reveal());
+        assert_eq!("secret".clear(), "secret".reveal());
+    }
+
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn join_with_resets_fn() {
+        let joined = join_with_resets(
+            [
+                "a".red().to_string(),
+                String::from("\x1B[4mb"),
+                String::from("c"),
+            ],
+            "|",
+        );
+        assert_eq!(joined, "\x1B[31ma\x1B[0m|\x1B[4mb\x1B[0m|c");
+    }
+
+    #[test]
+    fn prefix_lines_fn() {
+        let gutter = "> ".green();
+        let text = "a\n\nb\n".bold().prefix_lines(&gutter);
+        assert_eq!(text.to_plain(), "> a\n> \n> b\n");
+        assert_eq!(text.segments().len(), 8);
+        assert_eq!(text.segments()[1], "a".bold());
+        assert!(text.segments()[2].is_plain());
+        assert_eq!("".red().prefix_lines(&gutter).segments(), &[gutter]);
+    }
+
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn prefix_lines_embedded_fn() {
+        let text = "a\x1B[1mb\nc".red().prefix_lines(&"| ".blue());
+        assert_eq!(text.to_plain(), "| a\x1B[1mb\x1B[0m\n| c");
+        assert_eq!(text.segments()[1], "a\x1B[1mb\x1B[0m".red());
+        assert_eq!(text.segments()[4], "c".red().bold());
+    }
+
+    #[test]
+    fn bg_gradient_fn() {
+        let text = "ab\nc\r\n日".red().bg_gradient(Color::Black, Color::White);
+        assert_eq!(text.to_plain(), "ab\nc \r\n日");
+        assert_eq!(text.segments()[0], "ab".red().on_truecolor(0, 0, 0));
+        assert!(text.segments()[1].is_plain());
+        assert_eq!(text.segments()[2], "c ".red().on_truecolor(128, 128, 128));
+        assert_eq!(text.segments()[3], ColoredString::from("\r\n"));
+        assert_eq!(text.segments()[4], "日".red().on_truecolor(255, 255, 255));
+        let text = "a\nbc\n".red().bg_gradient(Color::Black, Color::White);
+        assert_eq!(text.to_plain(), "a \nbc\n");
+        assert_eq!(text.segments().len(), 4);
+        assert_eq!(text.segments()[2], "bc".red().on_truecolor(255, 255, 255));
+        assert_eq!("x".normal().bg_gradient(Color::Red, Color::Blue).segments(), &["x".on_truecolor(255, 0, 0)]);
+        assert!("".normal().bg_gradient(Color::Red, Color::Blue).segments().is_empty());
+    }
+
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn gradient_fn() {
+        let cstr = format!("a{}b", "X".bold()).normal();
+        let text = cstr.gradient(&Gradient::new(&[(0.0, Color::Black), (1.0, Color::White)]));
+        assert_eq!(text.to_plain(), "aXb");
+        assert_eq!(text.segments()[0], "a".truecolor(0, 0, 0));
+        assert_eq!(text.segments()[1], "X".bold().truecolor(128, 128, 128));
+        assert_eq!(text.segments()[2], "b".truecolor(255, 255, 255));
+        let hidden = "ab".hidden().gradient(&Gradient::RAINBOW);
+        assert_eq!(&*hidden.segments()[0], "a");
+        assert!(hidden.segments()[0].style().contains(Styles::Hidden));
+    }
+
+    #[test]
+    fn if_supports_color_fn() {
+        let always = "x".if_supports_color(ColorLevel::None, Colorize::red);
+        assert_eq!(always, Some("x".red()));
+        let level = control::color_level();
+        let res = "x".if_supports_color(ColorLevel::TrueColor, Colorize::red);
+        assert_eq!(res.is_some(), level == ColorLevel::TrueColor);
+    }
+
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn colorizer_fn() {
+        let cstr = "x".red();
+        let always = Colorizer::new(ColorChoice::Always);
+        assert_eq!(always.paint(&cstr).to_string(), "\x1B[31mx\x1B[0m");
+        assert_eq!(format!("{:#}", always.paint(&cstr)), r"\x1B[31mx\x1B[0m");
+        let never = Colorizer::new(ColorChoice::Never);
+        assert_eq!(format!("{:>3}", never.paint(&cstr)), "  x");
+        assert_eq!("always".parse::<ColorChoice>().unwrap(), ColorChoice::Always);
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+    }
+
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn colorizer_hooks() {
+        let colorizer = Colorizer::new(ColorChoice::Always)
+            .with_hook(|s| s.style_mut().insert(Styles::Dimmed))
+            .with_hook(|s| *s = std::mem::take(s).blue());
+        let cstr = "x".red();
+        assert_eq!(colorizer.paint(&cstr).to_string(), "x".blue().dimmed().to_string());
+        assert_eq!(cstr, "x".red());
+    }
+
+    #[test]
+    fn to_ansi_string_fn() {
+        for cstr in ["a".normal(), "a".red(), "a\x1B[0mb".on_blue().bold()] {
+            assert_eq!(cstr.to_ansi_string(), cstr.to_string());
+            assert_eq!(String::from(cstr.clone()), cstr.to_string());
+        }
+    }
+
+    #[test]
+    fn bytes_styled_fns() {
+        for cstr in ["a".normal(), "é".red(), "a\x1B[0mb".on_blue().bold()] {
+            assert_eq!(cstr.as_bytes_styled(), cstr.to_string().into_bytes());
+            let mut buf = Vec::new();
+            cstr.write_styled(&mut buf).unwrap();
+            assert_eq!(buf, cstr.to_string().into_bytes());
+        }
+
+        let mut short = [0; 4];
+        assert!("abcdef".red().write_styled(&mut &mut short[..]).is_err());
+    }
+
+    #[test]
+    fn metrics_fns() {
+        for cstr in ["".normal(), "a\x1B[0mb".on_blue().bold(), "e\u{301}🦀".red()] {
+            assert_eq!(cstr.byte_len_raw(), cstr.to_string().len());
+            assert_eq!(cstr.byte_len_text(), cstr.len());
+            assert_eq!(cstr.char_count(), cstr.chars().count());
+        }
+        assert_eq!("e\u{301}🦀".red().display_width(), 3);
+    }
+
+    #[test]
+    fn compact_resets_fn() {
+        let cases = [
+            ("", ""),
+            ("plain", "plain"),
+            ("\x1B[0m\x1B[0m", "\x1B[0m"),
+            ("a\x1B[31m\x1B[1m\x1B[mb", "a\x1B[mb"),
+            ("\x1B[31ma\x1B[0m\x1B[0m\x1B[1mb\x1B[0m", "\x1B[31ma\x1B[0m\x1B[1mb\x1B[0m"),
+            ("\x1B[31m\x1B[2K\x1B[0m", "\x1B[31m\x1B[2K\x1B[0m"),
+            ("a\x1B[0m\x1B[3", "a\x1B[0m\x1B[3"),
+            ("\x1B[31m\x1B]8;;u\x1B\\\x1B[0m", "\x1B[31m\x1B]8;;u\x1B\\\x1B[0m"),
+        ];
+        for (input, expected) in cases {
+            let mut buf = String::from(input);
+            compact_resets(&mut buf);
+            assert_eq!(buf, expected);
+        }
+    }
+
+    #[test]
+    fn untrusted_fn() {
+        let cases = [
+            ("plain\ttext\n", "plain\ttext\n"),
+            ("\x1B[31mred\x1B[0m", "\x1B[31mred\x1B[0m"),
+            ("\x1B[38:2::1:2:3mx", "\x1B[38:2::1:2:3mx\x1B[0m"),
+            ("a\x1B[2Jb\x1B[?25lc\x1B[1;1Hd", "abcd"),
+            ("a\x1B]8;;http://x\x1B\\b\x1B]0;t\x07c", "abc"),
+            ("a\x1BPq#0\x1B\\b\x1B7c\x1Bcd", "abcd"),
+            ("a\x08\r\x07\u{9B}2Jb", "a2Jb"),
+            ("a\x1B[31", "a"),
+            ("a\x1B", "a"),
+        ];
+        for (input, expected) in cases {
+            let cstr = ColoredString::untrusted(input);
+            assert!(cstr.is_plain());
+            assert_eq!(&*cstr, expected);
+        }
+    }
+
+    #[test]
+    fn raw_escape_fns() {
+        assert_eq!(bg_escape(Color::BrightBlue), "\x1B[104m");
+        let mut s = String::new();
+        write_bg(&mut s, Color::Black).unwrap();
+        write_fg(&mut s, Color::TrueColor { r: 0, g: 0, b: 0 }).unwrap();
+        assert_eq!(s, "\x1B[40m\x1B[38;2;0;0;0m");
+    }
+
+    #[test]
+    fn smart_pointer_colorize() {
+        let boxed: Box<str> = Box::from("boxed");
+        assert_eq!("boxed".red(), boxed.red());
+        let rc: Rc<str> = Rc::from("rc");
+        assert_eq!("rc".blue().bold(), rc.blue().bold());
+        let arc: Arc<str> = Arc::from("arc");
+        assert_eq!("arc".on_green(), arc.on_green());
+    }
+
     #[test]
     fn on_color_fn() {
         assert_eq!("blue".on_blue(), "blue".on_color("blue"));
@@ -1030,6 +2520,67 @@ mod tests {
         assert_eq!("blue".on_bright_blue(), "blue".on_color("bright blue"));
     }
 
+    #[test]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    fn split_at_visible_fn() {
+        let (left, right) = "ab".bold().split_at_visible(5);
+        assert_eq!((&*left, &*right), ("ab", ""));
+        let (left, right) = "日本".normal().split_at_visible(1);
+        assert_eq!((&*left, &*right), ("", "日本"));
+        let (left, right) = "e\u{301}x".normal().split_at_visible(1);
+        assert_eq!((&*left, &*right), ("e\u{301}", "x"));
+        let (left, right) = "\x1B[1ma\x1B[0mb".normal().split_at_visible(1);
+        assert_eq!((&*left, &*right), ("\x1B[1ma\x1B[0m", "b"));
+        assert_eq!(left.style(), right.style());
+        let link = "\x1B]8;;https://example.com\x1B\\docs\x1B]8;;\x1B\\";
+        let (left, right) = link.normal().split_at_visible(2);
+        assert_eq!((&*left, &*right), ("\x1B]8;;https://example.com\x1B\\do", "cs\x1B]8;;\x1B\\"));
+    }
+
+    #[test]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    fn ansi256_fns() {
+        assert_eq!("warn".ansi256(208), "warn".color(Color::Ansi256(208)));
+        assert_eq!("warn".on_ansi256(208), "warn".on_color(Color::Ansi256(208)));
+        assert_eq!(
+            "warn".ansi256(208).on_ansi256(16).compute_style(),
+            "\x1B[48;5;16;38;5;208m"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    fn buffer_reuse() {
+        let buf = String::from("previous frame, longer than the next one");
+        let (ptr, capacity) = (buf.as_ptr(), buf.capacity());
+        let line = ColoredString::from_buffer(buf, "next").red().bold();
+        assert_eq!(&*line, "next");
+        assert_eq!(line.fgcolor(), Some(Color::Red));
+        let buf = line.into_buffer();
+        assert_eq!(buf, "next");
+        assert_eq!((ptr, capacity), (buf.as_ptr(), buf.capacity()));
+    }
+
+    #[test]
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    fn default_color_fns() {
+        assert_eq!("x".default_color(), "x".color(Color::Default));
+        assert_eq!("x".on_default(), "x".on_color("default"));
+        assert_eq!("x".red().on_default().compute_style(), "\x1B[49;31m");
+        assert_eq!(Color::Default.to_fg_str(), "39");
+        assert_eq!(Color::Default.to_bg_str(), "49");
+    }
+
+    #[cfg(feature = "strip-styles")]
+    #[test]
+    fn strip_styles_is_plain() {
+        let mut cstring = "toto".red().on_blue().bold().truecolor(1, 2, 3);
+        cstring.style_mut().insert(Styles::Italic);
+        assert!(cstring.is_plain());
+        assert_eq!("toto", cstring.to_string());
+    }
+
+    #[cfg_attr(feature = "strip-styles", ignore)]
     #[test]
     fn exposing_tests() {
         let cstring = "".red();
@@ -0,0 +1,94 @@
+//! Color names in other languages than English, e.g. `rot` or `rouge`, to
+//! accept colors written by users in their own language, see
+//! [`Colorizer::set_color_names`](crate::Colorizer::set_color_names).
+//!
+//! ```rust
+//! # use colored::{*, locale};
+//! let mut colorizer = Colorizer::new(ColorChoice::Auto);
+//! colorizer.set_color_names(locale::names("de").unwrap());
+//! assert_eq!(colorizer.parse_color("Hellblau"), Ok(Color::BrightBlue));
+//! assert_eq!(colorizer.parse_color("blue"), Ok(Color::Blue));
+//! ```
+//!
+//! # Notes
+//! > Only available with the `locale-names` feature.
+
+use std::collections::HashMap;
+
+use crate::Color;
+
+/// The names of the 8 standard colors in each language, in the order of
+/// [`Color::NAMED`], and how the bright variants are named
+const LANGUAGES: [(&str, [&str; 8], Bright); 4] = [
+    ("de", ["schwarz", "rot", "grün", "gelb", "blau", "magenta", "cyan", "weiß"], Bright::Prefix("hell")),
+    ("es", ["negro", "rojo", "verde", "amarillo", "azul", "magenta", "cian", "blanco"], Bright::Suffix(" claro")),
+    ("fr", ["noir", "rouge", "vert", "jaune", "bleu", "magenta", "cyan", "blanc"], Bright::Suffix(" clair")),
+    ("it", ["nero", "rosso", "verde", "giallo", "blu", "magenta", "ciano", "bianco"], Bright::Suffix(" chiaro")),
+];
+
+/// Common synonyms in each language
+const ALIASES: [(&str, &str, Color); 10] = [
+    ("de", "weiss", Color::White),
+    ("de", "lila", Color::Magenta),
+    ("de", "grau", Color::BrightBlack),
+    ("es", "morado", Color::Magenta),
+    ("es", "gris", Color::BrightBlack),
+    ("fr", "violet", Color::Magenta),
+    ("fr", "gris", Color::BrightBlack),
+    ("it", "viola", Color::Magenta),
+    ("it", "grigio", Color::BrightBlack),
+    ("it", "azzurro", Color::BrightBlue),
+];
+
+/// How the bright variant of a color name is formed
+#[derive(Clone, Copy)]
+enum Bright {
+    /// A word prepended to the name, e.g. `hellrot`
+    Prefix(&'static str),
+    /// A word appended to the name, e.g. `rouge clair`
+    Suffix(&'static str),
+}
+
+/// The color names of `language`, an ISO 639-1 code among `de`, `es`, `fr`
+/// and `it`, to be passed to
+/// [`Colorizer::set_color_names`](crate::Colorizer::set_color_names)
+///
+/// ```rust
+/// # use colored::{*, locale};
+/// let french = locale::names("fr").unwrap();
+/// assert_eq!(french["rouge clair"], Color::BrightRed);
+/// assert!(locale::names("xx").is_none());
+/// ```
+#[must_use]
+pub fn names(language: &str) -> Option<HashMap<String, Color>> {
+    let (_, words, bright) = LANGUAGES.iter().find(|(code, ..)| code.eq_ignore_ascii_case(language))?;
+    let mut names = HashMap::new();
+    for (index, word) in words.iter().enumerate() {
+        names.insert((*word).to_owned(), Color::NAMED[index]);
+        let bright_word = match bright {
+            Bright::Prefix(prefix) => format!("{prefix}{word}"),
+            Bright::Suffix(suffix) => format!("{word}{suffix}"),
+        };
+        names.insert(bright_word, Color::NAMED[index + 8]);
+    }
+    for (_, alias, color) in ALIASES.iter().filter(|(code, ..)| code.eq_ignore_ascii_case(language)) {
+        names.insert((*alias).to_owned(), *color);
+    }
+    Some(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_language() {
+        for (code, ..) in LANGUAGES {
+            let names = names(code).unwrap();
+            assert!(names.len() >= 16);
+            assert!(names.keys().all(|name| *name == name.to_lowercase()));
+        }
+        assert_eq!(names("DE").unwrap()["hellgrün"], Color::BrightGreen);
+        assert_eq!(names("es").unwrap()["gris"], Color::BrightBlack);
+    }
+}
@@ -0,0 +1,209 @@
+//! The RGB values assumed for the 256 indexed terminal colors
+
+use crate::{Color, ColoredString, ColoredText};
+
+/// The levels used by the 6x6x6 color cube of the 256 color palette
+const CUBE_LEVELS: [u8; 6] = [0x00, 0x5F, 0x87, 0xAF, 0xD7, 0xFF];
+
+/// The 16 standard colors as rendered by `xterm`
+const XTERM_ANSI: [[u8; 3]; 16] = [
+    [0x00, 0x00, 0x00],
+    [0xCD, 0x00, 0x00],
+    [0x00, 0xCD, 0x00],
+    [0xCD, 0xCD, 0x00],
+    [0x00, 0x00, 0xEE],
+    [0xCD, 0x00, 0xCD],
+    [0x00, 0xCD, 0xCD],
+    [0xE5, 0xE5, 0xE5],
+    [0x7F, 0x7F, 0x7F],
+    [0xFF, 0x00, 0x00],
+    [0x00, 0xFF, 0x00],
+    [0xFF, 0xFF, 0x00],
+    [0x5C, 0x5C, 0xFF],
+    [0xFF, 0x00, 0xFF],
+    [0x00, 0xFF, 0xFF],
+    [0xFF, 0xFF, 0xFF],
+];
+
+/// The RGB values of the 256 indexed colors, used when a truecolor has to be
+/// approximated by one of them.
+///
+/// Indices `0..16` are the standard and bright colors, which differ the most
+/// between terminal themes. The defaults are the ones used by `xterm`.
+///
+/// ```rust
+/// # use colored::Palette;
+/// let mut palette = Palette::default();
+/// palette.set(4, [0x26, 0x8B, 0xD2]);
+/// assert_eq!(palette.get(4), [0x26, 0x8B, 0xD2]);
+/// assert_eq!(palette.nearest_ansi(0x20, 0x80, 0xD0), 4);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Palette {
+    /// RGB values of every indexed color
+    colors: [[u8; 3]; 256],
+}
+
+impl Default for Palette {
+    #[inline]
+    fn default() -> Self {
+        Self::xterm()
+    }
+}
+
+impl Palette {
+    /// The palette used by `xterm`
+    #[must_use]
+    pub const fn xterm() -> Self {
+        let mut colors = [[0; 3]; 256];
+        let mut i = 0;
+        while i < 16 {
+            colors[i] = XTERM_ANSI[i];
+            i += 1;
+        }
+        while i < 232 {
+            let n = i - 16;
+            colors[i] = [CUBE_LEVELS[n / 36], CUBE_LEVELS[(n / 6) % 6], CUBE_LEVELS[n % 6]];
+            i += 1;
+        }
+        while i < 256 {
+            #[allow(clippy::cast_possible_truncation)]
+            let level = (8 + (i - 232) * 10) as u8;
+            colors[i] = [level, level, level];
+            i += 1;
+        }
+        Self { colors }
+    }
+
+    /// Create a palette which uses the given 16 standard and bright colors,
+    /// and the `xterm` values for the rest
+    #[must_use]
+    pub const fn with_ansi(ansi: [[u8; 3]; 16]) -> Self {
+        let mut palette = Self::xterm();
+        let mut i = 0;
+        while i < 16 {
+            palette.colors[i] = ansi[i];
+            i += 1;
+        }
+        palette
+    }
+
+    /// Get the RGB value of an indexed color
+    #[inline]
+    #[must_use]
+    pub const fn get(&self, index: u8) -> [u8; 3] {
+        self.colors[index as usize]
+    }
+
+    /// Set the RGB value of an indexed color
+    #[inline]
+    pub fn set(&mut self, index: u8, rgb: [u8; 3]) {
+        self.colors[usize::from(index)] = rgb;
+    }
+
+    /// Index of the standard or bright color (`0..16`) closest to `rgb`
+    #[inline]
+    #[must_use]
+    pub fn nearest_ansi(&self, r: u8, g: u8, b: u8) -> u8 {
+        self.nearest_in(0..16, [r, g, b])
+    }
+
+    /// Index of the indexed color (`0..=255`) closest to `rgb`
+    #[inline]
+    #[must_use]
+    pub fn nearest_256(&self, r: u8, g: u8, b: u8) -> u8 {
+        self.nearest_in(0..256, [r, g, b])
+    }
+
+    /// Index of the color of the cube or the gray ramp (`16..=255`) closest to
+    /// `rgb`, leaving out the 16 colors which terminal themes redefine
+    #[inline]
+    pub(crate) fn nearest_cube_or_gray(&self, r: u8, g: u8, b: u8) -> u8 {
+        self.nearest_in(16..256, [r, g, b])
+    }
+
+    /// Swatches of every color of the palette, e.g. for a `--list-colors`
+    /// command: the 16 standard and bright colors on two lines, the 6x6x6
+    /// color cube on six lines and the grays on the last one
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let preview = Palette::xterm().preview();
+    /// assert_eq!(preview.to_plain().lines().count(), 9);
+    /// print!("{preview}");
+    /// ```
+    #[must_use]
+    pub fn preview(&self) -> ColoredText {
+        let rows = [(0, 8, 4), (8, 16, 4), (16, 52, 2), (52, 88, 2), (88, 124, 2)];
+        let rows = rows
+            .into_iter()
+            .chain([(124, 160, 2), (160, 196, 2), (196, 232, 2), (232, 256, 3)]);
+
+        let mut text = ColoredText::new();
+        for (start, end, width) in rows {
+            for index in start..end {
+                let [r, g, b] = self.colors[index];
+                text.push(Color::TrueColor { r, g, b }.swatch(width));
+            }
+            text.push(ColoredString::from("\n"));
+        }
+        text
+    }
+
+    /// Index of the color in `range` with the smallest distance to `rgb`
+    fn nearest_in(&self, range: std::ops::Range<usize>, rgb: [u8; 3]) -> u8 {
+        let nearest = range
+            .min_by_key(|&i| distance(self.colors[i], rgb))
+            .unwrap_or_default();
+        u8::try_from(nearest).unwrap_or(u8::MAX)
+    }
+}
+
+/// Squared euclidean distance between two RGB values
+fn distance(a: [u8; 3], b: [u8; 3]) -> u32 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(&x, &y)| u32::from(x.abs_diff(y)).pow(2))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xterm_cube_and_grays() {
+        let palette = Palette::xterm();
+        assert_eq!(palette.get(16), [0x00, 0x00, 0x00]);
+        assert_eq!(palette.get(196), [0xFF, 0x00, 0x00]);
+        assert_eq!(palette.get(231), [0xFF, 0xFF, 0xFF]);
+        assert_eq!(palette.get(232), [0x08, 0x08, 0x08]);
+        assert_eq!(palette.get(255), [0xEE, 0xEE, 0xEE]);
+    }
+
+    #[test]
+    fn nearest() {
+        let palette = Palette::xterm();
+        assert_eq!(palette.nearest_ansi(0xF0, 0x10, 0x10), 9);
+        assert_eq!(palette.nearest_256(0xFF, 0x00, 0x00), 9);
+        assert_eq!(palette.nearest_256(0x5F, 0x87, 0xAF), 67);
+    }
+
+    #[test]
+    fn preview_rows() {
+        let preview = Palette::xterm().preview();
+        assert_eq!(preview.segments().len(), 256 + 9);
+        let widths: Vec<usize> = preview.to_plain().lines().map(str::len).collect();
+        assert_eq!(widths, [32, 32, 72, 72, 72, 72, 72, 72, 72]);
+        assert_eq!(preview.segments()[10], Color::TrueColor { r: 0xFF, g: 0, b: 0 }.swatch(4));
+    }
+
+    #[test]
+    fn custom_ansi() {
+        let mut ansi = XTERM_ANSI;
+        ansi[1] = [0x80, 0x80, 0x80];
+        let palette = Palette::with_ansi(ansi);
+        assert_eq!(palette.get(1), [0x80, 0x80, 0x80]);
+        assert_eq!(palette.nearest_ansi(0x81, 0x81, 0x81), 1);
+    }
+}
@@ -0,0 +1,128 @@
+//! Querying the terminal for its capabilities, see
+//! [`control::probe_truecolor`](crate::control::probe_truecolor)
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Read, Write},
+    mem::MaybeUninit,
+    os::unix::io::AsRawFd,
+    time::{Duration, Instant},
+};
+
+/// Sets a truecolor background, asks for the current SGR with DECRQSS, resets
+/// it and asks for the primary device attributes. Every terminal answers the
+/// latter, so its reply marks the end of the replies.
+const QUERY: &[u8] = b"\x1B[48;2;1;2;3m\x1BP$qm\x1B\\\x1B[0m\x1B[c";
+
+/// The terminal in non-canonical mode without echo, restored when dropped
+struct RawMode<'a> {
+    /// The terminal
+    tty:      &'a File,
+    /// The settings to restore
+    original: libc::termios,
+}
+
+impl<'a> RawMode<'a> {
+    /// Switch `tty` to non-canonical mode, reads waiting at most `timeout`
+    fn enable(tty: &'a File, timeout: Duration) -> io::Result<Self> {
+        let fd = tty.as_raw_fd();
+        let mut original = MaybeUninit::uninit();
+        if unsafe { libc::tcgetattr(fd, original.as_mut_ptr()) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let original = unsafe { original.assume_init() };
+
+        let mut raw = original;
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+        raw.c_cc[libc::VMIN] = 0;
+        // in tenths of a second
+        let tenths = (timeout.as_millis() / 100).clamp(1, 255);
+        raw.c_cc[libc::VTIME] = libc::cc_t::try_from(tenths).unwrap_or(libc::cc_t::MAX);
+        if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self { tty, original })
+    }
+}
+
+impl Drop for RawMode<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(self.tty.as_raw_fd(), libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+/// Ask the controlling terminal whether it keeps truecolors, waiting at most
+/// `timeout` for its replies
+pub(crate) fn query_truecolor(timeout: Duration) -> io::Result<bool> {
+    let tty = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+    let _raw = RawMode::enable(&tty, timeout)?;
+
+    let mut writer = &tty;
+    writer.write_all(QUERY)?;
+    writer.flush()?;
+
+    let deadline = Instant::now() + timeout;
+    let mut reply = Vec::new();
+    let mut chunk = [0; 64];
+    while !has_device_attributes(&reply) && Instant::now() < deadline {
+        let read = (&tty).read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+        reply.extend_from_slice(&chunk[..read]);
+    }
+
+    Ok(confirms_truecolor(&reply))
+}
+
+/// Whether `reply` contains the reply to the primary device attributes,
+/// `CSI ? ... c`
+fn has_device_attributes(reply: &[u8]) -> bool {
+    reply
+        .windows(3)
+        .position(|w| w == b"\x1B[?")
+        .map_or(false, |start| reply[start..].contains(&b'c'))
+}
+
+/// Whether `reply` contains a DECRQSS reply reporting the truecolor
+/// background of [`QUERY`], with semicolons or colons
+fn confirms_truecolor(reply: &[u8]) -> bool {
+    let Some(start) = reply.windows(5).position(|w| w == b"\x1BP1$r") else {
+        return false;
+    };
+    let sgr = &reply[start + 5..];
+    let end = sgr.iter().position(|&b| b == 0x1B || b == 0x07).unwrap_or(sgr.len());
+    let sgr = String::from_utf8_lossy(&sgr[..end]).replace("::", ":").replace(':', ";");
+    sgr.trim_end_matches('m')
+        .split(';')
+        .collect::<Vec<_>>()
+        .windows(5)
+        .any(|w| w == ["48", "2", "1", "2", "3"])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn device_attributes() {
+        assert!(has_device_attributes(b"\x1BP1$r0m\x1B\\\x1B[?62;22c"));
+        assert!(!has_device_attributes(b"\x1B[?62;2"));
+        assert!(!has_device_attributes(b""));
+    }
+
+    #[test]
+    fn truecolor_replies() {
+        assert!(confirms_truecolor(b"\x1BP1$r0;48;2;1;2;3m\x1B\\\x1B[?62c"));
+        assert!(confirms_truecolor(b"\x1BP1$r48:2::1:2:3m\x1B\\"));
+        assert!(confirms_truecolor(b"\x1BP1$r48:2:1:2:3m\x07"));
+        // approximated to the 256 color palette
+        assert!(!confirms_truecolor(b"\x1BP1$r48;5;16m\x1B\\"));
+        // DECRQSS is not supported
+        assert!(!confirms_truecolor(b"\x1BP0$r\x1B\\\x1B[?1;2c"));
+        assert!(!confirms_truecolor(b"\x1B[?1;2c"));
+    }
+}
@@ -0,0 +1,113 @@
+//! Rendering for shell prompts, whose line editors must be told which bytes
+//! take no room on screen
+
+use crate::{
+    ansi::{AnsiEvent, SgrParser},
+    ColoredString,
+    ColoredText,
+};
+
+/// The shell a prompt is rendered for, see [`ColoredString::render_for`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PromptDialect {
+    /// `bash` and other readline prompts, wrapping escapes with `\[` and `\]`
+    Bash,
+    /// `zsh` prompts, wrapping escapes with `%{` and `%}`
+    Zsh,
+}
+
+impl PromptDialect {
+    /// The markers around zero-width text
+    const fn markers(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Bash => ("\\[", "\\]"),
+            Self::Zsh => ("%{", "%}"),
+        }
+    }
+
+    /// Wrap every escape sequence of `rendered` with the zero-width markers
+    fn wrap_escapes(self, rendered: &str) -> String {
+        let (open, close) = self.markers();
+        let mut res = String::with_capacity(rendered.len());
+        for event in SgrParser::new(rendered) {
+            match event {
+                AnsiEvent::Text(text) => res.push_str(text),
+                AnsiEvent::Sgr(params) => {
+                    res.push_str(open);
+                    res.push_str("\x1B[");
+                    res.push_str(params);
+                    res.push('m');
+                    res.push_str(close);
+                },
+                AnsiEvent::Escape(seq) => {
+                    res.push_str(open);
+                    res.push_str(seq);
+                    res.push_str(close);
+                },
+            }
+        }
+        res
+    }
+}
+
+impl ColoredString {
+    /// Render the string for a shell prompt, wrapping every escape sequence
+    /// with the zero-width markers of `dialect` so the line editor computes
+    /// the width of the prompt correctly. The text itself is left as is.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// # control::set_override(true);
+    /// let ps1 = "λ".green().render_for(PromptDialect::Bash);
+    /// assert_eq!(ps1, "\\[\x1B[32m\\]λ\\[\x1B[0m\\]");
+    /// # control::unset_override();
+    /// ```
+    #[must_use]
+    pub fn render_for(&self, dialect: PromptDialect) -> String {
+        dialect.wrap_escapes(&self.to_ansi_string())
+    }
+}
+
+impl ColoredText {
+    /// Render the text for a shell prompt, see [`ColoredString::render_for`]
+    #[must_use]
+    pub fn render_for(&self, dialect: PromptDialect) -> String {
+        dialect.wrap_escapes(&self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Colorize;
+
+    #[test]
+    fn plain_is_unchanged() {
+        assert_eq!("% $ \\".normal().render_for(PromptDialect::Zsh), "% $ \\");
+    }
+
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn wraps_every_escape() {
+        let text = "user".bold().push_colored("@host".blue());
+        let expected = text
+            .to_string()
+            .replace("\x1B[1m", "%{\x1B[1m%}")
+            .replace("\x1B[34m", "%{\x1B[34m%}")
+            .replace("\x1B[0m", "%{\x1B[0m%}");
+        assert_eq!(text.render_for(PromptDialect::Zsh), expected);
+
+        let nested = format!("a{}b", "x".red()).blue().render_for(PromptDialect::Bash);
+        assert_eq!(nested.matches("\\[\x1B[").count(), 5);
+        assert_eq!(nested.matches("m\\]").count(), 5);
+    }
+
+    #[test]
+    fn wraps_osc_whole() {
+        let link = "\x1B]8;;https://example.com\x1B\\docs\x1B]8;;\x1B\\";
+        assert_eq!(
+            ColoredString::from(link).render_for(PromptDialect::Bash),
+            "\\[\x1B]8;;https://example.com\x1B\\\\]docs\\[\x1B]8;;\x1B\\\\]"
+        );
+    }
+}
@@ -0,0 +1,235 @@
+//! Backends rendering colored strings, e.g. to HTML instead of ANSI escape
+//! sequences.
+//!
+//! A [`Colorizer`](crate::Colorizer) renders through the backend given to
+//! [`Colorizer::with_backend`](crate::Colorizer::with_backend), and
+//! [`ColoredString::render_with`] renders a single string.
+//!
+//! ```rust
+//! # use colored::{render::Html, *};
+//! let html = Colorizer::new(ColorChoice::Always).with_backend(Html);
+//! assert_eq!(
+//!     html.paint(&"a < b".red().bold()).to_string(),
+//!     r#"<span style="color:#ff0000;font-weight:bold">a &lt; b</span>"#
+//! );
+//! ```
+
+use std::fmt::{self, Write};
+
+use crate::{
+    ansi::{AnsiEvent, SgrParser},
+    Color,
+    ColoredString,
+    ColoredText,
+    Styles,
+};
+
+/// Renders a [`ColoredString`] to some output format
+pub trait RenderBackend: fmt::Debug + Send + Sync {
+    /// Write `s` to `out`, with its colors and style only if `colorize`
+    ///
+    /// # Errors
+    /// Will produce an error if `out` does
+    fn render(&self, s: &ColoredString, colorize: bool, out: &mut dyn Write) -> fmt::Result;
+}
+
+/// ANSI escape sequences, the way [`ColoredString`] is displayed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Ansi;
+
+impl RenderBackend for Ansi {
+    #[inline]
+    fn render(&self, s: &ColoredString, colorize: bool, out: &mut dyn Write) -> fmt::Result {
+        s.write_styled_text(out, colorize)
+    }
+}
+
+/// The text only, without any escape sequence, see
+/// [`ColoredString::to_plain`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Plain;
+
+impl RenderBackend for Plain {
+    #[inline]
+    fn render(&self, s: &ColoredString, _colorize: bool, out: &mut dyn Write) -> fmt::Result {
+        write_visible(&s.to_plain(), out, false)
+    }
+}
+
+/// HTML, each styled string being a `<span>` with an inline `style`. The text
+/// is escaped, and the colors are given in hexadecimal, see
+/// [`Color::to_hex`](crate::Color::to_hex).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Html;
+
+impl RenderBackend for Html {
+    fn render(&self, s: &ColoredString, colorize: bool, out: &mut dyn Write) -> fmt::Result {
+        let css = if colorize { css(s) } else { String::new() };
+        if css.is_empty() {
+            return write_visible(&s.input, out, true);
+        }
+
+        write!(out, "<span style=\"{css}\">")?;
+        write_visible(&s.input, out, true)?;
+        out.write_str("</span>")
+    }
+}
+
+/// The inline CSS of the effective colors and style of `s`
+fn css(s: &ColoredString) -> String {
+    let (style, fgcolor, bgcolor) = s.effective_style();
+    let (fg, bg) = if style.contains(Styles::Reversed) {
+        (bgcolor, fgcolor)
+    } else {
+        (fgcolor, bgcolor)
+    };
+
+    let mut rules = Vec::new();
+    match fg {
+        Some(Color::Default) => rules.push("color:inherit".to_owned()),
+        Some(fg) => rules.push(format!("color:#{}", fg.to_hex())),
+        None => {},
+    }
+    match bg {
+        Some(Color::Default) => rules.push("background-color:inherit".to_owned()),
+        Some(bg) => rules.push(format!("background-color:#{}", bg.to_hex())),
+        None => {},
+    }
+    for (styles, rule) in [
+        (Styles::Bold, "font-weight:bold"),
+        (Styles::Dimmed, "opacity:0.5"),
+        (Styles::Italic, "font-style:italic"),
+        (Styles::Hidden, "visibility:hidden"),
+    ] {
+        if style.contains(styles) {
+            rules.push(rule.to_owned());
+        }
+    }
+
+    let decorations = [
+        (Styles::Underline, "underline"),
+        (Styles::Strikethrough, "line-through"),
+        (Styles::Blink, "blink"),
+    ]
+    .iter()
+    .filter(|(styles, _)| style.contains(*styles))
+    .map(|(_, decoration)| *decoration)
+    .collect::<Vec<_>>();
+    if !decorations.is_empty() {
+        rules.push(format!("text-decoration:{}", decorations.join(" ")));
+    }
+
+    rules.join(";")
+}
+
+/// Write `text` without its escape sequences, e.g. those of nested colored
+/// strings, escaping the HTML special characters if `html`
+fn write_visible(text: &str, out: &mut dyn Write, html: bool) -> fmt::Result {
+    for event in SgrParser::new(text) {
+        if let AnsiEvent::Text(text) = event {
+            write_escaped(text, out, html)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write `text`, escaping the HTML special characters if `html`
+fn write_escaped(text: &str, out: &mut dyn Write, html: bool) -> fmt::Result {
+    if !html {
+        return out.write_str(text);
+    }
+    for c in text.chars() {
+        match c {
+            '&' => out.write_str("&amp;")?,
+            '<' => out.write_str("&lt;")?,
+            '>' => out.write_str("&gt;")?,
+            '"' => out.write_str("&quot;")?,
+            '\'' => out.write_str("&#39;")?,
+            c => out.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+impl ColoredString {
+    /// Render the string with `backend`, colorized if the global
+    /// [`ShouldColorize`](crate::control::ShouldColorize) says so
+    ///
+    /// ```rust
+    /// # use colored::{render::Plain, *};
+    /// assert_eq!("plain".red().render_with(&Plain), "plain");
+    /// ```
+    #[must_use]
+    pub fn render_with(&self, backend: &dyn RenderBackend) -> String {
+        let mut res = String::new();
+        backend
+            .render(self, self.has_colors(), &mut res)
+            .expect("writing to a String cannot fail");
+        res
+    }
+}
+
+impl ColoredText {
+    /// Render every segment with `backend`, see
+    /// [`ColoredString::render_with`]
+    #[must_use]
+    pub fn render_with(&self, backend: &dyn RenderBackend) -> String {
+        self.segments()
+            .iter()
+            .map(|segment| segment.render_with(backend))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ColorChoice, Colorize, Colorizer};
+
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn html() {
+        let html = Colorizer::new(ColorChoice::Always).with_backend(Html);
+        let s = "\"x\"".white().on_blue().underline().strikethrough().reversed();
+        assert_eq!(
+            html.paint(&s).to_string(),
+            "<span style=\"color:#0000ff;background-color:#ffffff;\
+             text-decoration:underline line-through\">&quot;x&quot;</span>"
+        );
+        assert_eq!(html.paint(&"<b>".normal()).to_string(), "&lt;b&gt;");
+
+        let nested = format!("a{}b", "c".red()).blue();
+        assert_eq!(
+            html.paint(&nested).to_string(),
+            "<span style=\"color:#0000ff\">acb</span>"
+        );
+
+        let never = Colorizer::new(ColorChoice::Never).with_backend(Html);
+        assert_eq!(never.paint(&"a&b".red()).to_string(), "a&amp;b");
+    }
+
+    #[cfg_attr(feature = "strip-styles", ignore)]
+    #[test]
+    fn plain() {
+        let plain = Colorizer::new(ColorChoice::Always).with_backend(Plain);
+        assert_eq!(format!("[{:>4}]", plain.paint(&"ab".red())), "[  ab]");
+        assert_eq!(plain.paint(&"ab".hidden()).to_string(), "  ");
+
+        let text = "a".red().push_colored("b".blue());
+        assert_eq!(text.render_with(&Plain), "ab");
+
+        let link = ColoredString::from("\x1B]8;;https://example.com\x1B\\docs\x1B]8;;\x1B\\");
+        assert_eq!(link.render_with(&Plain), "docs");
+    }
+
+    #[test]
+    fn ansi_matches_display() {
+        let s = "x".red().on_green().italic();
+        assert_eq!(s.render_with(&Ansi), s.to_string());
+        let ansi = Colorizer::new(ColorChoice::Always).with_backend(Ansi);
+        assert_eq!(
+            ansi.paint(&s).to_string(),
+            Colorizer::new(ColorChoice::Always).paint(&s).to_string()
+        );
+    }
+}
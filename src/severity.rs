@@ -0,0 +1,78 @@
+//! Severity levels styled consistently, see
+//! [`Colorize::by_severity`](crate::Colorize::by_severity)
+
+use crate::{theme, StyleSpec};
+
+/// How serious something is, e.g. an alert or a metric over a threshold.
+///
+/// Each level is styled by `severity.<name>` in the [`theme`]: `info` is
+/// unstyled, `warn` yellow, `error` bold red and `critical` white on red by
+/// default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Nothing to act on
+    Info,
+    /// Worth a look
+    Warn,
+    /// Something failed
+    Error,
+    /// Something failed and needs attention now
+    Critical,
+}
+
+impl Severity {
+    /// Every level, from the least to the most severe
+    pub const ALL: [Self; 4] = [Self::Info, Self::Warn, Self::Error, Self::Critical];
+
+    /// Map a value from `0.0` to `1.0` to a level, each taking a quarter of
+    /// the range. Values out of the range are clamped, `NaN` is `Info`.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert_eq!(Severity::from_value(0.1), Severity::Info);
+    /// assert_eq!(Severity::from_value(0.6), Severity::Error);
+    /// assert_eq!(Severity::from_value(2.0), Severity::Critical);
+    /// ```
+    #[must_use]
+    pub fn from_value(value: f32) -> Self {
+        match value {
+            v if v >= 0.75 => Self::Critical,
+            v if v >= 0.5 => Self::Error,
+            v if v >= 0.25 => Self::Warn,
+            _ => Self::Info,
+        }
+    }
+
+    /// The lowercase name of the level
+    #[inline]
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+            Self::Critical => "critical",
+        }
+    }
+
+    /// The style of the level, `severity.<name>` in the [`theme`]
+    #[must_use]
+    pub fn spec(self) -> StyleSpec {
+        theme::get(&format!("severity.{}", self.name())).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_value() {
+        assert_eq!(Severity::from_value(-1.0), Severity::Info);
+        assert_eq!(Severity::from_value(f32::NAN), Severity::Info);
+        assert_eq!(Severity::from_value(0.25), Severity::Warn);
+        assert_eq!(Severity::from_value(0.5), Severity::Error);
+        assert_eq!(Severity::from_value(0.75), Severity::Critical);
+        assert!(Severity::ALL.windows(2).all(|w| w[0] < w[1]));
+    }
+}
@@ -1,3 +1,14 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+    ansi::{AnsiEvent, SgrParser},
+    Color,
+    ColoredString,
+    Colorize,
+    ParseColorError,
+    RESET,
+};
+
 const CLEARV: u8 = 0b0000_0000;
 const BOLD: u8 = 0b0000_0001;
 const UNDERLINE: u8 = 0b0000_0010;
@@ -8,7 +19,7 @@ const HIDDEN: u8 = 0b0010_0000;
 const DIMMED: u8 = 0b0100_0000;
 const STRIKETHROUGH: u8 = 0b1000_0000;
 
-static STYLES: [(u8, Styles); 8] = [
+const STYLES: [(u8, Styles); 8] = [
     (BOLD, Styles::Bold),
     (DIMMED, Styles::Dimmed),
     (UNDERLINE, Styles::Underline),
@@ -19,10 +30,22 @@ static STYLES: [(u8, Styles); 8] = [
     (STRIKETHROUGH, Styles::Strikethrough),
 ];
 
+/// The SGR code switching off each style, in ascending order. Bold and
+/// dimmed share the same one.
+const STYLES_OFF: [(u8, &str); 7] = [
+    (BOLD | DIMMED, "22"),
+    (ITALIC, "23"),
+    (UNDERLINE, "24"),
+    (BLINK, "25"),
+    (REVERSED, "27"),
+    (HIDDEN, "28"),
+    (STRIKETHROUGH, "29"),
+];
+
 pub(crate) static CLEAR: Style = Style(CLEARV);
 
 /// A combinatorial style such as bold, italics, dimmed, etc.
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct Style(u8);
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -40,6 +63,23 @@ pub enum Styles {
 }
 
 impl Styles {
+    /// The number of styles, not counting [`Styles::Clear`]
+    pub const COUNT: usize = STYLES.len();
+
+    /// Every style but [`Styles::Clear`], which switches the others off.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let mut style = Style::default();
+    /// Styles::all().for_each(|s| style.insert(s));
+    /// assert!(Styles::all().all(|s| style.contains(s)));
+    /// assert_eq!(Styles::all().count(), Styles::COUNT);
+    /// ```
+    #[inline]
+    pub fn all() -> impl ExactSizeIterator<Item = Self> + Clone {
+        STYLES.into_iter().map(|(_, style)| style)
+    }
+
     const fn to_str<'a>(self) -> &'a str {
         match self {
             Self::Clear => "", // unreachable, but we don't want to panic
@@ -54,7 +94,7 @@ impl Styles {
         }
     }
 
-    const fn to_u8(self) -> u8 {
+    pub(crate) const fn to_u8(self) -> u8 {
         match self {
             Self::Clear => CLEARV,
             Self::Bold => BOLD,
@@ -86,7 +126,42 @@ impl Styles {
     }
 }
 
+impl Default for Style {
+    /// The empty style, with no [`Styles`] switched on
+    #[inline]
+    fn default() -> Self {
+        CLEAR
+    }
+}
+
 impl Style {
+    /// Check if no [`Styles`] is switched on.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// assert!(Style::default().is_empty());
+    /// assert!("plain".normal().style().is_empty());
+    /// assert!(!"bold".bold().style().is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == CLEARV
+    }
+
+    /// Switch off every [`Styles`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let mut style = "".bold().underline().style();
+    /// style.clear();
+    /// assert_eq!(style, Style::default());
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0 = CLEARV;
+    }
+
     /// Check if the current style has one of [`Styles`](Styles) switched on.
     ///
     /// ```rust
@@ -103,6 +178,33 @@ impl Style {
         self.0 & s == s
     }
 
+    /// Switch on a [`Styles`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let mut style = Style::default();
+    /// style.insert(Styles::Bold);
+    /// assert!(style.contains(Styles::Bold));
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, style: Styles) {
+        self.0 |= style.to_u8();
+    }
+
+    /// Switch off a [`Styles`].
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let mut style = "".bold().italic().style();
+    /// style.remove(Styles::Bold);
+    /// assert!(!style.contains(Styles::Bold));
+    /// assert!(style.contains(Styles::Italic));
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, style: Styles) {
+        self.0 &= !style.to_u8();
+    }
+
     /// Convert style to string
     pub(crate) fn to_str(self) -> String {
         let styles = Styles::from_u8(self.0).unwrap_or_default();
@@ -113,9 +215,555 @@ impl Style {
             .join(";")
     }
 
+    /// The SGR parameters switching off exactly the styles which are on,
+    /// without the surrounding `\x1B[` and `m`, see
+    /// [`Style::write_off_codes`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let style = "".bold().dimmed().underline().style();
+    /// assert_eq!(style.to_off_str(), "22;24");
+    /// assert_eq!(Style::default().to_off_str(), "");
+    /// ```
+    #[must_use]
+    pub fn to_off_str(self) -> String {
+        let mut out = String::new();
+        self.write_off_codes(&mut out)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    /// Write the SGR parameters switching off exactly the styles which are on
+    /// into `w`, separated by `;`, e.g. to end a style without resetting the
+    /// colors.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let mut seq = String::from("\x1B[");
+    /// "".italic().reversed().style().write_off_codes(&mut seq).unwrap();
+    /// seq.push('m');
+    /// assert_eq!(seq, "\x1B[23;27m");
+    /// ```
+    ///
+    /// # Errors
+    /// Will produce an error if `w` fails to write
+    pub fn write_off_codes<W: fmt::Write>(self, w: &mut W) -> fmt::Result {
+        let codes = STYLES_OFF.iter().filter(|&&(mask, _)| self.0 & mask != 0);
+        for (i, &(_, code)) in codes.enumerate() {
+            if i > 0 {
+                w.write_char(';')?;
+            }
+            w.write_str(code)?;
+        }
+        Ok(())
+    }
+
     /// Combine styles
+    #[inline]
     pub(crate) fn add(&mut self, two: Styles) {
-        self.0 |= two.to_u8();
+        self.insert(two);
+    }
+
+    /// Combine with every style of `other`
+    pub(crate) const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Remove every style found in the `mask`
+    pub(crate) const fn without(self, mask: u8) -> Self {
+        Self(self.0 & !mask)
+    }
+}
+
+/// A mutable borrow of the [`Style`] of a [`ColoredString`], see
+/// [`ColoredString::style_mut`]. With the `strip-styles` feature, the changes
+/// are dropped with it like those of every other style setter.
+#[derive(Debug)]
+pub struct StyleMut<'a> {
+    /// The borrowed style
+    style: &'a mut Style,
+}
+
+impl<'a> StyleMut<'a> {
+    pub(crate) fn new(style: &'a mut Style) -> Self {
+        Self { style }
+    }
+}
+
+impl std::ops::Deref for StyleMut<'_> {
+    type Target = Style;
+
+    #[inline]
+    fn deref(&self) -> &Style {
+        self.style
+    }
+}
+
+impl std::ops::DerefMut for StyleMut<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Style {
+        self.style
+    }
+}
+
+impl Drop for StyleMut<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        if cfg!(feature = "strip-styles") {
+            *self.style = CLEAR;
+        }
+    }
+}
+
+/// The colors and style of a [`ColoredString`], without
+/// its text, which can be applied to any text.
+///
+/// ```rust
+/// # use colored::*;
+/// let spec = StyleSpec::new().fg(Color::Red).with(Styles::Bold);
+/// assert_eq!("error".with_spec(spec), "error".red().bold());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StyleSpec {
+    /// Foreground color
+    fgcolor: Option<Color>,
+    /// Background color
+    bgcolor: Option<Color>,
+    /// Style of the text
+    style:   Style,
+}
+
+impl Default for StyleSpec {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StyleSpec {
+    /// Create a [`StyleSpec`] without any color or style
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            fgcolor: None,
+            bgcolor: None,
+            style:   Style(CLEARV),
+        }
+    }
+
+    /// Set the foreground color
+    #[inline]
+    #[must_use]
+    pub const fn fg(mut self, color: Color) -> Self {
+        self.fgcolor = Some(color);
+        self
+    }
+
+    /// Set the background color
+    #[inline]
+    #[must_use]
+    pub const fn bg(mut self, color: Color) -> Self {
+        self.bgcolor = Some(color);
+        self
+    }
+
+    /// Add a [`Styles`]
+    #[inline]
+    #[must_use]
+    pub const fn with(mut self, style: Styles) -> Self {
+        self.style = Style(self.style.0 | style.to_u8());
+        self
+    }
+
+    /// Get the foreground color
+    #[inline]
+    #[must_use]
+    pub const fn fgcolor(&self) -> Option<Color> {
+        self.fgcolor
+    }
+
+    /// Get the background color
+    #[inline]
+    #[must_use]
+    pub const fn bgcolor(&self) -> Option<Color> {
+        self.bgcolor
+    }
+
+    /// Get the [`Style`]
+    #[inline]
+    #[must_use]
+    pub const fn style(&self) -> Style {
+        self.style
+    }
+}
+
+impl StyleSpec {
+    /// Parses a style written with the syntax of `git config`, e.g.
+    /// `bold red`, `ul brightblue`, `red blue` (foreground then background)
+    /// or `reverse #ff0088`.
+    ///
+    /// Colors are `normal` (no color), `default`, the 8 standard colors, the
+    /// same prefixed with `bright`, a number up to 255 or a hex color. The
+    /// attributes are `bold`, `dim`, `italic`, `ul`, `blink`, `reverse`,
+    /// `strike` and their negations prefixed with `no` or `no-`.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let spec = StyleSpec::from_git("green bold").unwrap();
+    /// assert_eq!(spec, StyleSpec::new().fg(Color::Green).with(Styles::Bold));
+    /// let spec = StyleSpec::from_git("ul brightblue red").unwrap();
+    /// assert_eq!(spec.bgcolor(), Some(Color::Red));
+    /// ```
+    ///
+    /// # Errors
+    /// Will produce a [`ParseColorError`] if a word is neither a color nor an
+    /// attribute, or if more than two colors are given
+    pub fn from_git(src: &str) -> Result<Self, ParseColorError> {
+        let invalid = |word: &str| ParseColorError::BadStyle(word.to_owned());
+
+        let mut spec = Self::new();
+        let mut colors = 0_u8;
+        for word in src.split_whitespace() {
+            let lower = word.to_lowercase();
+            if let Some(style) = git_attribute(&lower) {
+                spec = spec.with(style);
+            } else if let Some(style) = lower
+                .strip_prefix("no-")
+                .or_else(|| lower.strip_prefix("no"))
+                .and_then(git_attribute)
+            {
+                spec.style = spec.style.without(style.to_u8());
+            } else if lower == "reset" {
+                spec = Self::new();
+            } else {
+                let color = git_color(&lower).map_err(|err| match err {
+                    ParseColorError::UnknownName(_) => invalid(word),
+                    err => err,
+                })?;
+                match (colors, color) {
+                    (0, Some(color)) => spec.fgcolor = Some(color),
+                    (1, Some(color)) => spec.bgcolor = Some(color),
+                    (0 | 1, None) => {},
+                    _ => return Err(invalid(word)),
+                }
+                colors += 1;
+            }
+        }
+
+        Ok(spec)
+    }
+}
+
+impl StyleSpec {
+    /// Render every item with this style. The escape sequence switching the
+    /// style on is computed once for all of them, which is faster than
+    /// rendering a [`ColoredString`] per item when styling many lines.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let spec = StyleSpec::new().fg(Color::Yellow).with(Styles::Bold);
+    /// let lines: Vec<String> = spec.apply_all(["a", "b"]).collect();
+    /// assert_eq!(lines, ["a".yellow().bold().to_string(), "b".yellow().bold().to_string()]);
+    /// ```
+    #[inline]
+    pub fn apply_all<I>(self, items: I) -> AppliedAll<I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let template = "".with_spec(self);
+        AppliedAll {
+            iter:  items.into_iter(),
+            style: template.compute_style_with(template.has_colors()),
+        }
+    }
+}
+
+/// The [`Iterator`] returned by [`StyleSpec::apply_all`]
+#[derive(Clone, Debug)]
+pub struct AppliedAll<I> {
+    /// The items to render
+    iter:  I,
+    /// The escape sequence switching the style on, empty when not colorizing
+    style: Arc<str>,
+}
+
+impl<I> Iterator for AppliedAll<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let item = self.iter.next()?;
+        let item = item.as_ref();
+        if self.style.is_empty() {
+            return Some(item.to_owned());
+        }
+
+        let mut res = String::with_capacity(self.style.len() + item.len() + RESET.len());
+        res.push_str(&self.style);
+        // switch the style on again after the resets of nested strings
+        for (i, part) in item.split(RESET).enumerate() {
+            if i > 0 {
+                res.push_str(RESET);
+                res.push_str(&self.style);
+            }
+            res.push_str(part);
+        }
+        res.push_str(RESET);
+        Some(res)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl StyleSpec {
+    /// Parses the parameters of an SGR escape sequence, i.e. what is found
+    /// between `\x1B[` and `m`, such as `01;31` or `1;38;2;255;0;136`.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let spec = StyleSpec::from_sgr("01;31").unwrap();
+    /// assert_eq!(spec, StyleSpec::new().fg(Color::Red).with(Styles::Bold));
+    /// ```
+    ///
+    /// # Errors
+    /// Will produce a [`ParseColorError`] if a parameter is not a number, or is
+    /// not a known SGR parameter
+    pub fn from_sgr(params: &str) -> Result<Self, ParseColorError> {
+        Self::new().apply_sgr(params, Self::new())
+    }
+
+    /// Apply the parameters of an SGR escape sequence on top of this spec, a
+    /// reset going back to `base`. Truecolor and underline parameters may use
+    /// the colon syntax of ECMA-48, e.g. `38:2::r:g:b` or `4:3`.
+    pub(crate) fn apply_sgr(mut self, params: &str, base: Self) -> Result<Self, ParseColorError> {
+        let invalid = || ParseColorError::BadSgr(params.to_owned());
+
+        let mut groups = params.split(';').filter(|p| !p.is_empty());
+        while let Some(group) = groups.next() {
+            if group.contains(':') {
+                self.apply_sgr_colon(group).ok_or_else(invalid)?;
+                continue;
+            }
+
+            let param = group.parse::<u8>().map_err(|_| invalid())?;
+            match param {
+                0 => self = base,
+                1 => self = self.with(Styles::Bold),
+                2 => self = self.with(Styles::Dimmed),
+                3 => self = self.with(Styles::Italic),
+                4 => self = self.with(Styles::Underline),
+                5 => self = self.with(Styles::Blink),
+                7 => self = self.with(Styles::Reversed),
+                8 => self = self.with(Styles::Hidden),
+                9 => self = self.with(Styles::Strikethrough),
+                22 => self.style = self.style.without(BOLD | DIMMED),
+                23 => self.style = self.style.without(ITALIC),
+                24 => self.style = self.style.without(UNDERLINE),
+                25 => self.style = self.style.without(BLINK),
+                27 => self.style = self.style.without(REVERSED),
+                28 => self.style = self.style.without(HIDDEN),
+                29 => self.style = self.style.without(STRIKETHROUGH),
+                39 => self.fgcolor = Some(Color::Default),
+                49 => self.bgcolor = Some(Color::Default),
+                38 | 48 => {
+                    let mut next = || {
+                        groups
+                            .next()
+                            .and_then(|p| p.parse::<u8>().ok())
+                            .ok_or_else(invalid)
+                    };
+                    let color = match next()? {
+                        5 => Color::Ansi256(next()?),
+                        2 => Color::TrueColor {
+                            r: next()?,
+                            g: next()?,
+                            b: next()?,
+                        },
+                        _ => return Err(invalid()),
+                    };
+                    self.set_sgr_color(param, color);
+                },
+                30..=37 | 90..=97 => self.fgcolor = Color::from_fg_str(&param.to_string()),
+                40..=47 | 100..=107 => self.bgcolor = Color::from_bg_str(&param.to_string()),
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Apply a parameter with colon separated sub-parameters
+    fn apply_sgr_colon(&mut self, group: &str) -> Option<()> {
+        let subs: Vec<&str> = group.split(':').collect();
+        let num = |i: usize| subs.get(i).and_then(|p| p.parse::<u8>().ok());
+        match (num(0)?, num(1)?) {
+            (4, 0) => self.style = self.style.without(UNDERLINE),
+            (4, _) => self.style.add(Styles::Underline),
+            (param @ (38 | 48), 5) => self.set_sgr_color(param, Color::Ansi256(num(2)?)),
+            (param @ (38 | 48), 2) => {
+                // the color space identifier may be omitted: `38:2:r:g:b`
+                let rgb = if subs.len() >= 6 { 3 } else { 2 };
+                let color = Color::TrueColor {
+                    r: num(rgb)?,
+                    g: num(rgb + 1)?,
+                    b: num(rgb + 2)?,
+                };
+                self.set_sgr_color(param, color);
+            },
+            _ => return None,
+        }
+        Some(())
+    }
+
+    /// Set the foreground color for `38`, the background color otherwise
+    fn set_sgr_color(&mut self, param: u8, color: Color) {
+        if param == 38 {
+            self.fgcolor = Some(color);
+        } else {
+            self.bgcolor = Some(color);
+        }
+    }
+}
+
+impl From<&ColoredString> for StyleSpec {
+    #[inline]
+    fn from(s: &ColoredString) -> Self {
+        Self {
+            fgcolor: s.fgcolor,
+            bgcolor: s.bgcolor,
+            style:   s.style,
+        }
+    }
+}
+
+/// An [`Iterator`] over the characters of a [`ColoredString`] and the
+/// [`StyleSpec`] each of them is displayed with, see
+/// [`ColoredString::chars_styled`].
+///
+/// SGR sequences embedded in the text change the spec of the following
+/// characters, a reset going back to the spec of the string. Other escape
+/// sequences are skipped.
+#[derive(Clone, Debug)]
+pub struct StyledChars<'a> {
+    /// The text runs and escape sequences left to iterate over
+    events:  SgrParser<'a>,
+    /// The text run being iterated over
+    text:    &'a str,
+    /// The spec of the string itself
+    base:    StyleSpec,
+    /// The spec of the next character
+    current: StyleSpec,
+}
+
+impl<'a> StyledChars<'a> {
+    /// Iterate over `text`, displayed with `base`
+    pub(crate) const fn new(text: &'a str, base: StyleSpec) -> Self {
+        Self {
+            events: SgrParser::new(text),
+            text: "",
+            base,
+            current: base,
+        }
+    }
+}
+
+impl Iterator for StyledChars<'_> {
+    type Item = (char, StyleSpec);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(ch) = self.text.chars().next() {
+                self.text = &self.text[ch.len_utf8()..];
+                return Some((ch, self.current));
+            }
+
+            match self.events.next()? {
+                AnsiEvent::Text(text) => self.text = text,
+                AnsiEvent::Sgr(params) => {
+                    if let Ok(spec) = self.current.apply_sgr(params, self.base) {
+                        self.current = spec;
+                    }
+                },
+                AnsiEvent::Escape(_) => {},
+            }
+        }
+    }
+}
+
+/// Parses an attribute of `git config`
+fn git_attribute(word: &str) -> Option<Styles> {
+    match word {
+        "bold" => Some(Styles::Bold),
+        "dim" => Some(Styles::Dimmed),
+        "italic" => Some(Styles::Italic),
+        "ul" => Some(Styles::Underline),
+        "blink" => Some(Styles::Blink),
+        "reverse" => Some(Styles::Reversed),
+        "strike" => Some(Styles::Strikethrough),
+        _ => None,
+    }
+}
+
+/// Parses a color of `git config`, `Some(None)` meaning that no color is
+/// set
+fn git_color(word: &str) -> Result<Option<Color>, ParseColorError> {
+    if word == "normal" || word == "default" {
+        return Ok(None);
+    }
+    if word.starts_with('#') {
+        return Color::from_hex(word).map(Some);
+    }
+    if let Ok(index) = word.parse::<u8>() {
+        return Ok(Some(Color::Ansi256(index)));
+    }
+
+    let color = match word.strip_prefix("bright") {
+        Some(name) => format!("bright {name}"),
+        None => word.to_owned(),
+    };
+    color.parse().map(Some)
+}
+
+/// An object safe way to style a [`ColoredString`], so that styles can be
+/// stored as `Box<dyn ApplyStyle>` and applied dynamically, unlike
+/// [`Colorize`] which consumes `self`.
+///
+/// ```rust
+/// # use colored::*;
+/// let styles: Vec<Box<dyn ApplyStyle>> = vec![
+///     Box::new(StyleSpec::new().fg(Color::Red)),
+///     Box::new(|s: &mut ColoredString| s.push_str("!")),
+/// ];
+/// let mut cstr = ColoredString::from("error");
+/// for style in &styles {
+///     style.apply(&mut cstr);
+/// }
+/// assert_eq!(cstr, "error!".red());
+/// ```
+pub trait ApplyStyle {
+    /// Style the given string in place
+    fn apply(&self, s: &mut ColoredString);
+}
+
+impl ApplyStyle for StyleSpec {
+    #[inline]
+    fn apply(&self, s: &mut ColoredString) {
+        *s = std::mem::take(s).with_spec(*self);
+    }
+}
+
+impl<F: Fn(&mut ColoredString)> ApplyStyle for F {
+    #[inline]
+    fn apply(&self, s: &mut ColoredString) {
+        self(s);
     }
 }
 
@@ -123,6 +771,35 @@ impl Style {
 mod tests {
     use super::*;
 
+    mod off_codes {
+        use super::super::{Style, Styles};
+
+        #[test]
+        fn every_style() {
+            let mut style = Style::default();
+            Styles::all().for_each(|s| style.insert(s));
+            assert_eq!("22;23;24;25;27;28;29", style.to_off_str());
+        }
+
+        #[test]
+        fn one_style() {
+            for (style, code) in [
+                (Styles::Bold, "22"),
+                (Styles::Dimmed, "22"),
+                (Styles::Italic, "23"),
+                (Styles::Underline, "24"),
+                (Styles::Blink, "25"),
+                (Styles::Reversed, "27"),
+                (Styles::Hidden, "28"),
+                (Styles::Strikethrough, "29"),
+            ] {
+                let mut s = Style::default();
+                s.insert(style);
+                assert_eq!(code, s.to_off_str());
+            }
+        }
+    }
+
     mod u8_to_styles_invalid_is_none {
         use super::super::{Styles, CLEARV};
 
@@ -291,6 +968,148 @@ mod tests {
         }
     }
 
+    mod from_git {
+        use super::super::{StyleSpec, Styles};
+        use crate::{Color, ParseColorError};
+
+        #[test]
+        fn colors() {
+            assert_eq!(
+                StyleSpec::from_git("red blue").unwrap(),
+                StyleSpec::new().fg(Color::Red).bg(Color::Blue)
+            );
+            assert_eq!(
+                StyleSpec::from_git("normal brightyellow").unwrap(),
+                StyleSpec::new().bg(Color::BrightYellow)
+            );
+            assert_eq!(
+                StyleSpec::from_git("#ff0088").unwrap(),
+                StyleSpec::new().fg(Color::TrueColor { r: 255, g: 0, b: 136 })
+            );
+            assert_eq!(
+                StyleSpec::from_git("196").unwrap(),
+                StyleSpec::new().fg(Color::Ansi256(196))
+            );
+        }
+
+        #[test]
+        fn attributes() {
+            assert_eq!(
+                StyleSpec::from_git("bold ul Reverse").unwrap(),
+                StyleSpec::new()
+                    .with(Styles::Bold)
+                    .with(Styles::Underline)
+                    .with(Styles::Reversed)
+            );
+            assert_eq!(
+                StyleSpec::from_git("bold italic no-bold").unwrap(),
+                StyleSpec::new().with(Styles::Italic)
+            );
+            assert_eq!(
+                StyleSpec::from_git("strike nostrike").unwrap(),
+                StyleSpec::new()
+            );
+        }
+
+        #[test]
+        fn errors() {
+            assert_eq!(
+                StyleSpec::from_git("red blue green"),
+                Err(ParseColorError::BadStyle("green".to_owned()))
+            );
+            assert_eq!(
+                StyleSpec::from_git("bold Zorglub"),
+                Err(ParseColorError::BadStyle("Zorglub".to_owned()))
+            );
+            assert_eq!(StyleSpec::from_git("#ff00"), Err(ParseColorError::BadLength(4)));
+        }
+    }
+
+    mod styled_chars {
+        use super::super::{StyleSpec, StyledChars, Styles};
+        use crate::Color;
+
+        #[test]
+        fn embedded_sequences() {
+            let base = StyleSpec::new().fg(Color::Red);
+            let chars: Vec<_> =
+                StyledChars::new("a\x1B[1;34mb\x1B[0mc\x1B]8;;u\x1B\\d\x1B[2K", base).collect();
+            assert_eq!(
+                chars,
+                [
+                    ('a', base),
+                    ('b', base.fg(Color::Blue).with(Styles::Bold)),
+                    ('c', base),
+                    ('d', base),
+                ]
+            );
+        }
+
+        #[test]
+        fn unterminated_sequences() {
+            let base = StyleSpec::new();
+            assert_eq!(StyledChars::new("é\x1B[31", base).count(), 1);
+            assert_eq!(StyledChars::new("\x1B", base).count(), 0);
+        }
+    }
+
+    mod from_sgr {
+        use super::super::{StyleSpec, Styles};
+        use crate::{Color, ParseColorError};
+
+        #[test]
+        fn simple() {
+            assert_eq!(
+                StyleSpec::from_sgr("01;31").unwrap(),
+                StyleSpec::new().fg(Color::Red).with(Styles::Bold)
+            );
+            assert_eq!(
+                StyleSpec::from_sgr("4;104").unwrap(),
+                StyleSpec::new().bg(Color::BrightBlue).with(Styles::Underline)
+            );
+            assert_eq!(StyleSpec::from_sgr("").unwrap(), StyleSpec::new());
+        }
+
+        #[test]
+        fn extended_colors() {
+            assert_eq!(
+                StyleSpec::from_sgr("38;2;1;2;3;48;5;196").unwrap(),
+                StyleSpec::new()
+                    .fg(Color::TrueColor { r: 1, g: 2, b: 3 })
+                    .bg(Color::Ansi256(196))
+            );
+        }
+
+        #[test]
+        fn resets() {
+            assert_eq!(
+                StyleSpec::from_sgr("1;3;31;22;39").unwrap(),
+                StyleSpec::new().with(Styles::Italic).fg(Color::Default)
+            );
+            assert_eq!(StyleSpec::from_sgr("1;31;0").unwrap(), StyleSpec::new());
+        }
+
+        #[test]
+        fn colon_syntax() {
+            assert_eq!(
+                StyleSpec::from_sgr("38:2::1:2:3;48:2:4:5:6;4:3").unwrap(),
+                StyleSpec::new()
+                    .fg(Color::TrueColor { r: 1, g: 2, b: 3 })
+                    .bg(Color::TrueColor { r: 4, g: 5, b: 6 })
+                    .with(Styles::Underline)
+            );
+            assert_eq!(StyleSpec::from_sgr("4;4:0").unwrap(), StyleSpec::new());
+            assert!(StyleSpec::from_sgr("38:2::1").is_err());
+        }
+
+        #[test]
+        fn errors() {
+            assert_eq!(StyleSpec::from_sgr("1;x"), Err(ParseColorError::BadSgr("1;x".to_owned())));
+            assert!(StyleSpec::from_sgr("38;2;1").is_err());
+            assert!(StyleSpec::from_sgr("66").is_err());
+        }
+    }
+
     #[test]
     fn test_style_contains() {
         let mut style = Style(Styles::Bold.to_u8());
@@ -300,4 +1119,45 @@ mod tests {
         assert_eq!(style.contains(Styles::Italic), true);
         assert_eq!(style.contains(Styles::Dimmed), false);
     }
+
+    #[test]
+    fn apply_all_matches_display() {
+        let items = ["", "plain", "a\x1B[0mb", "日本"];
+        let specs = [StyleSpec::new(), StyleSpec::new().fg(Color::Red).bg(Color::Blue).with(Styles::Italic)];
+        for spec in specs {
+            let rendered: Vec<String> = spec.apply_all(items).collect();
+            let expected: Vec<String> = items.iter().map(|s| s.with_spec(spec).to_string()).collect();
+            assert_eq!(rendered, expected);
+        }
+    }
+
+    #[test]
+    fn test_styles_all() {
+        let all: Vec<Styles> = Styles::all().collect();
+        assert_eq!(all.len(), Styles::COUNT);
+        assert!(!all.contains(&Styles::Clear));
+        let mut masks: Vec<u8> = all.iter().map(|s| s.to_u8()).collect();
+        masks.sort_unstable();
+        masks.dedup();
+        assert_eq!(masks.len(), Styles::COUNT);
+    }
+
+    #[test]
+    fn test_style_insert_remove() {
+        let mut style = Style::default();
+        style.insert(Styles::Underline);
+        style.insert(Styles::Blink);
+        style.remove(Styles::Underline);
+        style.remove(Styles::Clear);
+        assert_eq!(style, Style(Styles::Blink.to_u8()));
+    }
+
+    #[test]
+    fn test_style_clear() {
+        let mut style = Style(Styles::Bold.to_u8());
+        assert!(!style.is_empty());
+        style.clear();
+        assert!(style.is_empty());
+        assert_eq!(style, Style::default());
+    }
 }
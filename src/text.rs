@@ -0,0 +1,411 @@
+//! Text made of several differently colored segments
+
+use std::{fmt, sync::Arc};
+
+use crate::{style::StyledChars, ColoredString, StyleSpec};
+
+/// A sequence of [`ColoredString`] segments, each with its own color and
+/// style, which are displayed one after the other.
+///
+/// ```rust
+/// # use colored::*;
+/// let mut text = "error".red().bold().push_colored(": ".normal());
+/// text.push_str("file not found");
+/// assert_eq!(text.segments().len(), 2);
+/// assert_eq!(text.to_plain(), "error: file not found");
+/// ```
+#[derive(Clone, Default)]
+pub struct ColoredText {
+    /// The colored segments, in display order
+    segments: Vec<ColoredString>,
+    /// The last rendering of each segment, see [`ColoredText::render_into`]
+    cache:    Vec<Option<RenderedSegment>>,
+}
+
+/// A segment rendered by [`ColoredText::render_into`]
+#[derive(Clone, Debug)]
+struct RenderedSegment {
+    /// The effective style the segment was rendered with
+    style: Arc<str>,
+    /// The rendered bytes, escape sequences included
+    bytes: Vec<u8>,
+}
+
+impl ColoredText {
+    /// Create an empty [`ColoredText`]
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+            cache:    Vec::new(),
+        }
+    }
+
+    /// Append a colored segment
+    #[inline]
+    pub fn push(&mut self, segment: ColoredString) {
+        self.segments.push(segment);
+    }
+
+    /// Append plain text, which inherits the style of the last segment
+    #[inline]
+    pub fn push_str(&mut self, s: &str) {
+        match self.segments.last_mut() {
+            Some(last) => {
+                last.push_str(s);
+                if let Some(cached) = self.cache.get_mut(self.segments.len() - 1) {
+                    *cached = None;
+                }
+            },
+            None => self.segments.push(ColoredString::from(s)),
+        }
+    }
+
+    /// Parse text containing SGR escape sequences, e.g. the output of a
+    /// child process, into segments. Other escape sequences are dropped.
+    ///
+    /// Parsing the output of `Display` gives back the same segments, as long
+    /// as no segment is empty and no two adjacent segments share the same
+    /// colors and style, since those are merged.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let text = ColoredText::from_ansi("\x1B[1;31merror\x1B[0m: oops");
+    /// assert_eq!(text.segments(), &["error".red().bold(), ColoredString::from(": oops")]);
+    /// ```
+    #[must_use]
+    pub fn from_ansi(s: &str) -> Self {
+        let mut text = Self::new();
+        for (ch, spec) in StyledChars::new(s, StyleSpec::new()) {
+            match text.segments.last_mut() {
+                Some(last) if StyleSpec::from(&*last) == spec => last.input.push(ch),
+                _ => text.segments.push(ColoredString {
+                    input:   ch.to_string(),
+                    fgcolor: spec.fgcolor(),
+                    bgcolor: spec.bgcolor(),
+                    style:   spec.style(),
+                }),
+            }
+        }
+        text
+    }
+
+    /// Create a text from `(text, spec)` pairs, e.g. the spans of an editor
+    /// or a markdown renderer, one segment per pair.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let keyword = StyleSpec::new().fg(Color::Magenta).with(Styles::Bold);
+    /// let text = ColoredText::from_spans([("fn", keyword), (" main()", StyleSpec::new())]);
+    /// assert_eq!(text.segments(), &["fn".magenta().bold(), ColoredString::from(" main()")]);
+    /// ```
+    #[must_use]
+    pub fn from_spans<I, S>(spans: I) -> Self
+    where
+        I: IntoIterator<Item = (S, StyleSpec)>,
+        S: Into<String>,
+    {
+        spans
+            .into_iter()
+            .map(|(text, spec)| ColoredString {
+                input:   text.into(),
+                fgcolor: spec.fgcolor(),
+                bgcolor: spec.bgcolor(),
+                style:   spec.style(),
+            })
+            .collect()
+    }
+
+    /// The `(text, spec)` pair of every segment, the inverse of
+    /// [`ColoredText::from_spans`]
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let text = "error".red().push_colored(": oops".normal());
+    /// let spans = text.to_spans();
+    /// assert_eq!(spans[0], ("error".to_owned(), StyleSpec::new().fg(Color::Red)));
+    /// assert_eq!(ColoredText::from_spans(spans), text);
+    /// ```
+    #[must_use]
+    pub fn to_spans(&self) -> Vec<(String, StyleSpec)> {
+        self.segments
+            .iter()
+            .map(|segment| (segment.input.clone(), StyleSpec::from(segment)))
+            .collect()
+    }
+
+    /// The `(text, spec)` pair of every segment, reusing the text buffers
+    #[must_use]
+    pub fn into_spans(self) -> Vec<(String, StyleSpec)> {
+        self.segments
+            .into_iter()
+            .map(|segment| {
+                let spec = StyleSpec::from(&segment);
+                (segment.input, spec)
+            })
+            .collect()
+    }
+
+    /// The colored segments, in display order
+    #[inline]
+    #[must_use]
+    pub fn segments(&self) -> &[ColoredString] {
+        &self.segments
+    }
+
+    /// Append the rendering of the text to `buf`, like `Display` does.
+    ///
+    /// The bytes of each segment are cached along with its effective style,
+    /// which depends on the global color settings. As long as neither
+    /// changes, later calls copy the cached bytes instead of rendering the
+    /// segment again, which helps when the same text is redrawn every frame.
+    ///
+    /// ```rust
+    /// # use colored::*;
+    /// let mut status = "ok".green().push_colored(" 3 jobs".normal());
+    /// let mut frame = Vec::new();
+    /// status.render_into(&mut frame);
+    /// assert_eq!(frame, status.to_string().into_bytes());
+    /// ```
+    pub fn render_into(&mut self, buf: &mut Vec<u8>) {
+        self.cache.resize(self.segments.len(), None);
+        for (segment, cached) in self.segments.iter().zip(&mut self.cache) {
+            let style = segment.compute_style_with(segment.has_colors());
+            match cached {
+                Some(cached) if cached.style == style => {},
+                _ => {
+                    let mut bytes = Vec::new();
+                    segment.extend_rendered(&style, &mut bytes);
+                    *cached = Some(RenderedSegment { style, bytes });
+                },
+            }
+            if let Some(cached) = cached {
+                buf.extend_from_slice(&cached.bytes);
+            }
+        }
+    }
+
+    /// The text of every segment, without any color or style. Hidden text is
+    /// masked with spaces, see [`ColoredString::to_plain`]
+    #[inline]
+    #[must_use]
+    pub fn to_plain(&self) -> String {
+        self.segments.iter().map(ColoredString::to_plain).collect()
+    }
+}
+
+impl fmt::Debug for ColoredText {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ColoredText")
+            .field("segments", &self.segments)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for ColoredText {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.segments == other.segments
+    }
+}
+
+impl Eq for ColoredText {}
+
+impl From<ColoredString> for ColoredText {
+    #[inline]
+    fn from(segment: ColoredString) -> Self {
+        Self {
+            segments: vec![segment],
+            cache:    Vec::new(),
+        }
+    }
+}
+
+impl From<Vec<(String, StyleSpec)>> for ColoredText {
+    /// See [`ColoredText::from_spans`]
+    #[inline]
+    fn from(spans: Vec<(String, StyleSpec)>) -> Self {
+        Self::from_spans(spans)
+    }
+}
+
+impl From<ColoredText> for Vec<(String, StyleSpec)> {
+    /// See [`ColoredText::into_spans`]
+    #[inline]
+    fn from(text: ColoredText) -> Self {
+        text.into_spans()
+    }
+}
+
+impl FromIterator<ColoredString> for ColoredText {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = ColoredString>>(iter: I) -> Self {
+        Self {
+            segments: iter.into_iter().collect(),
+            cache:    Vec::new(),
+        }
+    }
+}
+
+impl Extend<ColoredString> for ColoredText {
+    #[inline]
+    fn extend<I: IntoIterator<Item = ColoredString>>(&mut self, iter: I) {
+        self.segments.extend(iter);
+    }
+}
+
+impl fmt::Display for ColoredText {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for segment in &self.segments {
+            write!(f, "{segment}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Colorize, Styles};
+
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn push_str_inherits_last_style() {
+        let mut text = ColoredText::from("a".red());
+        text.push("b".blue());
+        text.push_str("c");
+        assert_eq!(text.segments().len(), 2);
+        assert_eq!(&*text.segments()[1], "bc");
+        assert_eq!(text.segments()[1].fgcolor(), Some(Color::Blue));
+    }
+
+    #[cfg_attr(feature = "strip-styles", ignore)]
+    #[test]
+    fn to_plain_masks_hidden() {
+        let text = "user: ".normal().push_colored("hunter2".hidden());
+        assert_eq!(text.to_plain(), "user:        ");
+    }
+
+    /// A xorshift generator, to build many arbitrary strings reproducibly
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self, bound: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % bound
+        }
+
+        fn color(&mut self) -> Option<Color> {
+            match self.next(3) {
+                0 => None,
+                1 => Some(Color::NAMED[self.next(16) as usize]),
+                _ => Some(Color::truecolor(
+                    self.next(256) as u8,
+                    self.next(256) as u8,
+                    self.next(256) as u8,
+                )),
+            }
+        }
+
+        fn segment(&mut self) -> ColoredString {
+            let words = ["a", "é", " ", "日本", "x\ty", "[0m", ";"];
+            let input: String = (0..=self.next(3))
+                .map(|_| words[self.next(words.len() as u64) as usize])
+                .collect();
+            let mut spec = StyleSpec::new();
+            for style in Styles::all() {
+                if self.next(4) == 0 {
+                    spec = spec.with(style);
+                }
+            }
+            if let Some(color) = self.color() {
+                spec = spec.fg(color);
+            }
+            if let Some(color) = self.color() {
+                spec = spec.bg(color);
+            }
+            input.with_spec(spec)
+        }
+    }
+
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn from_ansi_round_trips_display() {
+        let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+        for _ in 0..500 {
+            let mut expected = ColoredText::new();
+            for _ in 0..=rng.next(5) {
+                let segment = rng.segment();
+                match expected.segments.last_mut() {
+                    Some(last) if StyleSpec::from(&*last) == StyleSpec::from(&segment) => {
+                        last.input.push_str(&segment);
+                    },
+                    _ => expected.push(segment),
+                }
+            }
+
+            assert_eq!(expected, ColoredText::from_ansi(&expected.to_string()));
+        }
+    }
+
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn from_ansi_keeps_default_colors() {
+        let text = ColoredText::from_ansi(&"x".red().on_default().to_string());
+        let spec = StyleSpec::from(&text.segments[0]);
+        assert_eq!(spec, StyleSpec::new().fg(Color::Red).bg(Color::Default));
+    }
+
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn render_into_reuses_cache() {
+        let mut text: ColoredText = vec!["a\x1B[0mb".red(), "c".normal()].into_iter().collect();
+        let mut frame = Vec::new();
+        text.render_into(&mut frame);
+        assert_eq!(frame, text.to_string().into_bytes());
+        assert_eq!(text.cache.len(), 2);
+
+        let first = text.cache[0].as_ref().map(|c| c.bytes.as_ptr());
+        frame.clear();
+        text.render_into(&mut frame);
+        assert_eq!(frame, text.to_string().into_bytes());
+        assert_eq!(text.cache[0].as_ref().map(|c| c.bytes.as_ptr()), first);
+
+        text.push_str("d");
+        frame.clear();
+        text.render_into(&mut frame);
+        assert_eq!(frame, text.to_string().into_bytes());
+    }
+
+    #[test]
+    fn push_str_on_empty() {
+        let mut text = ColoredText::new();
+        text.push_str("plain");
+        assert!(text.segments()[0].is_plain());
+    }
+
+    #[test]
+    fn spans_round_trip() {
+        let spans = vec![
+            ("a".to_owned(), StyleSpec::new().fg(Color::Red).with(Styles::Bold)),
+            (String::new(), StyleSpec::new().bg(Color::Blue)),
+            ("c".to_owned(), StyleSpec::new()),
+        ];
+        let text = ColoredText::from(spans.clone());
+        assert_eq!(text.segments().len(), 3);
+        assert_eq!(text.to_spans(), spans);
+        assert_eq!(Vec::from(text), spans);
+    }
+
+    #[cfg_attr(any(feature = "no-color", feature = "strip-styles"), ignore)]
+    #[test]
+    fn display_concatenates_segments() {
+        let text: ColoredText = vec!["a".red(), "b".normal()].into_iter().collect();
+        assert_eq!(text.to_string(), format!("{}{}", "a".red(), "b"));
+    }
+}
@@ -0,0 +1,152 @@
+//! A process-wide registry of named styles, used by the semantic helpers such
+//! as [`Colorize::ok`](crate::Colorize::ok).
+
+use std::{collections::HashMap, sync::RwLock};
+
+use crate::{Color, LazyLock, ParseColorError, StyleSpec, Styles};
+
+/// A set of named [`StyleSpec`]s.
+///
+/// The default theme contains `ok` (green), `err` (red) and `warn` (yellow),
+/// and the [`Severity`](crate::Severity) levels `severity.info` (unstyled),
+/// `severity.warn` (yellow), `severity.error` (bold red) and
+/// `severity.critical` (white on red).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Theme {
+    /// The styles, by name
+    styles: HashMap<String, StyleSpec>,
+}
+
+impl Default for Theme {
+    #[inline]
+    fn default() -> Self {
+        let mut theme = Self::empty();
+        theme.set("ok", StyleSpec::new().fg(Color::Green));
+        theme.set("err", StyleSpec::new().fg(Color::Red));
+        theme.set("warn", StyleSpec::new().fg(Color::Yellow));
+        theme.set("severity.info", StyleSpec::new());
+        theme.set("severity.warn", StyleSpec::new().fg(Color::Yellow));
+        theme.set("severity.error", StyleSpec::new().fg(Color::Red).with(Styles::Bold));
+        theme.set("severity.critical", StyleSpec::new().fg(Color::White).bg(Color::Red));
+        theme
+    }
+}
+
+impl Theme {
+    /// Create a theme without any style
+    #[inline]
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            styles: HashMap::new(),
+        }
+    }
+
+    /// Set the style registered under `name`
+    #[inline]
+    pub fn set<S: Into<String>>(&mut self, name: S, spec: StyleSpec) {
+        self.styles.insert(name.into(), spec);
+    }
+
+    /// Get the style registered under `name`
+    #[inline]
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<StyleSpec> {
+        self.styles.get(name).copied()
+    }
+
+    /// Parses a `capability=SGR` list separated by colons, as found in
+    /// `GREP_COLORS`, e.g. `ms=01;31:mc=01;31:sl=:cx=:fn=35:ln=32`. Boolean
+    /// capabilities such as `rv` or `ne` are ignored.
+    ///
+    /// ```rust
+    /// # use colored::{theme::Theme, *};
+    /// let theme = Theme::from_grep_colors("ms=01;31:fn=35:ne").unwrap();
+    /// assert_eq!(theme.get("ms"), Some(StyleSpec::new().fg(Color::Red).with(Styles::Bold)));
+    /// assert_eq!(theme.get("fn"), Some(StyleSpec::new().fg(Color::Magenta)));
+    /// assert_eq!(theme.get("ne"), None);
+    /// ```
+    ///
+    /// # Errors
+    /// Will produce a [`ParseColorError`] if one of the SGR sequences is
+    /// invalid, see [`StyleSpec::from_sgr`]
+    pub fn from_grep_colors(src: &str) -> Result<Self, ParseColorError> {
+        let mut theme = Self::empty();
+        for (name, sgr) in src.split(':').filter_map(|cap| cap.split_once('=')) {
+            theme.set(name, StyleSpec::from_sgr(sgr)?);
+        }
+        Ok(theme)
+    }
+}
+
+/// The theme used by the whole process
+static THEME: LazyLock<RwLock<Theme>> = LazyLock::new(|| RwLock::new(Theme::default()));
+
+/// Replace the theme used by the whole process
+#[inline]
+pub fn set_theme(theme: Theme) {
+    *THEME.write().expect("theme lock poisoned") = theme;
+}
+
+/// Set a single style of the theme used by the whole process.
+///
+/// ```rust
+/// # use colored::*;
+/// theme::set("ok", StyleSpec::new().fg(Color::BrightGreen));
+/// assert_eq!("done".ok(), "done".bright_green());
+/// # theme::set_theme(theme::Theme::default());
+/// ```
+#[inline]
+pub fn set<S: Into<String>>(name: S, spec: StyleSpec) {
+    THEME.write().expect("theme lock poisoned").set(name, spec);
+}
+
+/// Get a style of the theme used by the whole process
+#[inline]
+#[must_use]
+pub fn get(name: &str) -> Option<StyleSpec> {
+    THEME.read().expect("theme lock poisoned").get(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_theme() {
+        let theme = Theme::default();
+        assert_eq!(theme.get("ok"), Some(StyleSpec::new().fg(Color::Green)));
+        assert_eq!(theme.get("err"), Some(StyleSpec::new().fg(Color::Red)));
+        assert_eq!(theme.get("warn"), Some(StyleSpec::new().fg(Color::Yellow)));
+        assert_eq!(theme.get("severity.info"), Some(StyleSpec::new()));
+        assert_eq!(theme.get("unknown"), None);
+    }
+
+    #[test]
+    fn grep_colors() {
+        let theme = Theme::from_grep_colors("ms=01;31:mc=01;31:sl=:cx=:fn=35:ln=32:bn=32:se=36").unwrap();
+        assert_eq!(theme.get("ln"), Some(StyleSpec::new().fg(Color::Green)));
+        assert_eq!(theme.get("sl"), Some(StyleSpec::new()));
+        assert!(Theme::from_grep_colors("ms=01;zz").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "embed-theme")]
+    fn embedded() {
+        let theme = crate::embed_theme!("tests/themes/dark.toml");
+        assert_eq!(theme.get("err"), Some(StyleSpec::new().fg(Color::BrightRed).with(Styles::Bold)));
+        assert_eq!(theme.get("severity.info"), Some(StyleSpec::new()));
+        assert_eq!(
+            theme.get("severity.critical"),
+            Some(StyleSpec::new().fg(Color::White).bg(Color::Red))
+        );
+        assert_eq!(theme.get("severity.debug"), None);
+    }
+
+    #[test]
+    fn set_overrides() {
+        let mut theme = Theme::empty();
+        theme.set("ok", StyleSpec::new().bg(Color::Blue));
+        assert_eq!(theme.get("ok"), Some(StyleSpec::new().bg(Color::Blue)));
+    }
+}
@@ -0,0 +1,340 @@
+//! The number of terminal columns taken by text.
+//!
+//! This approximates the East Asian Width property without its full tables:
+//! control characters, combining marks, zero-width characters and variation
+//! selectors take no column, the CJK blocks, Hangul, fullwidth forms and most
+//! emoji take two, everything else takes one.
+
+/// Characters taking no column, sorted
+const ZERO: &[(char, char)] = &[
+    ('\0', '\x1F'),
+    ('\x7F', '\u{9F}'),
+    ('\u{300}', '\u{36F}'),
+    ('\u{483}', '\u{489}'),
+    ('\u{591}', '\u{5BD}'),
+    ('\u{610}', '\u{61A}'),
+    ('\u{64B}', '\u{65F}'),
+    ('\u{1AB0}', '\u{1AFF}'),
+    ('\u{1DC0}', '\u{1DFF}'),
+    ('\u{200B}', '\u{200F}'),
+    ('\u{2028}', '\u{202E}'),
+    ('\u{2060}', '\u{2064}'),
+    ('\u{20D0}', '\u{20FF}'),
+    ('\u{FE00}', '\u{FE0F}'),
+    ('\u{FE20}', '\u{FE2F}'),
+    ('\u{FEFF}', '\u{FEFF}'),
+    ('\u{1F3FB}', '\u{1F3FF}'),
+    ('\u{E0000}', '\u{E007F}'),
+    ('\u{E0100}', '\u{E01EF}'),
+];
+
+/// Characters taking two columns, sorted
+const WIDE: &[(char, char)] = &[
+    ('\u{1100}', '\u{115F}'),
+    ('\u{231A}', '\u{231B}'),
+    ('\u{23E9}', '\u{23EC}'),
+    ('\u{25FD}', '\u{25FE}'),
+    ('\u{2614}', '\u{2615}'),
+    ('\u{2648}', '\u{2653}'),
+    ('\u{26A1}', '\u{26A1}'),
+    ('\u{26AA}', '\u{26AB}'),
+    ('\u{26BD}', '\u{26BE}'),
+    ('\u{26C4}', '\u{26C5}'),
+    ('\u{26D4}', '\u{26D4}'),
+    ('\u{26EA}', '\u{26EA}'),
+    ('\u{26F2}', '\u{26F5}'),
+    ('\u{26FA}', '\u{26FD}'),
+    ('\u{2705}', '\u{2705}'),
+    ('\u{270A}', '\u{270B}'),
+    ('\u{274C}', '\u{274C}'),
+    ('\u{2753}', '\u{2755}'),
+    ('\u{2795}', '\u{2797}'),
+    ('\u{2B1B}', '\u{2B1C}'),
+    ('\u{2B50}', '\u{2B50}'),
+    ('\u{2E80}', '\u{303E}'),
+    ('\u{3041}', '\u{33FF}'),
+    ('\u{3400}', '\u{4DBF}'),
+    ('\u{4E00}', '\u{9FFF}'),
+    ('\u{A000}', '\u{A4CF}'),
+    ('\u{A960}', '\u{A97F}'),
+    ('\u{AC00}', '\u{D7A3}'),
+    ('\u{F900}', '\u{FAFF}'),
+    ('\u{FE10}', '\u{FE19}'),
+    ('\u{FE30}', '\u{FE6F}'),
+    ('\u{FF00}', '\u{FF60}'),
+    ('\u{FFE0}', '\u{FFE6}'),
+    ('\u{1F004}', '\u{1F004}'),
+    ('\u{1F0CF}', '\u{1F0CF}'),
+    ('\u{1F18E}', '\u{1F18E}'),
+    ('\u{1F191}', '\u{1F19A}'),
+    ('\u{1F200}', '\u{1F251}'),
+    ('\u{1F300}', '\u{1F320}'),
+    ('\u{1F32D}', '\u{1F335}'),
+    ('\u{1F337}', '\u{1F37C}'),
+    ('\u{1F37E}', '\u{1F393}'),
+    ('\u{1F3A0}', '\u{1F3CA}'),
+    ('\u{1F3CF}', '\u{1F3D3}'),
+    ('\u{1F3E0}', '\u{1F3F0}'),
+    ('\u{1F3F4}', '\u{1F3F4}'),
+    ('\u{1F3F8}', '\u{1F3FA}'),
+    ('\u{1F400}', '\u{1F64F}'),
+    ('\u{1F680}', '\u{1F6FF}'),
+    ('\u{1F7E0}', '\u{1F7EB}'),
+    ('\u{1F90C}', '\u{1F9FF}'),
+    ('\u{1FA70}', '\u{1FAFF}'),
+    ('\u{20000}', '\u{2FFFD}'),
+    ('\u{30000}', '\u{3FFFD}'),
+];
+
+/// Joins the characters of an emoji sequence, e.g. 👨‍👩‍👧
+const ZWJ: char = '\u{200D}';
+/// Asks for the emoji presentation of the character before it, e.g. ❤️
+const VS16: char = '\u{FE0F}';
+
+/// How to count the columns of emoji, whose width terminals disagree on.
+///
+/// The default counts each character on its own, the way `wcwidth` does:
+/// emoji take two columns, the characters of a ZWJ sequence are counted one
+/// by one and variation selectors take none. [`WidthPolicy::grapheme`]
+/// follows the terminals which display an emoji sequence as a single emoji.
+///
+/// ```rust
+/// # use colored::*;
+/// let family = "👨\u{200D}👩\u{200D}👧";
+/// assert_eq!(WidthPolicy::default().width(family), 6);
+/// assert_eq!(WidthPolicy::grapheme().width(family), 2);
+/// assert_eq!(WidthPolicy::grapheme().width("❤\u{FE0F}"), 2);
+/// assert_eq!(WidthPolicy::default().with_wide_emoji(false).width("🦀"), 1);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WidthPolicy {
+    /// Whether emoji take two columns rather than one
+    wide_emoji: bool,
+    /// Whether a character joined by a ZWJ takes no column of its own
+    join_zwj:   bool,
+    /// Whether VS16 makes the character before it two columns wide
+    widen_vs16: bool,
+}
+
+impl Default for WidthPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::wcwidth()
+    }
+}
+
+impl WidthPolicy {
+    /// Every character counted on its own, the default
+    #[inline]
+    #[must_use]
+    pub const fn wcwidth() -> Self {
+        Self {
+            wide_emoji: true,
+            join_zwj:   false,
+            widen_vs16: false,
+        }
+    }
+
+    /// Emoji sequences counted as a single emoji, and characters followed by
+    /// VS16 as emoji, as in e.g. `kitty` or `WezTerm`
+    #[inline]
+    #[must_use]
+    pub const fn grapheme() -> Self {
+        Self {
+            wide_emoji: true,
+            join_zwj:   true,
+            widen_vs16: true,
+        }
+    }
+
+    /// Make emoji take two columns, or one
+    #[inline]
+    #[must_use]
+    pub const fn with_wide_emoji(mut self, wide: bool) -> Self {
+        self.wide_emoji = wide;
+        self
+    }
+
+    /// Make the characters joined by a ZWJ take no column of their own
+    #[inline]
+    #[must_use]
+    pub const fn with_joined_zwj(mut self, join: bool) -> Self {
+        self.join_zwj = join;
+        self
+    }
+
+    /// Make VS16 widen a narrow character before it to two columns
+    #[inline]
+    #[must_use]
+    pub const fn with_widening_vs16(mut self, widen: bool) -> Self {
+        self.widen_vs16 = widen;
+        self
+    }
+
+    /// Whether emoji take two columns
+    #[inline]
+    #[must_use]
+    pub const fn wide_emoji(&self) -> bool {
+        self.wide_emoji
+    }
+
+    /// Whether the characters joined by a ZWJ take no column of their own
+    #[inline]
+    #[must_use]
+    pub const fn joined_zwj(&self) -> bool {
+        self.join_zwj
+    }
+
+    /// Whether VS16 widens a narrow character before it
+    #[inline]
+    #[must_use]
+    pub const fn widening_vs16(&self) -> bool {
+        self.widen_vs16
+    }
+
+    /// The number of columns taken by `s`, which must not contain escape
+    /// sequences, see [`ColoredString::display_width_with`](crate::ColoredString::display_width_with)
+    #[inline]
+    #[must_use]
+    pub fn width(&self, s: &str) -> usize {
+        self.chars_width(s.chars())
+    }
+
+    /// The number of columns taken by `chars`
+    pub(crate) fn chars_width<I: IntoIterator<Item = char>>(&self, chars: I) -> usize {
+        let mut columns = Columns::new(*self);
+        chars.into_iter().map(|c| columns.push(c)).sum()
+    }
+
+    /// The number of columns taken by `c` on its own
+    fn char_width(self, c: char) -> usize {
+        match char_width(c) {
+            2 if !self.wide_emoji && is_emoji(c) => 1,
+            width => width,
+        }
+    }
+}
+
+/// Counts the columns of text one character at a time, following a
+/// [`WidthPolicy`]
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Columns {
+    /// How emoji are counted
+    policy:  WidthPolicy,
+    /// The width of the last character taking a column
+    last:    usize,
+    /// Whether the next character is joined to the last one by a ZWJ
+    joining: bool,
+}
+
+impl Columns {
+    /// Start counting with `policy`
+    pub(crate) const fn new(policy: WidthPolicy) -> Self {
+        Self {
+            policy,
+            last: 0,
+            joining: false,
+        }
+    }
+
+    /// The columns added by `c`. A VS16 may add a column to the character
+    /// before it.
+    pub(crate) fn push(&mut self, c: char) -> usize {
+        if c == ZWJ && self.policy.join_zwj {
+            self.joining = true;
+            return 0;
+        }
+        if c == VS16 {
+            if self.policy.widen_vs16 && self.last == 1 {
+                self.last = 2;
+                return 1;
+            }
+            return 0;
+        }
+
+        let width = self.policy.char_width(c);
+        if width == 0 {
+            return 0;
+        }
+        if self.joining {
+            self.joining = false;
+            return 0;
+        }
+        self.last = width;
+        width
+    }
+}
+
+/// Is the wide character `c` an emoji, rather than e.g. a CJK ideograph?
+fn is_emoji(c: char) -> bool {
+    ('\u{2300}'..='\u{2BFF}').contains(&c) || ('\u{1F000}'..'\u{20000}').contains(&c)
+}
+
+/// Is `c` in one of the sorted `ranges`?
+fn in_ranges(c: char, ranges: &[(char, char)]) -> bool {
+    ranges
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                std::cmp::Ordering::Less
+            } else if start > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// The number of columns taken by `c`
+pub(crate) fn char_width(c: char) -> usize {
+    if c.is_ascii() {
+        usize::from(!c.is_ascii_control())
+    } else if in_ranges(c, ZERO) {
+        0
+    } else if in_ranges(c, WIDE) {
+        2
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widths() {
+        assert_eq!(char_width('a'), 1);
+        assert_eq!(char_width('\t'), 0);
+        assert_eq!(char_width('é'), 1);
+        assert_eq!(char_width('\u{301}'), 0);
+        assert_eq!(char_width('日'), 2);
+        assert_eq!(char_width('한'), 2);
+        assert_eq!(char_width('Ａ'), 2);
+        assert_eq!(char_width('🦀'), 2);
+        assert_eq!(char_width('\u{FE0F}'), 0);
+        assert_eq!(char_width('\u{200D}'), 0);
+        assert_eq!(char_width('─'), 1);
+    }
+
+    #[test]
+    fn policies() {
+        let family = "👨\u{200D}👩\u{200D}👧";
+        assert_eq!(WidthPolicy::grapheme().with_wide_emoji(false).width(family), 1);
+        assert_eq!(WidthPolicy::wcwidth().width("❤\u{FE0F}"), 1);
+        // already wide
+        assert_eq!(WidthPolicy::grapheme().width("🦀\u{FE0F}"), 2);
+        assert_eq!(WidthPolicy::default().with_wide_emoji(false).width("日🦀"), 3);
+        // a dangling ZWJ joins nothing
+        assert_eq!(WidthPolicy::grapheme().width("a\u{200D}"), 1);
+    }
+
+    #[test]
+    fn ranges_are_sorted() {
+        for ranges in [ZERO, WIDE] {
+            for pair in ranges.windows(2) {
+                assert!(pair[0].0 <= pair[0].1 && pair[0].1 < pair[1].0);
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Writing to legacy Windows consoles.
+//!
+//! Writing UTF-8 bytes to a console which uses a legacy code page mangles
+//! every non-ASCII character. [`ConsoleWriter`] converts the text to UTF-16
+//! and writes it with `WriteConsoleW` instead, which is correct whatever the
+//! code page of the console is.
+
+use std::{io, ptr, str};
+
+use winapi::{
+    shared::minwindef::DWORD,
+    um::{
+        consoleapi::WriteConsoleW,
+        processenv::GetStdHandle,
+        winbase::{STD_ERROR_HANDLE, STD_OUTPUT_HANDLE},
+        winnt::HANDLE,
+    },
+};
+
+use crate::control::Stream;
+
+/// An [`io::Write`] writing UTF-8 text to a Windows console with
+/// `WriteConsoleW`.
+///
+/// A character split across two calls to `write` is kept until it is
+/// complete. Bytes which are not valid UTF-8 are written as `U+FFFD`.
+///
+/// ```rust,no_run
+/// # use colored::{control::Stream, windows::ConsoleWriter, *};
+/// # use std::io::Write;
+/// let mut out = ConsoleWriter::new(Stream::Stdout).unwrap();
+/// writeln!(out, "{}", "héllo wörld".green()).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ConsoleWriter {
+    /// Handle of the console
+    handle:  HANDLE,
+    /// Bytes of an incomplete UTF-8 character
+    pending: Vec<u8>,
+}
+
+impl ConsoleWriter {
+    /// Create a writer to the console attached to `stream`
+    ///
+    /// # Errors
+    /// Will produce an error if the process has no such standard handle
+    pub fn new(stream: Stream) -> io::Result<Self> {
+        let id = match stream {
+            Stream::Stdout => STD_OUTPUT_HANDLE,
+            Stream::Stderr => STD_ERROR_HANDLE,
+        };
+
+        let handle = unsafe { GetStdHandle(id) };
+        // `INVALID_HANDLE_VALUE` is -1
+        if handle.is_null() || handle as isize == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self {
+            handle,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Write every UTF-16 unit of `wide` to the console
+    fn write_wide(&self, mut wide: &[u16]) -> io::Result<()> {
+        while !wide.is_empty() {
+            let len = DWORD::try_from(wide.len()).unwrap_or(DWORD::MAX);
+            let mut written: DWORD = 0;
+            let ok = unsafe {
+                WriteConsoleW(
+                    self.handle,
+                    wide.as_ptr().cast(),
+                    len,
+                    &mut written,
+                    ptr::null_mut(),
+                )
+            };
+            if ok == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            if written == 0 {
+                return Err(io::ErrorKind::WriteZero.into());
+            }
+            wide = &wide[written as usize..];
+        }
+        Ok(())
+    }
+}
+
+impl io::Write for ConsoleWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let previous = self.pending.len();
+        self.pending.extend_from_slice(buf);
+
+        let complete = match str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            // the end of the buffer is the start of a character
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+
+        let text = String::from_utf8_lossy(&self.pending[..complete]);
+        let wide: Vec<u16> = text.encode_utf16().collect();
+        if let Err(e) = self.write_wide(&wide) {
+            self.pending.truncate(previous);
+            return Err(e);
+        }
+        self.pending.drain(..complete);
+
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
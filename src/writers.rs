@@ -0,0 +1,214 @@
+//! Writers for colored output
+
+use std::io::{self, IoSlice, Write};
+
+use crate::ansi::{AnsiEvent, SgrParser};
+
+/// The longest escape sequence kept between two writes of a [`Tee`]. An
+/// unterminated OSC or DCS string longer than this is not an escape sequence
+/// the terminal is waiting on anymore, and is written to the file as text.
+const MAX_PENDING: usize = 4096;
+
+/// An [`io::Write`] writing everything to a terminal, and a copy without
+/// escape sequences to a file, e.g. to log to both the console and a logfile
+/// while rendering only once.
+///
+/// Escape sequences split between two writes are stripped as well, unless
+/// they grow past 4 KiB without being terminated. The file is written first,
+/// so that an error is never reported for bytes which already reached the
+/// terminal.
+///
+/// ```rust
+/// # use colored::*;
+/// # use std::io::Write;
+/// let mut tee = colored::writers::Tee::new(Vec::new(), Vec::new());
+/// write!(tee, "{} done", "ok".green()).unwrap();
+/// let (terminal, file) = tee.into_inner();
+/// assert_eq!(terminal, format!("{} done", "ok".green()).into_bytes());
+/// assert_eq!(file, b"ok done");
+/// ```
+#[derive(Debug)]
+pub struct Tee<T, F> {
+    /// Receives the output as is
+    terminal: T,
+    /// Receives the output without escape sequences
+    file:     F,
+    /// An escape sequence cut at the end of the last write
+    pending:  Vec<u8>,
+}
+
+impl<T: Write, F: Write> Tee<T, F> {
+    /// Create a writer to both `terminal` and `file`
+    #[inline]
+    pub const fn new(terminal: T, file: F) -> Self {
+        Self {
+            terminal,
+            file,
+            pending: Vec::new(),
+        }
+    }
+
+    /// The terminal and file sinks
+    #[inline]
+    pub const fn get_ref(&self) -> (&T, &F) {
+        (&self.terminal, &self.file)
+    }
+
+    /// Unwrap the terminal and file sinks
+    #[inline]
+    pub fn into_inner(self) -> (T, F) {
+        (self.terminal, self.file)
+    }
+
+    /// The bytes of `buf` which are not part of an escape sequence, keeping
+    /// an escape sequence cut at its end for the next write
+    fn strip(&mut self, buf: &[u8]) -> Vec<u8> {
+        let input = if self.pending.is_empty() {
+            buf.to_vec()
+        } else {
+            let mut input = std::mem::take(&mut self.pending);
+            input.extend_from_slice(buf);
+            input
+        };
+
+        let mut plain = Vec::with_capacity(input.len());
+        let mut parser = SgrParser::partial_bytes(&input);
+        for event in parser.by_ref() {
+            if let AnsiEvent::Text(text) = event {
+                plain.extend_from_slice(text);
+            }
+        }
+        let pending = parser.pending();
+        if pending.len() > MAX_PENDING {
+            plain.extend_from_slice(pending);
+        } else {
+            self.pending = pending.to_vec();
+        }
+        plain
+    }
+}
+
+impl<T: Write, F: Write> Write for Tee<T, F> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let pending = self.pending.clone();
+        let plain = self.strip(buf);
+        if let Err(e) = self.file.write_all(&plain) {
+            self.pending = pending;
+            return Err(e);
+        }
+        self.terminal.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.terminal.flush()?;
+        self.file.flush()
+    }
+}
+
+/// Write all of `parts` to `writer` with a single vectored write when the
+/// writer takes everything at once, so that a prefix, body and suffix cost one
+/// syscall and aren't interleaved with the output of other threads. What is
+/// left after a short write is written part by part.
+pub(crate) fn write_all_vectored<W: Write + ?Sized>(writer: &mut W, parts: &[&[u8]]) -> io::Result<()> {
+    let slices = parts.iter().map(|part| IoSlice::new(part)).collect::<Vec<_>>();
+    let mut written = loop {
+        match writer.write_vectored(&slices) {
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            result => break result?,
+        }
+    };
+
+    for part in parts {
+        let skip = written.min(part.len());
+        written -= skip;
+        writer.write_all(&part[skip..])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A writer taking at most `limit` bytes per call and counting the calls
+    struct Short {
+        out:   Vec<u8>,
+        limit: usize,
+        calls: usize,
+    }
+
+    impl Write for Short {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.calls += 1;
+            let len = buf.len().min(self.limit);
+            self.out.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn vectored_write_completes_short_writes() {
+        let parts: [&[u8]; 3] = [b"\x1B[31m", b"body", b"\x1B[0m"];
+        let mut out = Vec::new();
+        write_all_vectored(&mut out, &parts).unwrap();
+        assert_eq!(out, parts.concat());
+
+        let mut short = Short { out: Vec::new(), limit: 3, calls: 0 };
+        write_all_vectored(&mut short, &parts).unwrap();
+        assert_eq!(short.out, parts.concat());
+        assert!(short.calls > 1);
+    }
+
+    #[test]
+    fn strips_every_sequence() {
+        let mut tee = Tee::new(Vec::new(), Vec::new());
+        let out = b"\x1B[1;31ma\x1B[0m\x1B]8;;url\x1B\\b\x1B]0;t\x07c\x1B7d\xC3\xA9";
+        tee.write_all(out).unwrap();
+        let (terminal, file) = tee.into_inner();
+        assert_eq!(terminal, out);
+        assert_eq!(file, "abcdé".as_bytes());
+    }
+
+    #[test]
+    fn sequences_split_between_writes() {
+        let mut tee = Tee::new(io::sink(), Vec::new());
+        for chunk in [&b"a\x1B"[..], b"[3", b"1mb\x1B]8;;u\x1B", b"\\c"] {
+            tee.write_all(chunk).unwrap();
+        }
+        assert_eq!(tee.get_ref().1, b"abc");
+
+        let mut tee = Tee::new(io::sink(), Vec::new());
+        for chunk in [&b"\x1B"[..], "éa".as_bytes()] {
+            tee.write_all(chunk).unwrap();
+        }
+        assert_eq!(tee.get_ref().1, b"a");
+    }
+
+    #[test]
+    fn unterminated_sequences_are_flushed() {
+        let mut tee = Tee::new(io::sink(), Vec::new());
+        tee.write_all(b"a\x1B]0;").unwrap();
+        for _ in 0..5 {
+            tee.write_all(&[b'x'; 1000]).unwrap();
+        }
+        assert!(tee.pending.is_empty());
+        assert_eq!(tee.get_ref().1.len(), 1 + 4 + 5000);
+
+        tee.write_all(b"\x1B[31mb").unwrap();
+        assert!(tee.get_ref().1.ends_with(b"xb"));
+    }
+
+    #[test]
+    fn file_errors_leave_terminal_untouched() {
+        let mut file = Short { out: Vec::new(), limit: 0, calls: 0 };
+        let mut tee = Tee::new(Vec::new(), &mut file);
+        assert!(tee.write(b"[31ma").is_err());
+        assert!(tee.get_ref().0.is_empty());
+    }
+}
@@ -0,0 +1,644 @@
+//! The conventional names of the `xterm` 256 color palette, e.g.
+//! `DarkSeaGreen4` or `Grey37`.
+//!
+//! Some names are shared by several colors. Their constants are suffixed with
+//! `_A`, `_B`, ... in palette order, and [`from_name`] accepts the same
+//! suffixes, resolving the bare name to the first color.
+//!
+//! The colors are [`Color::Ansi256`] indices, displayed with the palette of
+//! the terminal.
+//!
+//! ```rust
+//! # use colored::{*, xterm};
+//! assert_eq!(xterm::GREY37, Color::Ansi256(59));
+//! assert_eq!("DarkSeaGreen4".parse(), Ok(xterm::DARK_SEA_GREEN4_A));
+//! assert_eq!(xterm::name(65), Some("DarkSeaGreen4"));
+//! println!("{}", "moss".color(xterm::DARK_SEA_GREEN4_B));
+//! ```
+
+use crate::Color;
+
+/// The color of the palette at `index`
+const fn indexed(index: u8) -> Color {
+    Color::Ansi256(index)
+}
+
+/// The name of every color from 16 to 255
+const NAMES: [&str; 240] = [
+    "Grey0", "NavyBlue", "DarkBlue", "Blue3", "Blue3", "Blue1", "DarkGreen",
+    "DeepSkyBlue4", "DeepSkyBlue4", "DeepSkyBlue4", "DodgerBlue3", "DodgerBlue2",
+    "Green4", "SpringGreen4", "Turquoise4", "DeepSkyBlue3", "DeepSkyBlue3", "DodgerBlue1",
+    "Green3", "SpringGreen3", "DarkCyan", "LightSeaGreen", "DeepSkyBlue2", "DeepSkyBlue1",
+    "Green3", "SpringGreen3", "SpringGreen2", "Cyan3", "DarkTurquoise", "Turquoise2",
+    "Green1", "SpringGreen2", "SpringGreen1", "MediumSpringGreen", "Cyan2", "Cyan1",
+    "DarkRed", "DeepPink4", "Purple4", "Purple4", "Purple3", "BlueViolet", "Orange4",
+    "Grey37", "MediumPurple4", "SlateBlue3", "SlateBlue3", "RoyalBlue1", "Chartreuse4",
+    "DarkSeaGreen4", "PaleTurquoise4", "SteelBlue", "SteelBlue3", "CornflowerBlue",
+    "Chartreuse3", "DarkSeaGreen4", "CadetBlue", "CadetBlue", "SkyBlue3", "SteelBlue1",
+    "Chartreuse3", "PaleGreen3", "SeaGreen3", "Aquamarine3", "MediumTurquoise",
+    "SteelBlue1", "Chartreuse2", "SeaGreen2", "SeaGreen1", "SeaGreen1", "Aquamarine1",
+    "DarkSlateGray2", "DarkRed", "DeepPink4", "DarkMagenta", "DarkMagenta", "DarkViolet",
+    "Purple", "Orange4", "LightPink4", "Plum4", "MediumPurple3", "MediumPurple3",
+    "SlateBlue1", "Yellow4", "Wheat4", "Grey53", "LightSlateGrey", "MediumPurple",
+    "LightSlateBlue", "Yellow4", "DarkOliveGreen3", "DarkSeaGreen", "LightSkyBlue3",
+    "LightSkyBlue3", "SkyBlue2", "Chartreuse2", "DarkOliveGreen3", "PaleGreen3",
+    "DarkSeaGreen3", "DarkSlateGray3", "SkyBlue1", "Chartreuse1", "LightGreen",
+    "LightGreen", "PaleGreen1", "Aquamarine1", "DarkSlateGray1", "Red3", "DeepPink4",
+    "MediumVioletRed", "Magenta3", "DarkViolet", "Purple", "DarkOrange3", "IndianRed",
+    "HotPink3", "MediumOrchid3", "MediumOrchid", "MediumPurple2", "DarkGoldenrod",
+    "LightSalmon3", "RosyBrown", "Grey63", "MediumPurple2", "MediumPurple1", "Gold3",
+    "DarkKhaki", "NavajoWhite3", "Grey69", "LightSteelBlue3", "LightSteelBlue", "Yellow3",
+    "DarkOliveGreen3", "DarkSeaGreen3", "DarkSeaGreen2", "LightCyan3", "LightSkyBlue1",
+    "GreenYellow", "DarkOliveGreen2", "PaleGreen1", "DarkSeaGreen2", "DarkSeaGreen1",
+    "PaleTurquoise1", "Red3", "DeepPink3", "DeepPink3", "Magenta3", "Magenta3",
+    "Magenta2", "DarkOrange3", "IndianRed", "HotPink3", "HotPink2", "Orchid",
+    "MediumOrchid1", "Orange3", "LightSalmon3", "LightPink3", "Pink3", "Plum3", "Violet",
+    "Gold3", "LightGoldenrod3", "Tan", "MistyRose3", "Thistle3", "Plum2", "Yellow3",
+    "Khaki3", "LightGoldenrod2", "LightYellow3", "Grey84", "LightSteelBlue1", "Yellow2",
+    "DarkOliveGreen1", "DarkOliveGreen1", "DarkSeaGreen1", "Honeydew2", "LightCyan1",
+    "Red1", "DeepPink2", "DeepPink1", "DeepPink1", "Magenta2", "Magenta1", "OrangeRed1",
+    "IndianRed1", "IndianRed1", "HotPink", "HotPink", "MediumOrchid1", "DarkOrange",
+    "Salmon1", "LightCoral", "PaleVioletRed1", "Orchid2", "Orchid1", "Orange1",
+    "SandyBrown", "LightSalmon1", "LightPink1", "Pink1", "Plum1", "Gold1",
+    "LightGoldenrod2", "LightGoldenrod2", "NavajoWhite1", "MistyRose1", "Thistle1",
+    "Yellow1", "LightGoldenrod1", "Khaki1", "Wheat1", "Cornsilk1", "Grey100", "Grey3",
+    "Grey7", "Grey11", "Grey15", "Grey19", "Grey23", "Grey27", "Grey30", "Grey35",
+    "Grey39", "Grey42", "Grey46", "Grey50", "Grey54", "Grey58", "Grey62", "Grey66",
+    "Grey70", "Grey74", "Grey78", "Grey82", "Grey85", "Grey89", "Grey93",
+];
+
+/// `Grey0`, color 16
+pub const GREY0: Color = indexed(16);
+/// `NavyBlue`, color 17
+pub const NAVY_BLUE: Color = indexed(17);
+/// `DarkBlue`, color 18
+pub const DARK_BLUE: Color = indexed(18);
+/// `Blue3`, color 19
+pub const BLUE3_A: Color = indexed(19);
+/// `Blue3`, color 20
+pub const BLUE3_B: Color = indexed(20);
+/// `Blue1`, color 21
+pub const BLUE1: Color = indexed(21);
+/// `DarkGreen`, color 22
+pub const DARK_GREEN: Color = indexed(22);
+/// `DeepSkyBlue4`, color 23
+pub const DEEP_SKY_BLUE4_A: Color = indexed(23);
+/// `DeepSkyBlue4`, color 24
+pub const DEEP_SKY_BLUE4_B: Color = indexed(24);
+/// `DeepSkyBlue4`, color 25
+pub const DEEP_SKY_BLUE4_C: Color = indexed(25);
+/// `DodgerBlue3`, color 26
+pub const DODGER_BLUE3: Color = indexed(26);
+/// `DodgerBlue2`, color 27
+pub const DODGER_BLUE2: Color = indexed(27);
+/// `Green4`, color 28
+pub const GREEN4: Color = indexed(28);
+/// `SpringGreen4`, color 29
+pub const SPRING_GREEN4: Color = indexed(29);
+/// `Turquoise4`, color 30
+pub const TURQUOISE4: Color = indexed(30);
+/// `DeepSkyBlue3`, color 31
+pub const DEEP_SKY_BLUE3_A: Color = indexed(31);
+/// `DeepSkyBlue3`, color 32
+pub const DEEP_SKY_BLUE3_B: Color = indexed(32);
+/// `DodgerBlue1`, color 33
+pub const DODGER_BLUE1: Color = indexed(33);
+/// `Green3`, color 34
+pub const GREEN3_A: Color = indexed(34);
+/// `SpringGreen3`, color 35
+pub const SPRING_GREEN3_A: Color = indexed(35);
+/// `DarkCyan`, color 36
+pub const DARK_CYAN: Color = indexed(36);
+/// `LightSeaGreen`, color 37
+pub const LIGHT_SEA_GREEN: Color = indexed(37);
+/// `DeepSkyBlue2`, color 38
+pub const DEEP_SKY_BLUE2: Color = indexed(38);
+/// `DeepSkyBlue1`, color 39
+pub const DEEP_SKY_BLUE1: Color = indexed(39);
+/// `Green3`, color 40
+pub const GREEN3_B: Color = indexed(40);
+/// `SpringGreen3`, color 41
+pub const SPRING_GREEN3_B: Color = indexed(41);
+/// `SpringGreen2`, color 42
+pub const SPRING_GREEN2_A: Color = indexed(42);
+/// `Cyan3`, color 43
+pub const CYAN3: Color = indexed(43);
+/// `DarkTurquoise`, color 44
+pub const DARK_TURQUOISE: Color = indexed(44);
+/// `Turquoise2`, color 45
+pub const TURQUOISE2: Color = indexed(45);
+/// `Green1`, color 46
+pub const GREEN1: Color = indexed(46);
+/// `SpringGreen2`, color 47
+pub const SPRING_GREEN2_B: Color = indexed(47);
+/// `SpringGreen1`, color 48
+pub const SPRING_GREEN1: Color = indexed(48);
+/// `MediumSpringGreen`, color 49
+pub const MEDIUM_SPRING_GREEN: Color = indexed(49);
+/// `Cyan2`, color 50
+pub const CYAN2: Color = indexed(50);
+/// `Cyan1`, color 51
+pub const CYAN1: Color = indexed(51);
+/// `DarkRed`, color 52
+pub const DARK_RED_A: Color = indexed(52);
+/// `DeepPink4`, color 53
+pub const DEEP_PINK4_A: Color = indexed(53);
+/// `Purple4`, color 54
+pub const PURPLE4_A: Color = indexed(54);
+/// `Purple4`, color 55
+pub const PURPLE4_B: Color = indexed(55);
+/// `Purple3`, color 56
+pub const PURPLE3: Color = indexed(56);
+/// `BlueViolet`, color 57
+pub const BLUE_VIOLET: Color = indexed(57);
+/// `Orange4`, color 58
+pub const ORANGE4_A: Color = indexed(58);
+/// `Grey37`, color 59
+pub const GREY37: Color = indexed(59);
+/// `MediumPurple4`, color 60
+pub const MEDIUM_PURPLE4: Color = indexed(60);
+/// `SlateBlue3`, color 61
+pub const SLATE_BLUE3_A: Color = indexed(61);
+/// `SlateBlue3`, color 62
+pub const SLATE_BLUE3_B: Color = indexed(62);
+/// `RoyalBlue1`, color 63
+pub const ROYAL_BLUE1: Color = indexed(63);
+/// `Chartreuse4`, color 64
+pub const CHARTREUSE4: Color = indexed(64);
+/// `DarkSeaGreen4`, color 65
+pub const DARK_SEA_GREEN4_A: Color = indexed(65);
+/// `PaleTurquoise4`, color 66
+pub const PALE_TURQUOISE4: Color = indexed(66);
+/// `SteelBlue`, color 67
+pub const STEEL_BLUE: Color = indexed(67);
+/// `SteelBlue3`, color 68
+pub const STEEL_BLUE3: Color = indexed(68);
+/// `CornflowerBlue`, color 69
+pub const CORNFLOWER_BLUE: Color = indexed(69);
+/// `Chartreuse3`, color 70
+pub const CHARTREUSE3_A: Color = indexed(70);
+/// `DarkSeaGreen4`, color 71
+pub const DARK_SEA_GREEN4_B: Color = indexed(71);
+/// `CadetBlue`, color 72
+pub const CADET_BLUE_A: Color = indexed(72);
+/// `CadetBlue`, color 73
+pub const CADET_BLUE_B: Color = indexed(73);
+/// `SkyBlue3`, color 74
+pub const SKY_BLUE3: Color = indexed(74);
+/// `SteelBlue1`, color 75
+pub const STEEL_BLUE1_A: Color = indexed(75);
+/// `Chartreuse3`, color 76
+pub const CHARTREUSE3_B: Color = indexed(76);
+/// `PaleGreen3`, color 77
+pub const PALE_GREEN3_A: Color = indexed(77);
+/// `SeaGreen3`, color 78
+pub const SEA_GREEN3: Color = indexed(78);
+/// `Aquamarine3`, color 79
+pub const AQUAMARINE3: Color = indexed(79);
+/// `MediumTurquoise`, color 80
+pub const MEDIUM_TURQUOISE: Color = indexed(80);
+/// `SteelBlue1`, color 81
+pub const STEEL_BLUE1_B: Color = indexed(81);
+/// `Chartreuse2`, color 82
+pub const CHARTREUSE2_A: Color = indexed(82);
+/// `SeaGreen2`, color 83
+pub const SEA_GREEN2: Color = indexed(83);
+/// `SeaGreen1`, color 84
+pub const SEA_GREEN1_A: Color = indexed(84);
+/// `SeaGreen1`, color 85
+pub const SEA_GREEN1_B: Color = indexed(85);
+/// `Aquamarine1`, color 86
+pub const AQUAMARINE1_A: Color = indexed(86);
+/// `DarkSlateGray2`, color 87
+pub const DARK_SLATE_GRAY2: Color = indexed(87);
+/// `DarkRed`, color 88
+pub const DARK_RED_B: Color = indexed(88);
+/// `DeepPink4`, color 89
+pub const DEEP_PINK4_B: Color = indexed(89);
+/// `DarkMagenta`, color 90
+pub const DARK_MAGENTA_A: Color = indexed(90);
+/// `DarkMagenta`, color 91
+pub const DARK_MAGENTA_B: Color = indexed(91);
+/// `DarkViolet`, color 92
+pub const DARK_VIOLET_A: Color = indexed(92);
+/// `Purple`, color 93
+pub const PURPLE_A: Color = indexed(93);
+/// `Orange4`, color 94
+pub const ORANGE4_B: Color = indexed(94);
+/// `LightPink4`, color 95
+pub const LIGHT_PINK4: Color = indexed(95);
+/// `Plum4`, color 96
+pub const PLUM4: Color = indexed(96);
+/// `MediumPurple3`, color 97
+pub const MEDIUM_PURPLE3_A: Color = indexed(97);
+/// `MediumPurple3`, color 98
+pub const MEDIUM_PURPLE3_B: Color = indexed(98);
+/// `SlateBlue1`, color 99
+pub const SLATE_BLUE1: Color = indexed(99);
+/// `Yellow4`, color 100
+pub const YELLOW4_A: Color = indexed(100);
+/// `Wheat4`, color 101
+pub const WHEAT4: Color = indexed(101);
+/// `Grey53`, color 102
+pub const GREY53: Color = indexed(102);
+/// `LightSlateGrey`, color 103
+pub const LIGHT_SLATE_GREY: Color = indexed(103);
+/// `MediumPurple`, color 104
+pub const MEDIUM_PURPLE: Color = indexed(104);
+/// `LightSlateBlue`, color 105
+pub const LIGHT_SLATE_BLUE: Color = indexed(105);
+/// `Yellow4`, color 106
+pub const YELLOW4_B: Color = indexed(106);
+/// `DarkOliveGreen3`, color 107
+pub const DARK_OLIVE_GREEN3_A: Color = indexed(107);
+/// `DarkSeaGreen`, color 108
+pub const DARK_SEA_GREEN: Color = indexed(108);
+/// `LightSkyBlue3`, color 109
+pub const LIGHT_SKY_BLUE3_A: Color = indexed(109);
+/// `LightSkyBlue3`, color 110
+pub const LIGHT_SKY_BLUE3_B: Color = indexed(110);
+/// `SkyBlue2`, color 111
+pub const SKY_BLUE2: Color = indexed(111);
+/// `Chartreuse2`, color 112
+pub const CHARTREUSE2_B: Color = indexed(112);
+/// `DarkOliveGreen3`, color 113
+pub const DARK_OLIVE_GREEN3_B: Color = indexed(113);
+/// `PaleGreen3`, color 114
+pub const PALE_GREEN3_B: Color = indexed(114);
+/// `DarkSeaGreen3`, color 115
+pub const DARK_SEA_GREEN3_A: Color = indexed(115);
+/// `DarkSlateGray3`, color 116
+pub const DARK_SLATE_GRAY3: Color = indexed(116);
+/// `SkyBlue1`, color 117
+pub const SKY_BLUE1: Color = indexed(117);
+/// `Chartreuse1`, color 118
+pub const CHARTREUSE1: Color = indexed(118);
+/// `LightGreen`, color 119
+pub const LIGHT_GREEN_A: Color = indexed(119);
+/// `LightGreen`, color 120
+pub const LIGHT_GREEN_B: Color = indexed(120);
+/// `PaleGreen1`, color 121
+pub const PALE_GREEN1_A: Color = indexed(121);
+/// `Aquamarine1`, color 122
+pub const AQUAMARINE1_B: Color = indexed(122);
+/// `DarkSlateGray1`, color 123
+pub const DARK_SLATE_GRAY1: Color = indexed(123);
+/// `Red3`, color 124
+pub const RED3_A: Color = indexed(124);
+/// `DeepPink4`, color 125
+pub const DEEP_PINK4_C: Color = indexed(125);
+/// `MediumVioletRed`, color 126
+pub const MEDIUM_VIOLET_RED: Color = indexed(126);
+/// `Magenta3`, color 127
+pub const MAGENTA3_A: Color = indexed(127);
+/// `DarkViolet`, color 128
+pub const DARK_VIOLET_B: Color = indexed(128);
+/// `Purple`, color 129
+pub const PURPLE_B: Color = indexed(129);
+/// `DarkOrange3`, color 130
+pub const DARK_ORANGE3_A: Color = indexed(130);
+/// `IndianRed`, color 131
+pub const INDIAN_RED_A: Color = indexed(131);
+/// `HotPink3`, color 132
+pub const HOT_PINK3_A: Color = indexed(132);
+/// `MediumOrchid3`, color 133
+pub const MEDIUM_ORCHID3: Color = indexed(133);
+/// `MediumOrchid`, color 134
+pub const MEDIUM_ORCHID: Color = indexed(134);
+/// `MediumPurple2`, color 135
+pub const MEDIUM_PURPLE2_A: Color = indexed(135);
+/// `DarkGoldenrod`, color 136
+pub const DARK_GOLDENROD: Color = indexed(136);
+/// `LightSalmon3`, color 137
+pub const LIGHT_SALMON3_A: Color = indexed(137);
+/// `RosyBrown`, color 138
+pub const ROSY_BROWN: Color = indexed(138);
+/// `Grey63`, color 139
+pub const GREY63: Color = indexed(139);
+/// `MediumPurple2`, color 140
+pub const MEDIUM_PURPLE2_B: Color = indexed(140);
+/// `MediumPurple1`, color 141
+pub const MEDIUM_PURPLE1: Color = indexed(141);
+/// `Gold3`, color 142
+pub const GOLD3_A: Color = indexed(142);
+/// `DarkKhaki`, color 143
+pub const DARK_KHAKI: Color = indexed(143);
+/// `NavajoWhite3`, color 144
+pub const NAVAJO_WHITE3: Color = indexed(144);
+/// `Grey69`, color 145
+pub const GREY69: Color = indexed(145);
+/// `LightSteelBlue3`, color 146
+pub const LIGHT_STEEL_BLUE3: Color = indexed(146);
+/// `LightSteelBlue`, color 147
+pub const LIGHT_STEEL_BLUE: Color = indexed(147);
+/// `Yellow3`, color 148
+pub const YELLOW3_A: Color = indexed(148);
+/// `DarkOliveGreen3`, color 149
+pub const DARK_OLIVE_GREEN3_C: Color = indexed(149);
+/// `DarkSeaGreen3`, color 150
+pub const DARK_SEA_GREEN3_B: Color = indexed(150);
+/// `DarkSeaGreen2`, color 151
+pub const DARK_SEA_GREEN2_A: Color = indexed(151);
+/// `LightCyan3`, color 152
+pub const LIGHT_CYAN3: Color = indexed(152);
+/// `LightSkyBlue1`, color 153
+pub const LIGHT_SKY_BLUE1: Color = indexed(153);
+/// `GreenYellow`, color 154
+pub const GREEN_YELLOW: Color = indexed(154);
+/// `DarkOliveGreen2`, color 155
+pub const DARK_OLIVE_GREEN2: Color = indexed(155);
+/// `PaleGreen1`, color 156
+pub const PALE_GREEN1_B: Color = indexed(156);
+/// `DarkSeaGreen2`, color 157
+pub const DARK_SEA_GREEN2_B: Color = indexed(157);
+/// `DarkSeaGreen1`, color 158
+pub const DARK_SEA_GREEN1_A: Color = indexed(158);
+/// `PaleTurquoise1`, color 159
+pub const PALE_TURQUOISE1: Color = indexed(159);
+/// `Red3`, color 160
+pub const RED3_B: Color = indexed(160);
+/// `DeepPink3`, color 161
+pub const DEEP_PINK3_A: Color = indexed(161);
+/// `DeepPink3`, color 162
+pub const DEEP_PINK3_B: Color = indexed(162);
+/// `Magenta3`, color 163
+pub const MAGENTA3_B: Color = indexed(163);
+/// `Magenta3`, color 164
+pub const MAGENTA3_C: Color = indexed(164);
+/// `Magenta2`, color 165
+pub const MAGENTA2_A: Color = indexed(165);
+/// `DarkOrange3`, color 166
+pub const DARK_ORANGE3_B: Color = indexed(166);
+/// `IndianRed`, color 167
+pub const INDIAN_RED_B: Color = indexed(167);
+/// `HotPink3`, color 168
+pub const HOT_PINK3_B: Color = indexed(168);
+/// `HotPink2`, color 169
+pub const HOT_PINK2: Color = indexed(169);
+/// `Orchid`, color 170
+pub const ORCHID: Color = indexed(170);
+/// `MediumOrchid1`, color 171
+pub const MEDIUM_ORCHID1_A: Color = indexed(171);
+/// `Orange3`, color 172
+pub const ORANGE3: Color = indexed(172);
+/// `LightSalmon3`, color 173
+pub const LIGHT_SALMON3_B: Color = indexed(173);
+/// `LightPink3`, color 174
+pub const LIGHT_PINK3: Color = indexed(174);
+/// `Pink3`, color 175
+pub const PINK3: Color = indexed(175);
+/// `Plum3`, color 176
+pub const PLUM3: Color = indexed(176);
+/// `Violet`, color 177
+pub const VIOLET: Color = indexed(177);
+/// `Gold3`, color 178
+pub const GOLD3_B: Color = indexed(178);
+/// `LightGoldenrod3`, color 179
+pub const LIGHT_GOLDENROD3: Color = indexed(179);
+/// `Tan`, color 180
+pub const TAN: Color = indexed(180);
+/// `MistyRose3`, color 181
+pub const MISTY_ROSE3: Color = indexed(181);
+/// `Thistle3`, color 182
+pub const THISTLE3: Color = indexed(182);
+/// `Plum2`, color 183
+pub const PLUM2: Color = indexed(183);
+/// `Yellow3`, color 184
+pub const YELLOW3_B: Color = indexed(184);
+/// `Khaki3`, color 185
+pub const KHAKI3: Color = indexed(185);
+/// `LightGoldenrod2`, color 186
+pub const LIGHT_GOLDENROD2_A: Color = indexed(186);
+/// `LightYellow3`, color 187
+pub const LIGHT_YELLOW3: Color = indexed(187);
+/// `Grey84`, color 188
+pub const GREY84: Color = indexed(188);
+/// `LightSteelBlue1`, color 189
+pub const LIGHT_STEEL_BLUE1: Color = indexed(189);
+/// `Yellow2`, color 190
+pub const YELLOW2: Color = indexed(190);
+/// `DarkOliveGreen1`, color 191
+pub const DARK_OLIVE_GREEN1_A: Color = indexed(191);
+/// `DarkOliveGreen1`, color 192
+pub const DARK_OLIVE_GREEN1_B: Color = indexed(192);
+/// `DarkSeaGreen1`, color 193
+pub const DARK_SEA_GREEN1_B: Color = indexed(193);
+/// `Honeydew2`, color 194
+pub const HONEYDEW2: Color = indexed(194);
+/// `LightCyan1`, color 195
+pub const LIGHT_CYAN1: Color = indexed(195);
+/// `Red1`, color 196
+pub const RED1: Color = indexed(196);
+/// `DeepPink2`, color 197
+pub const DEEP_PINK2: Color = indexed(197);
+/// `DeepPink1`, color 198
+pub const DEEP_PINK1_A: Color = indexed(198);
+/// `DeepPink1`, color 199
+pub const DEEP_PINK1_B: Color = indexed(199);
+/// `Magenta2`, color 200
+pub const MAGENTA2_B: Color = indexed(200);
+/// `Magenta1`, color 201
+pub const MAGENTA1: Color = indexed(201);
+/// `OrangeRed1`, color 202
+pub const ORANGE_RED1: Color = indexed(202);
+/// `IndianRed1`, color 203
+pub const INDIAN_RED1_A: Color = indexed(203);
+/// `IndianRed1`, color 204
+pub const INDIAN_RED1_B: Color = indexed(204);
+/// `HotPink`, color 205
+pub const HOT_PINK_A: Color = indexed(205);
+/// `HotPink`, color 206
+pub const HOT_PINK_B: Color = indexed(206);
+/// `MediumOrchid1`, color 207
+pub const MEDIUM_ORCHID1_B: Color = indexed(207);
+/// `DarkOrange`, color 208
+pub const DARK_ORANGE: Color = indexed(208);
+/// `Salmon1`, color 209
+pub const SALMON1: Color = indexed(209);
+/// `LightCoral`, color 210
+pub const LIGHT_CORAL: Color = indexed(210);
+/// `PaleVioletRed1`, color 211
+pub const PALE_VIOLET_RED1: Color = indexed(211);
+/// `Orchid2`, color 212
+pub const ORCHID2: Color = indexed(212);
+/// `Orchid1`, color 213
+pub const ORCHID1: Color = indexed(213);
+/// `Orange1`, color 214
+pub const ORANGE1: Color = indexed(214);
+/// `SandyBrown`, color 215
+pub const SANDY_BROWN: Color = indexed(215);
+/// `LightSalmon1`, color 216
+pub const LIGHT_SALMON1: Color = indexed(216);
+/// `LightPink1`, color 217
+pub const LIGHT_PINK1: Color = indexed(217);
+/// `Pink1`, color 218
+pub const PINK1: Color = indexed(218);
+/// `Plum1`, color 219
+pub const PLUM1: Color = indexed(219);
+/// `Gold1`, color 220
+pub const GOLD1: Color = indexed(220);
+/// `LightGoldenrod2`, color 221
+pub const LIGHT_GOLDENROD2_B: Color = indexed(221);
+/// `LightGoldenrod2`, color 222
+pub const LIGHT_GOLDENROD2_C: Color = indexed(222);
+/// `NavajoWhite1`, color 223
+pub const NAVAJO_WHITE1: Color = indexed(223);
+/// `MistyRose1`, color 224
+pub const MISTY_ROSE1: Color = indexed(224);
+/// `Thistle1`, color 225
+pub const THISTLE1: Color = indexed(225);
+/// `Yellow1`, color 226
+pub const YELLOW1: Color = indexed(226);
+/// `LightGoldenrod1`, color 227
+pub const LIGHT_GOLDENROD1: Color = indexed(227);
+/// `Khaki1`, color 228
+pub const KHAKI1: Color = indexed(228);
+/// `Wheat1`, color 229
+pub const WHEAT1: Color = indexed(229);
+/// `Cornsilk1`, color 230
+pub const CORNSILK1: Color = indexed(230);
+/// `Grey100`, color 231
+pub const GREY100: Color = indexed(231);
+/// `Grey3`, color 232
+pub const GREY3: Color = indexed(232);
+/// `Grey7`, color 233
+pub const GREY7: Color = indexed(233);
+/// `Grey11`, color 234
+pub const GREY11: Color = indexed(234);
+/// `Grey15`, color 235
+pub const GREY15: Color = indexed(235);
+/// `Grey19`, color 236
+pub const GREY19: Color = indexed(236);
+/// `Grey23`, color 237
+pub const GREY23: Color = indexed(237);
+/// `Grey27`, color 238
+pub const GREY27: Color = indexed(238);
+/// `Grey30`, color 239
+pub const GREY30: Color = indexed(239);
+/// `Grey35`, color 240
+pub const GREY35: Color = indexed(240);
+/// `Grey39`, color 241
+pub const GREY39: Color = indexed(241);
+/// `Grey42`, color 242
+pub const GREY42: Color = indexed(242);
+/// `Grey46`, color 243
+pub const GREY46: Color = indexed(243);
+/// `Grey50`, color 244
+pub const GREY50: Color = indexed(244);
+/// `Grey54`, color 245
+pub const GREY54: Color = indexed(245);
+/// `Grey58`, color 246
+pub const GREY58: Color = indexed(246);
+/// `Grey62`, color 247
+pub const GREY62: Color = indexed(247);
+/// `Grey66`, color 248
+pub const GREY66: Color = indexed(248);
+/// `Grey70`, color 249
+pub const GREY70: Color = indexed(249);
+/// `Grey74`, color 250
+pub const GREY74: Color = indexed(250);
+/// `Grey78`, color 251
+pub const GREY78: Color = indexed(251);
+/// `Grey82`, color 252
+pub const GREY82: Color = indexed(252);
+/// `Grey85`, color 253
+pub const GREY85: Color = indexed(253);
+/// `Grey89`, color 254
+pub const GREY89: Color = indexed(254);
+/// `Grey93`, color 255
+pub const GREY93: Color = indexed(255);
+
+/// The conventional name of the color at `index`, `None` for the 16 standard
+/// colors which are named by [`Color`] itself
+#[inline]
+#[must_use]
+pub const fn name(index: u8) -> Option<&'static str> {
+    if index < 16 {
+        None
+    } else {
+        Some(NAMES[index as usize - 16])
+    }
+}
+
+/// The palette index of a color name, ignoring case, spaces, `-` and `_`.
+/// A name shared by several colors can be suffixed with `a`, `b`, ... to
+/// pick one of them, the bare name meaning the first one.
+///
+/// ```rust
+/// # use colored::xterm;
+/// assert_eq!(xterm::index_of("grey_37"), Some(59));
+/// assert_eq!(xterm::index_of("DeepSkyBlue4"), Some(23));
+/// assert_eq!(xterm::index_of("deep sky blue 4c"), Some(25));
+/// assert_eq!(xterm::index_of("red"), None);
+/// ```
+#[must_use]
+pub fn index_of(name: &str) -> Option<u8> {
+    let name: String = name
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    if let Some(index) = indices_named(&name).next() {
+        return Some(index);
+    }
+
+    let (last, suffix) = name.char_indices().next_back()?;
+    let base = &name[..last];
+    let nth = usize::from(u8::try_from(suffix).ok()?.checked_sub(b'a')?);
+    let mut duplicates = indices_named(base);
+    if duplicates.clone().count() < 2 {
+        return None;
+    }
+    duplicates.nth(nth)
+}
+
+/// The indices of the colors named `name`, ignoring case
+fn indices_named(name: &str) -> impl Iterator<Item = u8> + Clone + '_ {
+    NAMES
+        .iter()
+        .zip(16..=255)
+        .filter(move |(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, index)| index)
+}
+
+/// The color with the given name, see [`index_of`]
+#[inline]
+#[must_use]
+pub fn from_name(name: &str) -> Option<Color> {
+    index_of(name).map(indexed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for index in 16..=255 {
+            let name = name(index).unwrap();
+            let first = index_of(name).unwrap();
+            assert!(first <= index);
+            assert_eq!(from_name(name), Some(indexed(first)));
+        }
+        assert_eq!(name(15), None);
+    }
+
+    #[test]
+    fn suffixes() {
+        assert_eq!(index_of("Blue3a"), Some(19));
+        assert_eq!(index_of("blue3b"), Some(20));
+        assert_eq!(index_of("blue3c"), None);
+        assert_eq!(index_of("grey37a"), None);
+        assert_eq!(index_of(""), None);
+        assert_eq!(DEEP_SKY_BLUE4_C, indexed(25));
+        assert_eq!(GREY93.to_hex_array(), [238, 238, 238]);
+    }
+
+    #[test]
+    fn non_ascii() {
+        assert_eq!(index_of("weiß"), None);
+        assert_eq!(index_of("é"), None);
+        assert_eq!(index_of("blue3é"), None);
+        assert_eq!("weiß".parse::<Color>(), Err(crate::ParseColorError::UnknownName("weiß".to_owned())));
+    }
+}